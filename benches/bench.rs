@@ -72,6 +72,17 @@ fn actson_benchmark(c: &mut Criterion) {
         })
     });
 
+    // a document with long strings, which benefit from the `simd` feature
+    let text = "The quick brown fox jumps over the lazy dog. ".repeat(50);
+    let json_strings = make_large(&format!(r#"{{"text": "{text}", "tags": ["{text}"]}}"#));
+    let json_strings_bytes = json_strings.as_bytes();
+
+    c.bench_function("actson_strings", |b| {
+        b.iter(|| {
+            consume(json_strings_bytes);
+        })
+    });
+
     #[cfg(feature = "serde_json")]
     c.bench_function("actson_serde", |b| {
         b.iter(|| {
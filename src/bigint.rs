@@ -0,0 +1,103 @@
+use core::str::FromStr;
+
+use num_bigint::{BigInt, ParseBigIntError};
+use thiserror::Error;
+
+use crate::feeder::JsonFeeder;
+use crate::parser::InvalidStringValueError;
+use crate::stack::ModeStack;
+use crate::JsonParser;
+
+/// An error that can happen when trying to parse the current value to a
+/// [`BigInt`]
+#[derive(Error, Debug)]
+pub enum InvalidBigIntValueError {
+    #[error("unable to convert current value to string: {0}")]
+    String(#[from] InvalidStringValueError),
+
+    #[error("unable to parse current value to big integer: {0}")]
+    BigInt(
+        // `num-bigint` only implements `Error` if the `std` feature is enabled
+        #[cfg_attr(feature = "std", source)] ParseBigIntError,
+    ),
+}
+
+impl From<ParseBigIntError> for InvalidBigIntValueError {
+    fn from(err: ParseBigIntError) -> Self {
+        InvalidBigIntValueError::BigInt(err)
+    }
+}
+
+impl<T, S> JsonParser<T, S>
+where
+    T: JsonFeeder,
+    S: ModeStack,
+{
+    /// Get the value of the integer that has just been parsed as a
+    /// [`BigInt`] with arbitrary precision. Call this function after you've
+    /// received [`JsonEvent::ValueInt`](crate::JsonEvent::ValueInt).
+    ///
+    /// This method allocates memory and is considerably slower than
+    /// [`current_int()`](Self::current_int()). Only use it if you expect
+    /// integers that do not even fit into an `i128` or `u128`.
+    ///
+    /// *Heads up:* The `num-bigint` feature has to be enabled for this.
+    ///
+    /// ```
+    /// use actson::{JsonParser, JsonEvent};
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use num_bigint::BigInt;
+    ///
+    /// let json = r#"{"id": 123456789012345678901234567890123456789012345678901234567890}"#.as_bytes();
+    ///
+    /// let feeder = SliceJsonFeeder::new(json);
+    /// let mut parser = JsonParser::new(feeder);
+    /// while let Some(event) = parser.next_event().unwrap() {
+    ///     if event == JsonEvent::ValueInt {
+    ///         let expected: BigInt = "123456789012345678901234567890123456789012345678901234567890"
+    ///             .parse()
+    ///             .unwrap();
+    ///         assert_eq!(parser.current_bigint().unwrap(), expected);
+    ///     }
+    /// }
+    /// ```
+    pub fn current_bigint(&self) -> Result<BigInt, InvalidBigIntValueError> {
+        Ok(BigInt::from_str(self.current_number_str()?)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use num_bigint::BigInt;
+
+    use crate::bigint::InvalidBigIntValueError;
+    use crate::feeder::SliceJsonFeeder;
+    use crate::{JsonEvent, JsonParser};
+
+    /// Test that integers of arbitrary size are parsed and that floats are
+    /// rejected
+    #[test]
+    fn parse() {
+        let json = "[0, -1, 340282366920938463463374607431768211456, \
+            -99999999999999999999999999999999999999999999999999, 1.5]";
+        let mut parser = JsonParser::new(SliceJsonFeeder::new(json.as_bytes()));
+        let mut r = Vec::new();
+        while let Some(e) = parser.next_event().unwrap() {
+            if e == JsonEvent::ValueInt || e == JsonEvent::ValueFloat {
+                r.push(parser.current_bigint());
+            }
+        }
+
+        assert_eq!(r.len(), 5);
+        assert_eq!(*r[0].as_ref().unwrap(), BigInt::from(0));
+        assert_eq!(*r[1].as_ref().unwrap(), BigInt::from(-1));
+        assert_eq!(*r[2].as_ref().unwrap(), BigInt::from(u128::MAX) + 1);
+        assert_eq!(
+            r[3].as_ref().unwrap().to_string(),
+            "-99999999999999999999999999999999999999999999999999"
+        );
+        assert!(matches!(r[4], Err(InvalidBigIntValueError::BigInt(_))));
+    }
+}
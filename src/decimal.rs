@@ -0,0 +1,122 @@
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+use crate::feeder::JsonFeeder;
+use crate::parser::{InvalidStringValueError, NumberKind};
+use crate::stack::ModeStack;
+use crate::JsonParser;
+
+/// An error that can happen when trying to parse the current value to a
+/// [`Decimal`]
+#[derive(Error, Debug)]
+pub enum InvalidDecimalValueError {
+    #[error("unable to convert current value to string: {0}")]
+    String(#[from] InvalidStringValueError),
+
+    #[error("unable to parse current value to decimal: {0}")]
+    Decimal(
+        // `rust_decimal` only implements `Error` if the `std` feature is enabled
+        #[cfg_attr(feature = "std", source)] rust_decimal::Error,
+    ),
+}
+
+impl From<rust_decimal::Error> for InvalidDecimalValueError {
+    fn from(err: rust_decimal::Error) -> Self {
+        InvalidDecimalValueError::Decimal(err)
+    }
+}
+
+impl<T, S> JsonParser<T, S>
+where
+    T: JsonFeeder,
+    S: ModeStack,
+{
+    /// Get the value of the number that has just been parsed as an exact
+    /// [`Decimal`]. Call this function after you've received
+    /// [`JsonEvent::ValueInt`](crate::JsonEvent::ValueInt) or
+    /// [`JsonEvent::ValueFloat`](crate::JsonEvent::ValueFloat). Returns an
+    /// error if the number cannot be represented without loss (e.g. because
+    /// its exponent is too large).
+    ///
+    /// *Heads up:* The `rust_decimal` feature has to be enabled for this.
+    ///
+    /// ```
+    /// use actson::{JsonParser, JsonEvent};
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use rust_decimal::Decimal;
+    ///
+    /// let json = r#"{"price": 0.1}"#.as_bytes();
+    ///
+    /// let feeder = SliceJsonFeeder::new(json);
+    /// let mut parser = JsonParser::new(feeder);
+    /// while let Some(event) = parser.next_event().unwrap() {
+    ///     if event == JsonEvent::ValueFloat {
+    ///         assert_eq!(parser.current_decimal().unwrap(), Decimal::new(1, 1));
+    ///     }
+    /// }
+    /// ```
+    pub fn current_decimal(&self) -> Result<Decimal, InvalidDecimalValueError> {
+        let s = self.current_number_str()?;
+        if self.number_kind() == NumberKind::Exponent {
+            Ok(Decimal::from_scientific(s)?)
+        } else {
+            Ok(Decimal::from_str_exact(s)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use rust_decimal::Decimal;
+
+    use crate::decimal::InvalidDecimalValueError;
+    use crate::feeder::SliceJsonFeeder;
+    use crate::{JsonEvent, JsonParser};
+
+    /// Parse all numbers in the given JSON text to decimals
+    fn parse(json: &str) -> Vec<Result<Decimal, InvalidDecimalValueError>> {
+        let mut parser = JsonParser::new(SliceJsonFeeder::new(json.as_bytes()));
+        let mut r = Vec::new();
+        while let Some(e) = parser.next_event().unwrap() {
+            if e == JsonEvent::ValueInt || e == JsonEvent::ValueFloat {
+                r.push(parser.current_decimal());
+            }
+        }
+        r
+    }
+
+    /// Test that numbers are parsed exactly
+    #[test]
+    fn exact() {
+        let r = parse("[0, -12, 1.50, 0.1, 1e3, -2.5E-3, 1.2e+2, 79228162514264337593543950335]")
+            .into_iter()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            r,
+            [
+                Decimal::new(0, 0),
+                Decimal::new(-12, 0),
+                Decimal::new(150, 2),
+                Decimal::new(1, 1),
+                Decimal::new(1000, 0),
+                Decimal::new(-25, 4),
+                Decimal::new(120, 0),
+                Decimal::MAX,
+            ]
+        );
+        assert_eq!(r[2].to_string(), "1.50");
+    }
+
+    /// Test that numbers that are out of range are rejected
+    #[test]
+    fn out_of_range() {
+        for r in
+            parse("[1e400, 1e-400, 79228162514264337593543950336, 0.00000000000000000000000000001]")
+        {
+            assert!(matches!(r, Err(InvalidDecimalValueError::Decimal(_))));
+        }
+    }
+}
@@ -0,0 +1,544 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::mem;
+use core::str::from_utf8;
+
+use crate::feeder::{FillError, FillJsonFeeder, JsonFeeder};
+use crate::parser::{
+    EntryValue, InvalidFloatValueError, InvalidIntValueError, InvalidStringValueError, ParserError,
+};
+use crate::stack::ModeStack;
+use crate::{JsonEvent, JsonParser};
+
+/// Creates the values of a document object model (DOM). Implement this trait
+/// for your own value type and then use [`build_value()`] or a
+/// [`ValueAssembler`] to parse JSON into it.
+pub trait ValueBuilder {
+    /// The type of values this builder creates
+    type Value;
+
+    /// The error type of [`build_value()`] and [`ValueAssembler`]
+    type Error: From<ParserError>
+        + From<InvalidStringValueError>
+        + From<InvalidIntValueError>
+        + From<InvalidFloatValueError>;
+
+    /// Create an empty object
+    fn object(&mut self) -> Self::Value;
+
+    /// Create an empty array
+    fn array(&mut self) -> Self::Value;
+
+    /// Insert a value into an object previously created with
+    /// [`object()`](Self::object())
+    fn insert(&mut self, obj: &mut Self::Value, key: String, val: Self::Value);
+
+    /// Append a value to an array previously created with
+    /// [`array()`](Self::array())
+    fn push(&mut self, arr: &mut Self::Value, val: Self::Value);
+
+    /// Create a string value
+    fn string(&mut self, value: &str) -> Self::Value;
+
+    /// Create an integer value
+    fn int(&mut self, value: i64) -> Self::Value;
+
+    /// Create a floating point value. Return an error if the value is not
+    /// supported.
+    fn float(&mut self, value: f64) -> Result<Self::Value, Self::Error>;
+
+    /// Create a boolean value
+    fn bool(&mut self, value: bool) -> Self::Value;
+
+    /// Create a `null` value
+    fn null(&mut self) -> Self::Value;
+
+    /// Create a number value from its text as it appears in the JSON text
+    /// (e.g. `-1.5e3`). `event` is either [`JsonEvent::ValueInt`] or
+    /// [`JsonEvent::ValueFloat`]. Override this method to control how
+    /// numbers are materialized (e.g. to keep integers that do not fit into
+    /// an `i64`). Return `None` to let the number be parsed and passed to
+    /// [`int()`](Self::int()) or [`float()`](Self::float()), which is what
+    /// the default implementation does.
+    fn number(
+        &mut self,
+        _text: &str,
+        _event: JsonEvent,
+    ) -> Option<Result<Self::Value, Self::Error>> {
+        None
+    }
+}
+
+/// Assembles a value from the events of a [`JsonParser`] with the help of a
+/// [`ValueBuilder`]. Use this if you need to control how the parser is fed
+/// (e.g. with a [`PushJsonFeeder`](crate::feeder::PushJsonFeeder)).
+/// Otherwise, [`build_value()`] is more convenient.
+pub struct ValueAssembler<B>
+where
+    B: ValueBuilder,
+{
+    builder: B,
+    stack: Vec<(Option<String>, B::Value)>,
+    result: Option<B::Value>,
+    current_key: Option<String>,
+
+    /// The chunks of the current string value if the parser returns long
+    /// strings as [`JsonEvent::PartialValueString`]s
+    partial_string: Vec<u8>,
+}
+
+impl<B> ValueAssembler<B>
+where
+    B: ValueBuilder,
+{
+    /// Create a new assembler that uses the given builder
+    pub fn new(builder: B) -> Self {
+        ValueAssembler {
+            builder,
+            stack: Vec::new(),
+            result: None,
+            current_key: None,
+            partial_string: Vec::new(),
+        }
+    }
+
+    /// Create a scalar value from the given event
+    fn scalar<T, S>(
+        &mut self,
+        event: JsonEvent,
+        parser: &JsonParser<T, S>,
+    ) -> Result<B::Value, B::Error>
+    where
+        T: JsonFeeder,
+        S: ModeStack,
+    {
+        if matches!(event, JsonEvent::ValueInt | JsonEvent::ValueFloat) {
+            if let Some(v) = self.builder.number(parser.current_number_str()?, event) {
+                return v;
+            }
+        }
+        Ok(match event {
+            JsonEvent::ValueString if !self.partial_string.is_empty() => {
+                // the last chunk of a string that has been returned in chunks
+                let mut buf = mem::take(&mut self.partial_string);
+                buf.extend_from_slice(parser.current_bytes());
+                let s = from_utf8(&buf).map_err(InvalidStringValueError::from)?;
+                self.builder.string(s)
+            }
+            JsonEvent::ValueString => self.builder.string(parser.current_str()?),
+            JsonEvent::ValueInt => self.builder.int(parser.current_int()?),
+            JsonEvent::ValueFloat => self.builder.float(parser.current_float()?)?,
+            JsonEvent::ValueTrue => self.builder.bool(true),
+            JsonEvent::ValueFalse => self.builder.bool(false),
+            JsonEvent::ValueNull => self.builder.null(),
+            _ => unreachable!("this function will only be called for scalar values"),
+        })
+    }
+
+    /// Create a scalar value from the value of a [`JsonEvent::Entry`]
+    fn entry_scalar(&mut self, value: EntryValue<'_>) -> Result<B::Value, B::Error> {
+        if matches!(value.event(), JsonEvent::ValueInt | JsonEvent::ValueFloat) {
+            if let Some(v) = self.builder.number(value.as_str()?, value.event()) {
+                return v;
+            }
+        }
+        Ok(match value.event() {
+            JsonEvent::ValueString => self.builder.string(value.as_str()?),
+            JsonEvent::ValueInt => self.builder.int(value.as_int()?),
+            JsonEvent::ValueFloat => self.builder.float(value.as_float()?)?,
+            JsonEvent::ValueTrue => self.builder.bool(true),
+            JsonEvent::ValueFalse => self.builder.bool(false),
+            JsonEvent::ValueNull => self.builder.null(),
+            _ => unreachable!("entries only contain scalar values"),
+        })
+    }
+
+    /// Add the given value to the container on top of the stack or make it
+    /// the result if there is no container
+    fn add<T, S>(
+        &mut self,
+        key: Option<String>,
+        v: B::Value,
+        parser: &JsonParser<T, S>,
+    ) -> Result<(), B::Error>
+    where
+        T: JsonFeeder,
+        S: ModeStack,
+    {
+        if let Some((_, top)) = self.stack.last_mut() {
+            match key {
+                Some(key) => self.builder.insert(top, key, v),
+                None => self.builder.push(top, v),
+            }
+        } else if self.result.is_none() {
+            self.result = Some(v);
+        } else {
+            return Err(parser.syntax_error(None).into());
+        }
+        Ok(())
+    }
+
+    /// Process the given event
+    pub fn on_event<T, S>(
+        &mut self,
+        event: JsonEvent,
+        parser: &JsonParser<T, S>,
+    ) -> Result<(), B::Error>
+    where
+        T: JsonFeeder,
+        S: ModeStack,
+    {
+        match event {
+            JsonEvent::NeedMoreInput
+            | JsonEvent::Whitespace
+            | JsonEvent::Comment
+            | JsonEvent::StartDocument
+            | JsonEvent::EndDocument => {}
+
+            JsonEvent::PartialValueString => {
+                // chunks may end in the middle of a UTF-8 character, so
+                // they can only be decoded together
+                self.partial_string
+                    .extend_from_slice(parser.current_bytes());
+            }
+
+            JsonEvent::StartObject => {
+                let v = self.builder.object();
+                self.stack.push((self.current_key.take(), v));
+            }
+
+            JsonEvent::StartArray => {
+                let v = self.builder.array();
+                self.stack.push((self.current_key.take(), v));
+            }
+
+            JsonEvent::EndObject | JsonEvent::EndArray => {
+                let (key, v) = self.stack.pop().unwrap();
+                self.add(key, v, parser)?;
+            }
+
+            JsonEvent::FieldName => self.current_key = Some(parser.current_str()?.to_string()),
+
+            JsonEvent::ValueString
+            | JsonEvent::ValueInt
+            | JsonEvent::ValueFloat
+            | JsonEvent::ValueTrue
+            | JsonEvent::ValueFalse
+            | JsonEvent::ValueNull => {
+                let v = self.scalar(event, parser)?;
+                let key = self.current_key.take();
+                self.add(key, v, parser)?;
+            }
+
+            JsonEvent::Entry => {
+                if let Some(value) = parser.entry_value() {
+                    let v = self.entry_scalar(value)?;
+                    let key = Some(parser.current_str()?.to_string());
+                    self.add(key, v, parser)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the assembled value if it is complete and reset the assembler
+    /// so it can assemble the next one
+    pub fn take_complete(&mut self) -> Option<B::Value> {
+        if self.stack.is_empty() {
+            self.result.take()
+        } else {
+            None
+        }
+    }
+
+    /// Return the assembled value
+    pub fn finish(self) -> Result<B::Value, B::Error> {
+        self.result.ok_or(ParserError::NoMoreInput.into())
+    }
+}
+
+/// Parse a JSON text into a value created by the given [`ValueBuilder`].
+/// Whenever the parser needs more input, the feeder is filled synchronously.
+///
+/// ```
+/// use actson::dom::{build_value, ValueBuilder};
+/// use actson::feeder::SliceJsonFeeder;
+/// use actson::parser::ParserError;
+/// use actson::JsonParser;
+///
+/// /// Counts all values
+/// struct Counter;
+///
+/// impl ValueBuilder for Counter {
+///     type Value = usize;
+///     type Error = Box<dyn std::error::Error>;
+///
+///     fn object(&mut self) -> usize { 1 }
+///     fn array(&mut self) -> usize { 1 }
+///     fn insert(&mut self, obj: &mut usize, _key: String, val: usize) { *obj += val }
+///     fn push(&mut self, arr: &mut usize, val: usize) { *arr += val }
+///     fn string(&mut self, _value: &str) -> usize { 1 }
+///     fn int(&mut self, _value: i64) -> usize { 1 }
+///     fn float(&mut self, _value: f64) -> Result<usize, Self::Error> { Ok(1) }
+///     fn bool(&mut self, _value: bool) -> usize { 1 }
+///     fn null(&mut self) -> usize { 1 }
+/// }
+///
+/// let json = r#"{"name": "Elvis", "albums": [1, 2.5, true, null]}"#.as_bytes();
+///
+/// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+/// assert_eq!(build_value(&mut parser, Counter).unwrap(), 7);
+/// ```
+pub fn build_value<T, S, B>(parser: &mut JsonParser<T, S>, builder: B) -> Result<B::Value, B::Error>
+where
+    T: FillJsonFeeder,
+    S: ModeStack,
+    B: ValueBuilder,
+    B::Error: From<FillError>,
+{
+    let mut assembler = ValueAssembler::new(builder);
+    while let Some(event) = parser.next_event()? {
+        if event == JsonEvent::NeedMoreInput {
+            parser.feeder.fill()?;
+        } else {
+            assembler.on_event(event, parser)?;
+        }
+    }
+    assembler.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+    use std::io::BufReader;
+
+    use thiserror::Error;
+
+    use crate::dom::{build_value, ValueAssembler, ValueBuilder};
+    use crate::feeder::{BufReaderJsonFeeder, FillError, PushJsonFeeder, SliceJsonFeeder};
+    use crate::options::JsonParserOptionsBuilder;
+    use crate::parser::{
+        InvalidFloatValueError, InvalidIntValueError, InvalidStringValueError, ParserError,
+    };
+    use crate::JsonParser;
+
+    /// A simple DOM
+    #[derive(Debug, PartialEq)]
+    enum Dom {
+        Object(Vec<(String, Dom)>),
+        Array(Vec<Dom>),
+        String(String),
+        Int(i64),
+        Float(f64),
+        Bool(bool),
+        Null,
+    }
+
+    #[derive(Error, Debug)]
+    enum DomError {
+        #[error("{0}")]
+        Parse(#[from] ParserError),
+
+        #[error("{0}")]
+        InvalidStringValue(#[from] InvalidStringValueError),
+
+        #[error("{0}")]
+        InvalidIntValue(#[from] InvalidIntValueError),
+
+        #[error("{0}")]
+        InvalidFloatValue(#[from] InvalidFloatValueError),
+
+        #[error("{0}")]
+        Fill(#[from] FillError),
+
+        #[error("NaN")]
+        NaN,
+    }
+
+    /// A builder for [`Dom`] values
+    struct DomBuilder;
+
+    impl ValueBuilder for DomBuilder {
+        type Value = Dom;
+        type Error = DomError;
+
+        fn object(&mut self) -> Dom {
+            Dom::Object(Vec::new())
+        }
+
+        fn array(&mut self) -> Dom {
+            Dom::Array(Vec::new())
+        }
+
+        fn insert(&mut self, obj: &mut Dom, key: String, val: Dom) {
+            if let Dom::Object(entries) = obj {
+                entries.push((key, val));
+            }
+        }
+
+        fn push(&mut self, arr: &mut Dom, val: Dom) {
+            if let Dom::Array(values) = arr {
+                values.push(val);
+            }
+        }
+
+        fn string(&mut self, value: &str) -> Dom {
+            Dom::String(value.to_string())
+        }
+
+        fn int(&mut self, value: i64) -> Dom {
+            Dom::Int(value)
+        }
+
+        fn float(&mut self, value: f64) -> Result<Dom, DomError> {
+            if value.is_nan() {
+                return Err(DomError::NaN);
+            }
+            Ok(Dom::Float(value))
+        }
+
+        fn bool(&mut self, value: bool) -> Dom {
+            Dom::Bool(value)
+        }
+
+        fn null(&mut self) -> Dom {
+            Dom::Null
+        }
+    }
+
+    /// Test that a nested document is built correctly (with and without
+    /// entry events) and that the feeder is filled when necessary
+    #[test]
+    fn build() {
+        let json = r#"{"a": [1, 2.5, "b"], "c": {"d": true, "e": false}, "f": null}"#;
+        for entry_events in [false, true] {
+            let reader = BufReader::with_capacity(3, json.as_bytes());
+            let options = JsonParserOptionsBuilder::default()
+                .with_entry_events(entry_events)
+                .build();
+            let mut parser =
+                JsonParser::new_with_options(BufReaderJsonFeeder::new(reader), options);
+
+            assert_eq!(
+                build_value(&mut parser, DomBuilder).unwrap(),
+                Dom::Object(vec![
+                    (
+                        "a".to_string(),
+                        Dom::Array(vec![
+                            Dom::Int(1),
+                            Dom::Float(2.5),
+                            Dom::String("b".to_string())
+                        ])
+                    ),
+                    (
+                        "c".to_string(),
+                        Dom::Object(vec![
+                            ("d".to_string(), Dom::Bool(true)),
+                            ("e".to_string(), Dom::Bool(false)),
+                        ])
+                    ),
+                    ("f".to_string(), Dom::Null),
+                ])
+            );
+        }
+    }
+
+    /// Test that top-level scalars are built correctly
+    #[test]
+    fn top_level_scalar() {
+        let mut parser = JsonParser::new(SliceJsonFeeder::new(b"-5"));
+        assert_eq!(build_value(&mut parser, DomBuilder).unwrap(), Dom::Int(-5));
+    }
+
+    /// Test that strings returned in chunks are put together again
+    #[test]
+    fn chunked_strings() {
+        let json = r#"{"name": "Grüße aus Köln", "a": ["", "abc", "x"]}"#;
+        for max_string_chunk in [1, 2, 3, 100] {
+            let mut parser = JsonParser::new_with_options(
+                SliceJsonFeeder::new(json.as_bytes()),
+                JsonParserOptionsBuilder::default()
+                    .with_max_string_chunk(max_string_chunk)
+                    .build(),
+            );
+            assert_eq!(
+                build_value(&mut parser, DomBuilder).unwrap(),
+                Dom::Object(vec![
+                    (
+                        "name".to_string(),
+                        Dom::String("Grüße aus Köln".to_string())
+                    ),
+                    (
+                        "a".to_string(),
+                        Dom::Array(vec![
+                            Dom::String("".to_string()),
+                            Dom::String("abc".to_string()),
+                            Dom::String("x".to_string()),
+                        ])
+                    ),
+                ])
+            );
+        }
+    }
+
+    /// Test that errors are returned
+    #[test]
+    fn error() {
+        let mut parser = JsonParser::new(SliceJsonFeeder::new(br#"{"a": 1"#));
+        assert!(matches!(
+            build_value(&mut parser, DomBuilder),
+            Err(DomError::Parse(ParserError::UnexpectedEof {
+                open_containers: 1
+            }))
+        ));
+
+        let mut parser = JsonParser::new(SliceJsonFeeder::new(b""));
+        assert!(matches!(
+            build_value(&mut parser, DomBuilder),
+            Err(DomError::Parse(ParserError::EmptyInput))
+        ));
+    }
+
+    /// Test that multiple top-level values can be assembled with a push feeder
+    #[test]
+    fn assembler() {
+        let mut parser = JsonParser::new_with_options(
+            PushJsonFeeder::new(),
+            JsonParserOptionsBuilder::default()
+                .with_streaming(true)
+                .build(),
+        );
+        let mut assembler = ValueAssembler::new(DomBuilder);
+        let mut values = Vec::new();
+
+        let mut input = br#"[1, {"a": null}] "x" "#.iter();
+        while let Some(event) = parser.next_event().unwrap() {
+            if event == crate::JsonEvent::NeedMoreInput {
+                match input.next() {
+                    Some(b) => {
+                        parser.feeder.push_byte(*b).unwrap();
+                    }
+                    None => parser.feeder.done(),
+                }
+                continue;
+            }
+            assembler.on_event(event, &parser).unwrap();
+            if let Some(v) = assembler.take_complete() {
+                values.push(v);
+            }
+        }
+
+        assert_eq!(
+            values,
+            vec![
+                Dom::Array(vec![
+                    Dom::Int(1),
+                    Dom::Object(vec![("a".to_string(), Dom::Null)])
+                ]),
+                Dom::String("x".to_string()),
+            ]
+        );
+    }
+}
@@ -1,5 +1,11 @@
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+use thiserror::Error;
+
 /// All possible JSON events returned by [`JsonParser::next_event()`](crate::JsonParser::next_event())
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum JsonEvent {
     /// The JSON parser needs more input before the next event can be returned.
     /// Invoke the parser's feeder to give it more input.
@@ -41,4 +47,169 @@ pub enum JsonEvent {
 
     /// A `null` value.
     ValueNull = 11,
+
+    /// A chunk of a string value. Only returned if
+    /// [`JsonParserOptionsBuilder::with_max_string_chunk()`](crate::options::JsonParserOptionsBuilder::with_max_string_chunk())
+    /// has been set and the string value is longer than the maximum chunk size.
+    /// Call [JsonParser::current_str()](crate::JsonParser::current_str())
+    /// to get the chunk. The last chunk will be returned with a
+    /// [`JsonEvent::ValueString`] event.
+    PartialValueString = 12,
+
+    /// A run of white space between two tokens. Only returned if
+    /// [`JsonParserOptionsBuilder::with_emit_whitespace()`](crate::options::JsonParserOptionsBuilder::with_emit_whitespace())
+    /// has been set. Call [JsonParser::current_str()](crate::JsonParser::current_str())
+    /// to get the white space characters.
+    Whitespace = 13,
+
+    /// A `//` or `/* */` comment. Only returned if
+    /// [`JsonParserOptionsBuilder::with_emit_comments()`](crate::options::JsonParserOptionsBuilder::with_emit_comments())
+    /// has been set. Call [JsonParser::current_str()](crate::JsonParser::current_str())
+    /// to get the text of the comment without its delimiters.
+    Comment = 14,
+
+    /// A field name together with the scalar value that follows it. Only
+    /// returned if
+    /// [`JsonParserOptionsBuilder::with_entry_events()`](crate::options::JsonParserOptionsBuilder::with_entry_events())
+    /// has been set. Call [JsonParser::current_str()](crate::JsonParser::current_str())
+    /// to get the field name and [JsonParser::entry_value()](crate::JsonParser::entry_value())
+    /// to get the value.
+    Entry = 15,
+
+    /// The start of a top-level value. Only returned if
+    /// [`JsonParserOptionsBuilder::with_document_events()`](crate::options::JsonParserOptionsBuilder::with_document_events())
+    /// has been set.
+    StartDocument = 16,
+
+    /// The end of a top-level value. Only returned if
+    /// [`JsonParserOptionsBuilder::with_document_events()`](crate::options::JsonParserOptionsBuilder::with_document_events())
+    /// has been set.
+    EndDocument = 17,
+}
+
+impl JsonEvent {
+    /// All events in the order of their discriminants
+    const ALL: [JsonEvent; 18] = [
+        JsonEvent::NeedMoreInput,
+        JsonEvent::StartObject,
+        JsonEvent::EndObject,
+        JsonEvent::StartArray,
+        JsonEvent::EndArray,
+        JsonEvent::FieldName,
+        JsonEvent::ValueString,
+        JsonEvent::ValueInt,
+        JsonEvent::ValueFloat,
+        JsonEvent::ValueTrue,
+        JsonEvent::ValueFalse,
+        JsonEvent::ValueNull,
+        JsonEvent::PartialValueString,
+        JsonEvent::Whitespace,
+        JsonEvent::Comment,
+        JsonEvent::Entry,
+        JsonEvent::StartDocument,
+        JsonEvent::EndDocument,
+    ];
+
+    /// Get the name of the event (e.g. `"StartObject"`). In contrast to the
+    /// output of [`Debug`], the name is guaranteed to stay the same. It can
+    /// be converted back with [`JsonEvent::from_str()`].
+    ///
+    /// ```
+    /// use actson::JsonEvent;
+    ///
+    /// assert_eq!(JsonEvent::ValueInt.name(), "ValueInt");
+    /// assert_eq!("ValueInt".parse::<JsonEvent>().unwrap(), JsonEvent::ValueInt);
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            JsonEvent::NeedMoreInput => "NeedMoreInput",
+            JsonEvent::StartObject => "StartObject",
+            JsonEvent::EndObject => "EndObject",
+            JsonEvent::StartArray => "StartArray",
+            JsonEvent::EndArray => "EndArray",
+            JsonEvent::FieldName => "FieldName",
+            JsonEvent::ValueString => "ValueString",
+            JsonEvent::ValueInt => "ValueInt",
+            JsonEvent::ValueFloat => "ValueFloat",
+            JsonEvent::ValueTrue => "ValueTrue",
+            JsonEvent::ValueFalse => "ValueFalse",
+            JsonEvent::ValueNull => "ValueNull",
+            JsonEvent::PartialValueString => "PartialValueString",
+            JsonEvent::Whitespace => "Whitespace",
+            JsonEvent::Comment => "Comment",
+            JsonEvent::Entry => "Entry",
+            JsonEvent::StartDocument => "StartDocument",
+            JsonEvent::EndDocument => "EndDocument",
+        }
+    }
+}
+
+impl Display for JsonEvent {
+    /// Write a human-readable description of the event (e.g.
+    /// `start of object` or `string value`)
+    ///
+    /// ```
+    /// use actson::JsonEvent;
+    ///
+    /// assert_eq!(JsonEvent::StartObject.to_string(), "start of object");
+    /// assert_eq!(JsonEvent::ValueString.to_string(), "string value");
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            JsonEvent::NeedMoreInput => "end of available input",
+            JsonEvent::StartObject => "start of object",
+            JsonEvent::EndObject => "end of object",
+            JsonEvent::StartArray => "start of array",
+            JsonEvent::EndArray => "end of array",
+            JsonEvent::FieldName => "field name",
+            JsonEvent::ValueString => "string value",
+            JsonEvent::ValueInt => "integer value",
+            JsonEvent::ValueFloat => "floating point value",
+            JsonEvent::ValueTrue => "true value",
+            JsonEvent::ValueFalse => "false value",
+            JsonEvent::ValueNull => "null value",
+            JsonEvent::PartialValueString => "partial string value",
+            JsonEvent::Whitespace => "white space",
+            JsonEvent::Comment => "comment",
+            JsonEvent::Entry => "object entry",
+            JsonEvent::StartDocument => "start of document",
+            JsonEvent::EndDocument => "end of document",
+        })
+    }
+}
+
+/// An error that is returned if a string is not the name of a [`JsonEvent`]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("unknown event name: `{0}'")]
+pub struct ParseEventError(pub String);
+
+impl FromStr for JsonEvent {
+    type Err = ParseEventError;
+
+    /// Get the event with the given name (see [`JsonEvent::name()`])
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        JsonEvent::ALL
+            .into_iter()
+            .find(|e| e.name() == s)
+            .ok_or_else(|| ParseEventError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::event::ParseEventError;
+    use crate::JsonEvent;
+
+    /// Test that all events can be converted to their names and back
+    #[test]
+    fn name_round_trip() {
+        for (i, e) in JsonEvent::ALL.into_iter().enumerate() {
+            assert_eq!(e as usize, i);
+            assert_eq!(e.name().parse::<JsonEvent>(), Ok(e));
+        }
+        assert_eq!(
+            "valueint".parse::<JsonEvent>(),
+            Err(ParseEventError("valueint".into()))
+        );
+    }
 }
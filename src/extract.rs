@@ -0,0 +1,702 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use thiserror::Error;
+
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+
+use crate::dom::{ValueAssembler, ValueBuilder};
+use crate::feeder::{FillError, FillJsonFeeder, SliceJsonFeeder};
+use crate::options::JsonParserOptionsBuilder;
+use crate::parser::{
+    InvalidFloatValueError, InvalidIntValueError, InvalidStringValueError, ParserError,
+};
+#[cfg(feature = "serde")]
+use crate::serde::{ActsonDeserializer, DeserializeError};
+use crate::stack::{HeapStack, ModeStack};
+use crate::{JsonEvent, JsonParser};
+
+/// A value returned by [`extract()`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExtractedValue<'a> {
+    /// A string value
+    String(String),
+
+    /// An integer value
+    Int(i64),
+
+    /// A floating point value or an integer that does not fit into an `i64`
+    Float(f64),
+
+    /// A boolean value
+    Bool(bool),
+
+    /// A `null` value
+    Null,
+
+    /// An object or an array exactly as it appears in the JSON text
+    Raw(&'a [u8]),
+}
+
+/// An error that can happen in [`extract()`]
+#[derive(Error, Debug)]
+pub enum ExtractError {
+    /// The given string is not a valid JSON pointer
+    #[error("invalid JSON pointer: `{0}'")]
+    InvalidPointer(String),
+
+    /// The value of the given field is not a string (see
+    /// [`collect_string_map()`])
+    #[error("value of field `{0}' is not a string")]
+    NotAString(String),
+
+    /// The value at the given JSON pointer is not an array (see
+    /// [`JsonParser::array_elements_at_pointer()`])
+    #[error("value at `{0}' is not an array")]
+    NotAnArray(String),
+
+    #[error("{0}")]
+    Parse(#[from] ParserError),
+
+    #[error("{0}")]
+    InvalidStringValue(#[from] InvalidStringValueError),
+
+    #[error("{0}")]
+    InvalidIntValue(#[from] InvalidIntValueError),
+
+    #[error("{0}")]
+    InvalidFloatValue(#[from] InvalidFloatValueError),
+
+    #[error("{0}")]
+    Fill(#[from] FillError),
+}
+
+/// Decode the reference tokens of the given JSON pointer (see RFC 6901)
+fn parse_pointer(pointer: &str) -> Result<Vec<String>, ExtractError> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(tokens) = pointer.strip_prefix('/') else {
+        return Err(ExtractError::InvalidPointer(pointer.to_string()));
+    };
+    tokens
+        .split('/')
+        .map(|token| {
+            let mut r = String::with_capacity(token.len());
+            let mut chars = token.chars();
+            while let Some(c) = chars.next() {
+                if c != '~' {
+                    r.push(c);
+                    continue;
+                }
+                match chars.next() {
+                    Some('0') => r.push('~'),
+                    Some('1') => r.push('/'),
+                    _ => return Err(ExtractError::InvalidPointer(pointer.to_string())),
+                }
+            }
+            Ok(r)
+        })
+        .collect()
+}
+
+/// Check if the given reference token refers to the array element with the
+/// given index
+fn is_index(token: &str, index: usize) -> bool {
+    // leading zeros are not allowed
+    !token.is_empty()
+        && token.bytes().all(|b| b.is_ascii_digit())
+        && (token == "0" || !token.starts_with('0'))
+        && token.parse() == Ok(index)
+}
+
+/// Get the value at the given JSON pointer (e.g. `/features/0/name`) from
+/// the given JSON text without parsing all of it. Parsing stops as soon as
+/// the value has been found or as soon as it is clear that the JSON text
+/// does not contain it. Everything after that point is not validated.
+///
+/// Objects and arrays are returned as they appear in the JSON text (see
+/// [`ExtractedValue::Raw`]). The function returns `None` if the value
+/// does not exist.
+///
+/// ```
+/// use actson::extract::{extract, ExtractedValue};
+///
+/// let json = br#"{"features": [{"name": "a"}, {"name": "b", "tags": [1, 2]}]}"#;
+///
+/// assert_eq!(
+///     extract(json, "/features/1/name").unwrap(),
+///     Some(ExtractedValue::String("b".to_string())),
+/// );
+/// assert_eq!(
+///     extract(json, "/features/1/tags").unwrap(),
+///     Some(ExtractedValue::Raw(b"[1, 2]")),
+/// );
+/// assert_eq!(extract(json, "/features/2").unwrap(), None);
+/// ```
+pub fn extract<'a>(
+    json: &'a [u8],
+    pointer: &str,
+) -> Result<Option<ExtractedValue<'a>>, ExtractError> {
+    let target = parse_pointer(pointer)?;
+
+    let mut parser = JsonParser::new_with_options(
+        SliceJsonFeeder::new(json),
+        JsonParserOptionsBuilder::default()
+            .with_int_overflow_as_float(true)
+            .build(),
+    );
+
+    let Some(event) = seek(&mut parser, &target)? else {
+        return Ok(None);
+    };
+
+    let value = match event {
+        JsonEvent::StartObject | JsonEvent::StartArray => {
+            let start = parser.parsed_bytes() - 1;
+            let mut depth = 1;
+            while depth > 0 {
+                match parser.next_event()? {
+                    Some(JsonEvent::StartObject | JsonEvent::StartArray) => depth += 1,
+                    Some(JsonEvent::EndObject | JsonEvent::EndArray) => depth -= 1,
+                    Some(_) => {}
+                    None => break,
+                }
+            }
+            ExtractedValue::Raw(&json[start..parser.parsed_bytes()])
+        }
+        JsonEvent::ValueString => ExtractedValue::String(parser.current_str()?.to_string()),
+        JsonEvent::ValueInt => ExtractedValue::Int(parser.current_int()?),
+        JsonEvent::ValueFloat => ExtractedValue::Float(parser.current_float()?),
+        JsonEvent::ValueTrue => ExtractedValue::Bool(true),
+        JsonEvent::ValueFalse => ExtractedValue::Bool(false),
+        _ => ExtractedValue::Null,
+    };
+    Ok(Some(value))
+}
+
+/// Advance the parser to the value the given reference tokens refer to and
+/// return the event the value starts with. Return `None` as soon as it is
+/// clear that the JSON text does not contain the value.
+fn seek<T, S>(
+    parser: &mut JsonParser<T, S>,
+    target: &[String],
+) -> Result<Option<JsonEvent>, ExtractError>
+where
+    T: FillJsonFeeder,
+    S: ModeStack,
+{
+    // the index of the next element in each of the containers the parser is
+    // currently in, or `None` if the container is an object
+    let mut containers: Vec<Option<usize>> = Vec::new();
+
+    // the number of outer containers whose current element is on the path
+    // to the target
+    let mut matched = 0;
+
+    while let Some(event) = parser.next_event()? {
+        let depth = containers.len();
+
+        match event {
+            JsonEvent::NeedMoreInput => {
+                parser.feeder.fill()?;
+                continue;
+            }
+
+            JsonEvent::Whitespace
+            | JsonEvent::Comment
+            | JsonEvent::PartialValueString
+            | JsonEvent::StartDocument
+            | JsonEvent::EndDocument => continue,
+
+            JsonEvent::FieldName | JsonEvent::Entry => {
+                if matched + 1 >= depth {
+                    matched = depth - 1 + usize::from(parser.current_str()? == target[depth - 1]);
+                }
+                if event == JsonEvent::FieldName {
+                    continue;
+                }
+            }
+
+            JsonEvent::EndObject | JsonEvent::EndArray => {
+                containers.pop();
+                if matched >= depth - 1 {
+                    // we've left the container that should have contained
+                    // the target
+                    return Ok(None);
+                }
+                continue;
+            }
+
+            _ => {}
+        }
+
+        // a new value starts
+        if let Some(Some(index)) = containers.last_mut() {
+            if matched + 1 >= depth {
+                matched = depth - 1 + usize::from(is_index(&target[depth - 1], *index));
+            }
+            *index += 1;
+        }
+
+        if matched == depth && depth == target.len() {
+            return Ok(Some(event));
+        }
+
+        if matches!(event, JsonEvent::StartObject | JsonEvent::StartArray) {
+            containers.push((event == JsonEvent::StartArray).then_some(0));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Iterates over the elements of an array in a JSON text, one element at a
+/// time. Created by [`JsonParser::array_elements_at_pointer()`].
+pub struct ArrayElements<'a, T, S = HeapStack> {
+    parser: &'a mut JsonParser<T, S>,
+    finished: bool,
+}
+
+impl<T, S> JsonParser<T, S>
+where
+    T: FillJsonFeeder,
+    S: ModeStack,
+{
+    /// Advance the parser to the array at the given JSON pointer (e.g.
+    /// `/features`) and return an [`ArrayElements`] object that lets you
+    /// process the array's elements one by one (e.g. with
+    /// [`ArrayElements::build_next()`]). Whenever the parser needs more
+    /// input, the feeder is filled synchronously.
+    ///
+    /// Return `None` if the JSON text does not contain a value at the given
+    /// pointer and [`ExtractError::NotAnArray`] if the value is not an
+    /// array. Call this method before the parser has returned any events.
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::JsonParser;
+    ///
+    /// let json = br#"{"type": "FeatureCollection", "features": [{"id": 1}, {"id": 2}]}"#;
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// let mut elements = parser.array_elements_at_pointer("/features").unwrap().unwrap();
+    /// let mut n = 0;
+    /// while elements.skip_next().unwrap() {
+    ///     n += 1;
+    /// }
+    /// assert_eq!(n, 2);
+    /// ```
+    pub fn array_elements_at_pointer(
+        &mut self,
+        pointer: &str,
+    ) -> Result<Option<ArrayElements<'_, T, S>>, ExtractError> {
+        let target = parse_pointer(pointer)?;
+        match seek(self, &target)? {
+            None => Ok(None),
+            Some(JsonEvent::StartArray) => Ok(Some(ArrayElements {
+                parser: self,
+                finished: false,
+            })),
+            Some(_) => Err(ExtractError::NotAnArray(pointer.to_string())),
+        }
+    }
+}
+
+impl<T, S> ArrayElements<'_, T, S>
+where
+    T: FillJsonFeeder,
+    S: ModeStack,
+{
+    /// Get the parser
+    pub fn parser(&self) -> &JsonParser<T, S> {
+        self.parser
+    }
+
+    /// Parse the next element into a value created by the given
+    /// [`ValueBuilder`]. Return `None` if the end of the array has been
+    /// reached.
+    pub fn build_next<B>(&mut self, builder: B) -> Result<Option<B::Value>, B::Error>
+    where
+        B: ValueBuilder,
+        B::Error: From<FillError>,
+    {
+        if !self.has_next::<B::Error>()? {
+            return Ok(None);
+        }
+        let mut assembler = ValueAssembler::new(builder);
+        loop {
+            match self.parser.next_event()? {
+                Some(JsonEvent::NeedMoreInput) => self.parser.feeder.fill()?,
+                Some(event) => {
+                    assembler.on_event(event, self.parser)?;
+                    if let Some(v) = assembler.take_complete() {
+                        return Ok(Some(v));
+                    }
+                }
+                None => return Err(ParserError::NoMoreInput.into()),
+            }
+        }
+    }
+
+    /// Skip the next element. Return `false` if the end of the array has
+    /// been reached.
+    pub fn skip_next(&mut self) -> Result<bool, ExtractError> {
+        if !self.has_next::<ExtractError>()? {
+            return Ok(false);
+        }
+        let mut depth = 0;
+        loop {
+            match self.parser.next_event()? {
+                Some(JsonEvent::NeedMoreInput) => self.parser.feeder.fill()?,
+                Some(JsonEvent::StartObject | JsonEvent::StartArray) => depth += 1,
+                Some(JsonEvent::EndObject | JsonEvent::EndArray) => depth -= 1,
+                Some(
+                    JsonEvent::Whitespace | JsonEvent::Comment | JsonEvent::PartialValueString,
+                ) => continue,
+                Some(_) => {}
+                None => return Err(ParserError::NoMoreInput.into()),
+            }
+            if depth == 0 {
+                return Ok(true);
+            }
+        }
+    }
+
+    /// Skip white space and comments before the next element and check if
+    /// there is one. Consume the end of the array if there is none.
+    fn has_next<E>(&mut self) -> Result<bool, E>
+    where
+        E: From<ParserError> + From<FillError>,
+    {
+        if self.finished {
+            return Ok(false);
+        }
+        loop {
+            match self.parser.peek_event()? {
+                Some(JsonEvent::NeedMoreInput) => {
+                    self.parser.next_event()?;
+                    self.parser.feeder.fill()?;
+                }
+                Some(JsonEvent::Whitespace | JsonEvent::Comment) => {
+                    self.parser.next_event()?;
+                }
+                Some(JsonEvent::EndArray) => {
+                    self.parser.next_event()?;
+                    self.finished = true;
+                    return Ok(false);
+                }
+                Some(_) => return Ok(true),
+                None => return Err(ParserError::NoMoreInput.into()),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> ArrayElements<'_, T>
+where
+    T: FillJsonFeeder,
+{
+    /// Deserialize the next element with an
+    /// [`ActsonDeserializer`](crate::serde::ActsonDeserializer). Return
+    /// `None` if the end of the array has been reached. The feeder must have
+    /// all input data of the element available.
+    ///
+    /// *Heads up:* You need to enable the `serde` feature for this.
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::JsonParser;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Feature {
+    ///     id: u32,
+    /// }
+    ///
+    /// let json = br#"{"features": [{"id": 1}, {"id": 2}], "type": "FeatureCollection"}"#;
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// let mut elements = parser.array_elements_at_pointer("/features").unwrap().unwrap();
+    /// let mut ids = Vec::new();
+    /// while let Some(f) = elements.deserialize_next::<Feature>().unwrap() {
+    ///     ids.push(f.id);
+    /// }
+    /// assert_eq!(ids, vec![1, 2]);
+    /// ```
+    pub fn deserialize_next<D>(&mut self) -> Result<Option<D>, DeserializeError>
+    where
+        D: DeserializeOwned,
+    {
+        if !self.has_next::<DeserializeError>()? {
+            return Ok(None);
+        }
+        let mut deserializer = ActsonDeserializer::from_parser_mut(self.parser);
+        Ok(Some(D::deserialize(&mut deserializer)?))
+    }
+}
+
+/// Specifies what [`collect_string_map_with()`] should do with fields whose
+/// values are not strings
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NonStringValues {
+    /// Return [`ExtractError::NotAString`]
+    Reject,
+
+    /// Ignore the field
+    Skip,
+}
+
+/// Collect the fields of a flat JSON object whose values are all strings
+/// into a map. Return [`ExtractError::NotAString`] if a value is not a
+/// string (including nested objects and arrays). If a field occurs more
+/// than once, the last value wins.
+///
+/// ```
+/// use actson::extract::collect_string_map;
+///
+/// let json = br#"{"host": "localhost", "port": "8080"}"#;
+///
+/// let map = collect_string_map(json).unwrap();
+/// assert_eq!(map["host"], "localhost");
+/// assert_eq!(map["port"], "8080");
+/// ```
+pub fn collect_string_map(json: &[u8]) -> Result<BTreeMap<String, String>, ExtractError> {
+    collect_string_map_with(json, NonStringValues::Reject)
+}
+
+/// Collect the fields of a flat JSON object whose values are strings into
+/// a map, just like [`collect_string_map()`], but specify what should happen
+/// with other values
+///
+/// ```
+/// use actson::extract::{collect_string_map_with, NonStringValues};
+///
+/// let json = br#"{"host": "localhost", "port": 8080, "tags": ["a"]}"#;
+///
+/// let map = collect_string_map_with(json, NonStringValues::Skip).unwrap();
+/// assert_eq!(map.len(), 1);
+/// assert_eq!(map["host"], "localhost");
+/// ```
+pub fn collect_string_map_with(
+    json: &[u8],
+    non_string_values: NonStringValues,
+) -> Result<BTreeMap<String, String>, ExtractError> {
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    parser.expect(JsonEvent::StartObject)?;
+
+    let mut result = BTreeMap::new();
+    let mut key = String::new();
+    let mut depth = 0;
+    while let Some(event) = parser.next_event()? {
+        match event {
+            JsonEvent::FieldName if depth == 0 => {
+                key.clear();
+                key.push_str(parser.current_str()?);
+            }
+
+            JsonEvent::ValueString if depth == 0 => {
+                result.insert(key.clone(), parser.current_str()?.to_string());
+            }
+
+            JsonEvent::EndObject if depth == 0 => {}
+
+            _ if non_string_values == NonStringValues::Reject => {
+                return Err(ExtractError::NotAString(key));
+            }
+
+            JsonEvent::StartObject | JsonEvent::StartArray => depth += 1,
+            JsonEvent::EndObject | JsonEvent::EndArray => depth -= 1,
+            _ => {}
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use crate::extract::{
+        collect_string_map, collect_string_map_with, extract, ExtractError, ExtractedValue,
+        NonStringValues,
+    };
+    use crate::feeder::SliceJsonFeeder;
+    use crate::parser::ParserError;
+    use crate::{JsonEvent, JsonParser};
+
+    const JSON: &[u8] = br#"{
+        "type": "FeatureCollection",
+        "features": [
+            {"id": 1, "properties": {"name": "a", "a/b": true, "m~n": null}},
+            {"id": 2.5, "properties": {"name": "b"}, "geometry": [[1, 2], []]}
+        ]
+    }"#;
+
+    /// Test that scalar values can be extracted
+    #[test]
+    fn scalars() {
+        let expected = [
+            (
+                "/type",
+                ExtractedValue::String("FeatureCollection".to_string()),
+            ),
+            ("/features/0/id", ExtractedValue::Int(1)),
+            ("/features/1/id", ExtractedValue::Float(2.5)),
+            (
+                "/features/0/properties/name",
+                ExtractedValue::String("a".to_string()),
+            ),
+            (
+                "/features/1/properties/name",
+                ExtractedValue::String("b".to_string()),
+            ),
+            ("/features/0/properties/a~1b", ExtractedValue::Bool(true)),
+            ("/features/0/properties/m~0n", ExtractedValue::Null),
+            ("/features/1/geometry/0/1", ExtractedValue::Int(2)),
+        ];
+        for (pointer, value) in expected {
+            assert_eq!(extract(JSON, pointer).unwrap(), Some(value), "{pointer}");
+        }
+    }
+
+    /// Test that objects and arrays are returned as raw bytes
+    #[test]
+    fn containers() {
+        assert_eq!(
+            extract(JSON, "").unwrap(),
+            Some(ExtractedValue::Raw(JSON.trim_ascii()))
+        );
+        assert_eq!(
+            extract(JSON, "/features/1/geometry").unwrap(),
+            Some(ExtractedValue::Raw(b"[[1, 2], []]"))
+        );
+        assert_eq!(
+            extract(JSON, "/features/1/geometry/1").unwrap(),
+            Some(ExtractedValue::Raw(b"[]"))
+        );
+        assert_eq!(
+            extract(JSON, "/features/0/properties").unwrap(),
+            Some(ExtractedValue::Raw(
+                br#"{"name": "a", "a/b": true, "m~n": null}"#
+            ))
+        );
+    }
+
+    /// Test that `None` is returned if the value does not exist
+    #[test]
+    fn not_found() {
+        for pointer in [
+            "/foo",
+            "/features/2",
+            "/features/01",
+            "/features/-",
+            "/features/0/id/foo",
+            "/type/0",
+            "/features/1/geometry/1/0",
+        ] {
+            assert_eq!(extract(JSON, pointer).unwrap(), None, "{pointer}");
+        }
+    }
+
+    /// Test that parsing stops as soon as the value has been found or it is
+    /// clear that it does not exist
+    #[test]
+    fn early_termination() {
+        let json = br#"{"a": {"b": 1, "c": [true]}, "d": invalid"#;
+        assert_eq!(
+            extract(json, "/a/c/0").unwrap(),
+            Some(ExtractedValue::Bool(true))
+        );
+        assert_eq!(
+            extract(json, "/a/c").unwrap(),
+            Some(ExtractedValue::Raw(b"[true]"))
+        );
+        assert_eq!(extract(json, "/a/e").unwrap(), None);
+        assert!(matches!(
+            extract(json, "/d"),
+            Err(ExtractError::Parse(ParserError::SyntaxError(_)))
+        ));
+    }
+
+    /// Test that invalid pointers are rejected
+    #[test]
+    fn invalid_pointer() {
+        for pointer in ["foo", "/a~2", "/a~"] {
+            assert!(matches!(
+                extract(JSON, pointer),
+                Err(ExtractError::InvalidPointer(p)) if p == pointer
+            ));
+        }
+    }
+
+    /// Test that the string fields of an object can be collected
+    #[test]
+    fn string_map() {
+        let json = br#"{"a": "1", "b": {"c": "2", "d": [3]}, "e": null, "a": "4", "f": "5"}"#;
+
+        assert!(matches!(
+            collect_string_map(json),
+            Err(ExtractError::NotAString(field)) if field == "b"
+        ));
+
+        let map = collect_string_map_with(json, NonStringValues::Skip).unwrap();
+        assert_eq!(
+            map.into_iter().collect::<Vec<_>>(),
+            [
+                ("a".to_string(), "4".to_string()),
+                ("f".to_string(), "5".to_string())
+            ]
+        );
+
+        assert!(collect_string_map(b"{}").unwrap().is_empty());
+        assert!(matches!(
+            collect_string_map(br#"["a"]"#),
+            Err(ExtractError::Parse(ParserError::UnexpectedEvent {
+                expected: JsonEvent::StartObject,
+                got: Some(JsonEvent::StartArray),
+            }))
+        ));
+        assert!(matches!(
+            collect_string_map(br#"{"a": "1"} x"#),
+            Err(ExtractError::Parse(ParserError::TrailingData(_)))
+        ));
+    }
+
+    /// Test that the elements of an array at a JSON pointer can be iterated
+    #[test]
+    fn array_elements() {
+        let mut parser = JsonParser::new(SliceJsonFeeder::new(JSON));
+        let mut elements = parser
+            .array_elements_at_pointer("/features")
+            .unwrap()
+            .unwrap();
+        assert!(elements.skip_next().unwrap());
+        assert!(elements.skip_next().unwrap());
+        assert!(!elements.skip_next().unwrap());
+        assert!(!elements.skip_next().unwrap());
+        assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndObject));
+
+        let mut parser = JsonParser::new(SliceJsonFeeder::new(JSON));
+        let mut elements = parser
+            .array_elements_at_pointer("/features/1/geometry/1")
+            .unwrap()
+            .unwrap();
+        assert!(!elements.skip_next().unwrap());
+
+        let mut parser = JsonParser::new(SliceJsonFeeder::new(JSON));
+        assert!(parser
+            .array_elements_at_pointer("/features/2")
+            .unwrap()
+            .is_none());
+
+        let mut parser = JsonParser::new(SliceJsonFeeder::new(JSON));
+        assert!(matches!(
+            parser.array_elements_at_pointer("/features/0"),
+            Err(ExtractError::NotAnArray(p)) if p == "/features/0"
+        ));
+    }
+}
@@ -1,6 +1,6 @@
-use std::io::{BufRead, BufReader, Read};
+use std::io::{BufRead, BufReader, ErrorKind, Read};
 
-use super::{FillError, JsonFeeder};
+use super::{FillError, FillJsonFeeder, JsonFeeder};
 
 /// A [`JsonFeeder`] that reads from a [`BufReader`].
 pub struct BufReaderJsonFeeder<T> {
@@ -22,14 +22,57 @@ where
         }
     }
 
-    /// Fill the feeder's internal buffer
+    /// Create a new feeder that reads from the given reader through a
+    /// [`BufReader`] with the given capacity. Larger buffers can increase
+    /// the throughput when parsing large files.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn with_capacity(capacity: usize, reader: T) -> Self {
+        assert!(capacity > 0, "capacity must not be 0");
+        Self::new(BufReader::with_capacity(capacity, reader))
+    }
+
+    /// Fill the feeder's internal buffer. Blocks until data is available or
+    /// until the end of the input has been reached.
     pub fn fill_buf(&mut self) -> Result<(), FillError> {
         self.reader.consume(self.pos);
-        self.reader.fill_buf()?;
-        self.filled = true;
         self.pos = 0;
+        loop {
+            match self.reader.fill_buf() {
+                Ok(_) => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        self.filled = true;
         Ok(())
     }
+
+    /// Get the number of bytes in the feeder's internal buffer that have not
+    /// been consumed by the parser yet. Use this to decide if it is
+    /// necessary to fill the buffer.
+    pub fn buffered_len(&self) -> usize {
+        self.reader.buffer().len() - self.pos
+    }
+
+    /// Consume the feeder and return its [`BufReader`]. Bytes that have
+    /// been consumed by the parser are removed from the reader's buffer, so
+    /// you can continue reading where the parser has stopped.
+    pub fn into_inner(mut self) -> BufReader<T> {
+        self.reader.consume(self.pos);
+        self.reader
+    }
+}
+
+impl<T> FillJsonFeeder for BufReaderJsonFeeder<T>
+where
+    T: Read,
+{
+    fn fill(&mut self) -> Result<(), FillError> {
+        self.fill_buf()
+    }
 }
 
 impl<T> JsonFeeder for BufReaderJsonFeeder<T>
@@ -54,4 +97,12 @@ where
             None
         }
     }
+
+    fn input_slice(&self) -> &[u8] {
+        &self.reader.buffer()[self.pos..]
+    }
+
+    fn consume_input(&mut self, n: usize) {
+        self.pos = (self.pos + n).min(self.reader.buffer().len());
+    }
 }
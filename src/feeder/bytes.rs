@@ -0,0 +1,132 @@
+use alloc::collections::VecDeque;
+
+use ::bytes::{Buf, Bytes};
+
+use super::JsonFeeder;
+
+/// A feeder that provides chunks of [`Bytes`] (or `BytesMut`) to the
+/// [`JsonParser`](crate::JsonParser) without copying them. This is useful
+/// if the JSON text is received from a network stack that hands out
+/// [`Bytes`], such as `hyper` or `tonic`. Push chunks with
+/// [`push_bytes()`](Self::push_bytes()) and call [`done()`](Self::done())
+/// at the end of the JSON text. Each chunk is dropped as soon as the parser
+/// has consumed it.
+///
+/// *Heads up:* The `bytes` feature has to be enabled for this.
+///
+/// ```
+/// use actson::feeder::BytesJsonFeeder;
+/// use actson::{JsonEvent, JsonParser};
+/// use bytes::Bytes;
+///
+/// let mut parser = JsonParser::new(BytesJsonFeeder::new());
+/// parser.feeder.push_bytes(Bytes::from_static(br#"{"name": "#));
+/// parser.feeder.push_bytes(Bytes::from_static(br#""Elvis"}"#));
+/// parser.feeder.done();
+///
+/// while let Some(event) = parser.next_event().unwrap() {
+///     match event {
+///         JsonEvent::FieldName => assert!(matches!(parser.current_str(), Ok("name"))),
+///         JsonEvent::ValueString => assert!(matches!(parser.current_str(), Ok("Elvis"))),
+///         _ => {}
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct BytesJsonFeeder {
+    chunks: VecDeque<Bytes>,
+    done: bool,
+}
+
+impl BytesJsonFeeder {
+    /// Create a new feeder without any chunks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a chunk to the input. Empty chunks are ignored.
+    pub fn push_bytes<B>(&mut self, b: B)
+    where
+        B: Into<Bytes>,
+    {
+        let b = b.into();
+        if !b.is_empty() {
+            self.chunks.push_back(b);
+        }
+    }
+
+    /// Call this method to indicate that the end of the JSON text has been
+    /// reached and that there is no more input to parse.
+    pub fn done(&mut self) {
+        self.done = true;
+    }
+}
+
+impl JsonFeeder for BytesJsonFeeder {
+    fn has_input(&self) -> bool {
+        !self.chunks.is_empty()
+    }
+
+    fn is_done(&self) -> bool {
+        self.done && !self.has_input()
+    }
+
+    fn next_input(&mut self) -> Option<u8> {
+        let chunk = self.chunks.front_mut()?;
+        let b = chunk.get_u8();
+        if chunk.is_empty() {
+            self.chunks.pop_front();
+        }
+        Some(b)
+    }
+
+    fn input_slice(&self) -> &[u8] {
+        self.chunks.front().map_or(&[], |c| c.as_ref())
+    }
+
+    fn consume_input(&mut self, mut n: usize) {
+        while let Some(chunk) = self.chunks.front_mut() {
+            if n < chunk.len() {
+                chunk.advance(n);
+                return;
+            }
+            n -= chunk.len();
+            self.chunks.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bytes::{Bytes, BytesMut};
+
+    use crate::feeder::{BytesJsonFeeder, JsonFeeder};
+
+    /// Test that chunks are consumed one after the other and dropped when
+    /// they are exhausted
+    #[test]
+    fn chunks() {
+        let mut feeder = BytesJsonFeeder::new();
+        assert!(!feeder.has_input());
+        assert!(!feeder.is_done());
+
+        feeder.push_bytes(Bytes::from_static(b"ab"));
+        feeder.push_bytes(Bytes::new());
+        feeder.push_bytes(BytesMut::from(&b"cde"[..]));
+        assert_eq!(feeder.chunks.len(), 2);
+
+        assert_eq!(feeder.next_input(), Some(b'a'));
+        assert_eq!(feeder.input_slice(), b"b");
+        assert_eq!(feeder.next_input(), Some(b'b'));
+        assert_eq!(feeder.chunks.len(), 1);
+        assert_eq!(feeder.input_slice(), b"cde");
+        feeder.consume_input(2);
+        assert_eq!(feeder.next_input(), Some(b'e'));
+        assert_eq!(feeder.next_input(), None);
+        assert!(!feeder.has_input());
+        assert!(!feeder.is_done());
+
+        feeder.done();
+        assert!(feeder.is_done());
+    }
+}
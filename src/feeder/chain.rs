@@ -0,0 +1,163 @@
+use core::iter::Map;
+
+use super::{JsonFeeder, SliceJsonFeeder};
+
+/// A [`JsonFeeder`] that concatenates a sequence of feeders. The
+/// [`JsonParser`](crate::JsonParser) sees their inputs as one continuous
+/// JSON text. The feeder advances to the next one as soon as the current one
+/// is done. It is only done itself when the last feeder is done.
+///
+/// ```
+/// use actson::{JsonParser, JsonEvent};
+/// use actson::feeder::ChainJsonFeeder;
+///
+/// let header = r#"{"name": "#.as_bytes();
+/// let body = r#""Elvis"}"#.as_bytes();
+///
+/// let feeder = ChainJsonFeeder::from_slices([header, body]);
+/// let mut parser = JsonParser::new(feeder);
+/// while let Some(event) = parser.next_event().unwrap() {
+///     match event {
+///         JsonEvent::FieldName => assert!(matches!(parser.current_str(), Ok("name"))),
+///         JsonEvent::ValueString => assert!(matches!(parser.current_str(), Ok("Elvis"))),
+///         _ => {}
+///     }
+/// }
+/// ```
+pub struct ChainJsonFeeder<I>
+where
+    I: Iterator,
+{
+    feeders: I,
+    current: Option<I::Item>,
+}
+
+impl<I> ChainJsonFeeder<I>
+where
+    I: Iterator,
+    I::Item: JsonFeeder,
+{
+    /// Create a new feeder that consumes the given feeders one after the other
+    pub fn new<F>(feeders: F) -> Self
+    where
+        F: IntoIterator<IntoIter = I>,
+    {
+        let mut feeders = feeders.into_iter();
+        let current = feeders.next();
+        let mut r = ChainJsonFeeder { feeders, current };
+        r.skip_done();
+        r
+    }
+
+    /// Get a mutable reference to the feeder that is currently being
+    /// consumed (e.g. to fill its buffer or to push more data to it).
+    /// Returns `None` if all feeders have been consumed.
+    pub fn current_mut(&mut self) -> Option<&mut I::Item> {
+        self.current.as_mut()
+    }
+
+    /// Advance to the next feeder until the current one is not done yet
+    fn skip_done(&mut self) {
+        while self.current.as_ref().is_some_and(|f| f.is_done()) {
+            self.current = self.feeders.next();
+        }
+    }
+}
+
+impl<'a, I> ChainJsonFeeder<Map<I, fn(&'a [u8]) -> SliceJsonFeeder<'a>>>
+where
+    I: Iterator<Item = &'a [u8]>,
+{
+    /// Create a new feeder that consumes the given byte slices one after
+    /// the other
+    pub fn from_slices<S>(slices: S) -> Self
+    where
+        S: IntoIterator<IntoIter = I>,
+    {
+        Self::new(
+            slices
+                .into_iter()
+                .map(SliceJsonFeeder::new as fn(&'a [u8]) -> SliceJsonFeeder<'a>),
+        )
+    }
+}
+
+impl<I> JsonFeeder for ChainJsonFeeder<I>
+where
+    I: Iterator,
+    I::Item: JsonFeeder,
+{
+    fn has_input(&self) -> bool {
+        self.current.as_ref().is_some_and(|f| f.has_input())
+    }
+
+    fn is_done(&self) -> bool {
+        self.current.is_none()
+    }
+
+    fn next_input(&mut self) -> Option<u8> {
+        while let Some(f) = &mut self.current {
+            if let Some(b) = f.next_input() {
+                self.skip_done();
+                return Some(b);
+            }
+            if !f.is_done() {
+                return None;
+            }
+            self.current = self.feeders.next();
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::feeder::{ChainJsonFeeder, JsonFeeder, PushJsonFeeder, SliceJsonFeeder};
+
+    /// Test that a chain without feeders is done immediately
+    #[test]
+    fn empty() {
+        let feeder = ChainJsonFeeder::new(Vec::<SliceJsonFeeder>::new());
+        assert!(!feeder.has_input());
+        assert!(feeder.is_done());
+    }
+
+    /// Test that all feeders are consumed one after the other and that empty
+    /// feeders are skipped
+    #[test]
+    fn consume_all() {
+        let mut feeder =
+            ChainJsonFeeder::from_slices(["".as_bytes(), b"El", b"", b"", b"v", b"is", b""]);
+        let mut r = Vec::new();
+        while let Some(b) = feeder.next_input() {
+            assert!(!feeder.is_done() || !feeder.has_input());
+            r.push(b);
+        }
+        assert_eq!(r, b"Elvis");
+        assert!(!feeder.has_input());
+        assert!(feeder.is_done());
+    }
+
+    /// Test that the chain waits for the current feeder to be done
+    #[test]
+    fn wait_for_current() {
+        let mut feeder = ChainJsonFeeder::new([PushJsonFeeder::new(), PushJsonFeeder::new()]);
+        assert!(!feeder.is_done());
+        assert_eq!(feeder.next_input(), None);
+
+        feeder.current_mut().unwrap().push_bytes(b"ab");
+        assert_eq!(feeder.next_input(), Some(b'a'));
+        assert_eq!(feeder.next_input(), Some(b'b'));
+        assert_eq!(feeder.next_input(), None);
+        assert!(!feeder.is_done());
+
+        feeder.current_mut().unwrap().done();
+        assert_eq!(feeder.next_input(), None);
+        assert!(!feeder.is_done());
+
+        feeder.current_mut().unwrap().push_bytes(b"c");
+        feeder.current_mut().unwrap().done();
+        assert_eq!(feeder.next_input(), Some(b'c'));
+        assert!(feeder.is_done());
+    }
+}
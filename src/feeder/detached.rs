@@ -0,0 +1,23 @@
+use super::JsonFeeder;
+
+/// A placeholder [`JsonFeeder`] for parsers that take their input from
+/// feeders passed to
+/// [`JsonParser::next_event_with()`](crate::JsonParser::next_event_with())
+/// (see [`JsonParser::stateless()`](crate::JsonParser::stateless())). It
+/// never has input and is never done.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DetachedJsonFeeder;
+
+impl JsonFeeder for DetachedJsonFeeder {
+    fn has_input(&self) -> bool {
+        false
+    }
+
+    fn is_done(&self) -> bool {
+        false
+    }
+
+    fn next_input(&mut self) -> Option<u8> {
+        None
+    }
+}
@@ -0,0 +1,174 @@
+use std::io::Read;
+
+use flate2::read::GzDecoder;
+
+use super::{BufReaderJsonFeeder, FillError, FillJsonFeeder, JsonFeeder};
+
+/// The default size of the buffer for decompressed data
+const DEFAULT_CAPACITY: usize = 64 * 1024;
+
+/// A [`JsonFeeder`] that decompresses gzip data from a [`Read`] on the fly.
+/// This allows you to parse a `.json.gz` file without decompressing it to
+/// memory first.
+///
+/// *Heads up:* The `flate2` feature has to be enabled for this.
+///
+/// ```
+/// use std::io::Write;
+///
+/// use actson::feeder::GzipJsonFeeder;
+/// use actson::{JsonEvent, JsonParser};
+/// use flate2::write::GzEncoder;
+/// use flate2::Compression;
+///
+/// let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+/// encoder.write_all(br#"{"name": "Elvis"}"#).unwrap();
+/// let compressed = encoder.finish().unwrap();
+///
+/// let feeder = GzipJsonFeeder::new(compressed.as_slice());
+/// let mut parser = JsonParser::new(feeder);
+/// while let Some(event) = parser.next_event().unwrap() {
+///     match event {
+///         JsonEvent::NeedMoreInput => parser.feeder.fill_buf().unwrap(),
+///         JsonEvent::FieldName => assert!(matches!(parser.current_str(), Ok("name"))),
+///         JsonEvent::ValueString => assert!(matches!(parser.current_str(), Ok("Elvis"))),
+///         _ => {}
+///     }
+/// }
+/// ```
+pub struct GzipJsonFeeder<R> {
+    inner: BufReaderJsonFeeder<GzDecoder<R>>,
+}
+
+impl<R> GzipJsonFeeder<R>
+where
+    R: Read,
+{
+    /// Create a new feeder that decompresses the gzip data from the given
+    /// reader. Decompressed data is buffered in chunks of 64 KiB.
+    pub fn new(reader: R) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, reader)
+    }
+
+    /// Create a new feeder that decompresses the gzip data from the given
+    /// reader and that buffers decompressed data in chunks of the given
+    /// size. Larger buffers can increase the throughput when parsing large
+    /// files.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn with_capacity(capacity: usize, reader: R) -> Self {
+        assert!(capacity > 0, "capacity must not be 0");
+        GzipJsonFeeder {
+            inner: BufReaderJsonFeeder::with_capacity(capacity, GzDecoder::new(reader)),
+        }
+    }
+
+    /// Fill the feeder's internal buffer with decompressed data. Blocks
+    /// until data is available or until the end of the input has been
+    /// reached.
+    pub fn fill_buf(&mut self) -> Result<(), FillError> {
+        self.inner.fill_buf()
+    }
+
+    /// Get the number of decompressed bytes in the feeder's internal buffer
+    /// that have not been consumed by the parser yet
+    pub fn buffered_len(&self) -> usize {
+        self.inner.buffered_len()
+    }
+}
+
+impl<R> FillJsonFeeder for GzipJsonFeeder<R>
+where
+    R: Read,
+{
+    fn fill(&mut self) -> Result<(), FillError> {
+        self.fill_buf()
+    }
+}
+
+impl<R> JsonFeeder for GzipJsonFeeder<R>
+where
+    R: Read,
+{
+    fn has_input(&self) -> bool {
+        self.inner.has_input()
+    }
+
+    fn is_done(&self) -> bool {
+        self.inner.is_done()
+    }
+
+    fn next_input(&mut self) -> Option<u8> {
+        self.inner.next_input()
+    }
+
+    fn input_slice(&self) -> &[u8] {
+        self.inner.input_slice()
+    }
+
+    fn consume_input(&mut self, n: usize) {
+        self.inner.consume_input(n)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use crate::feeder::{FillError, GzipJsonFeeder};
+    use crate::visitor::{DriveError, Visitor};
+    use crate::JsonParser;
+
+    /// Counts all integer values
+    #[derive(Default)]
+    struct Count(usize);
+
+    impl Visitor for Count {
+        fn value_int(&mut self, _value: i64) {
+            self.0 += 1;
+        }
+    }
+
+    /// Test that a document larger than the buffer is decompressed and
+    /// parsed completely
+    #[test]
+    fn large() {
+        let json = format!("[{}0]", "1234567,".repeat(100_000));
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(json.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        for capacity in [1, 1000, 64 * 1024] {
+            let feeder = GzipJsonFeeder::with_capacity(capacity, compressed.as_slice());
+            let mut parser = JsonParser::new(feeder);
+            let mut count = Count::default();
+            parser.drive(&mut count).unwrap();
+            assert_eq!(count.0, 100_001);
+        }
+    }
+
+    /// Test that invalid or truncated gzip data leads to an I/O error
+    #[test]
+    fn invalid() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"[1, 2]").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        for input in [
+            &b"[1, 2]"[..],
+            &b""[..],
+            &compressed[..compressed.len() - 4],
+        ] {
+            let mut parser = JsonParser::new(GzipJsonFeeder::new(input));
+            assert!(matches!(
+                parser.drive(&mut Count::default()),
+                Err(DriveError::Fill(FillError::Io(_)))
+            ));
+        }
+    }
+}
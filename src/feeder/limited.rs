@@ -0,0 +1,202 @@
+use super::{FillError, FillJsonFeeder, JsonFeeder};
+
+/// A [`JsonFeeder`] that wraps around another feeder and limits the number
+/// of bytes that can be consumed from it. As soon as the limit has been
+/// reached, the feeder does not provide more input. If the wrapped feeder
+/// still has input, the end of the JSON text is not reached, so the parser
+/// keeps returning [`JsonEvent::NeedMoreInput`](crate::JsonEvent::NeedMoreInput)
+/// and [`fill()`](FillJsonFeeder::fill()) fails with
+/// [`FillError::LimitReached`]. Use
+/// [`limit_reached()`](Self::limit_reached()) to check whether this is the
+/// case.
+///
+/// ```
+/// use actson::{JsonParser, JsonEvent};
+/// use actson::feeder::{LimitedJsonFeeder, SliceJsonFeeder};
+///
+/// let json = r#"{"name": "Elvis"}"#.as_bytes();
+///
+/// let feeder = LimitedJsonFeeder::new(SliceJsonFeeder::new(json), 8);
+/// let mut parser = JsonParser::new(feeder);
+/// loop {
+///     match parser.next_event() {
+///         Ok(Some(JsonEvent::NeedMoreInput)) | Ok(None) | Err(_) => break,
+///         Ok(Some(_)) => {}
+///     }
+/// }
+///
+/// assert!(parser.feeder.limit_reached());
+/// ```
+pub struct LimitedJsonFeeder<F> {
+    inner: F,
+    remaining: usize,
+}
+
+impl<F> LimitedJsonFeeder<F>
+where
+    F: JsonFeeder,
+{
+    /// Create a new feeder that wraps around the given one and that will
+    /// provide at most `limit` bytes
+    pub fn new(inner: F, limit: usize) -> Self {
+        LimitedJsonFeeder {
+            inner,
+            remaining: limit,
+        }
+    }
+
+    /// Return the number of bytes that can still be consumed before the
+    /// limit is reached
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Check if the limit has been reached even though the wrapped feeder
+    /// could provide more input
+    pub fn limit_reached(&self) -> bool {
+        self.remaining == 0 && (self.inner.has_input() || !self.inner.is_done())
+    }
+
+    /// Get a reference to the wrapped feeder
+    pub fn get_ref(&self) -> &F {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the wrapped feeder (e.g. to fill its
+    /// buffer or to push more data to it)
+    pub fn get_mut(&mut self) -> &mut F {
+        &mut self.inner
+    }
+
+    /// Consume this feeder and return the wrapped one
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+}
+
+impl<F> FillJsonFeeder for LimitedJsonFeeder<F>
+where
+    F: FillJsonFeeder,
+{
+    fn fill(&mut self) -> Result<(), FillError> {
+        if self.remaining == 0 {
+            if self.limit_reached() {
+                return Err(FillError::LimitReached);
+            }
+            return Ok(());
+        }
+        self.inner.fill()
+    }
+}
+
+impl<F> JsonFeeder for LimitedJsonFeeder<F>
+where
+    F: JsonFeeder,
+{
+    fn has_input(&self) -> bool {
+        self.remaining > 0 && self.inner.has_input()
+    }
+
+    fn is_done(&self) -> bool {
+        // input that has been cut off by the limit must not look like the
+        // end of the JSON text. Otherwise, the parser might successfully
+        // return a truncated value.
+        self.inner.is_done() && (self.remaining > 0 || !self.inner.has_input())
+    }
+
+    fn next_input(&mut self) -> Option<u8> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let r = self.inner.next_input();
+        if r.is_some() {
+            self.remaining -= 1;
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::feeder::{
+        FillError, FillJsonFeeder, JsonFeeder, LimitedJsonFeeder, PushJsonFeeder, SliceJsonFeeder,
+    };
+    use crate::visitor::{DriveError, Visitor};
+    use crate::{JsonEvent, JsonParser};
+
+    struct Noop;
+
+    impl Visitor for Noop {}
+
+    /// Test that the feeder stops providing input once the limit is reached
+    #[test]
+    fn stop_at_limit() {
+        let mut feeder = LimitedJsonFeeder::new(SliceJsonFeeder::new(b"Elvis"), 3);
+        assert!(feeder.has_input());
+        assert!(!feeder.is_done());
+        assert_eq!(feeder.next_input(), Some(b'E'));
+        assert_eq!(feeder.next_input(), Some(b'l'));
+        assert_eq!(feeder.next_input(), Some(b'v'));
+        assert_eq!(feeder.next_input(), None);
+        assert!(!feeder.has_input());
+        assert!(!feeder.is_done());
+        assert!(feeder.limit_reached());
+    }
+
+    /// Test that the limit is not reported as reached if the wrapped feeder
+    /// does not provide more input
+    #[test]
+    fn below_limit() {
+        let mut feeder = LimitedJsonFeeder::new(SliceJsonFeeder::new(b"Elvis"), 5);
+        while feeder.next_input().is_some() {}
+        assert!(feeder.is_done());
+        assert!(!feeder.limit_reached());
+
+        let mut feeder = LimitedJsonFeeder::new(SliceJsonFeeder::new(b"Elvis"), 10);
+        while feeder.next_input().is_some() {}
+        assert!(feeder.is_done());
+        assert!(!feeder.limit_reached());
+        assert_eq!(feeder.remaining(), 5);
+    }
+
+    /// Test that the wrapped feeder can still be accessed
+    #[test]
+    fn push_to_inner() {
+        let mut feeder = LimitedJsonFeeder::new(PushJsonFeeder::new(), 2);
+        assert!(!feeder.has_input());
+        assert!(!feeder.is_done());
+        feeder.get_mut().push_bytes(b"abc");
+        assert_eq!(feeder.next_input(), Some(b'a'));
+        assert_eq!(feeder.next_input(), Some(b'b'));
+        assert_eq!(feeder.next_input(), None);
+        assert!(!feeder.is_done());
+        assert!(feeder.limit_reached());
+        assert!(feeder.get_ref().has_input());
+    }
+
+    /// Test that a top-level value that has been cut off by the limit is
+    /// not returned as if it was complete
+    #[test]
+    fn truncated_value() {
+        let feeder = LimitedJsonFeeder::new(SliceJsonFeeder::new(b"123456"), 3);
+        let mut parser = JsonParser::new(feeder);
+        assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::NeedMoreInput));
+        assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::NeedMoreInput));
+        assert!(parser.feeder.limit_reached());
+        assert!(matches!(parser.feeder.fill(), Err(FillError::LimitReached)));
+
+        let feeder = LimitedJsonFeeder::new(SliceJsonFeeder::new(b"123456"), 3);
+        let mut parser = JsonParser::new(feeder);
+        assert!(matches!(
+            parser.drive(&mut Noop),
+            Err(DriveError::Fill(FillError::LimitReached))
+        ));
+
+        // the value is complete if the limit has not been exceeded
+        let feeder = LimitedJsonFeeder::new(SliceJsonFeeder::new(b"123"), 3);
+        let mut parser = JsonParser::new(feeder);
+        assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+        assert_eq!(parser.current_int::<i64>().unwrap(), 123);
+        assert_eq!(parser.next_event().unwrap(), None);
+    }
+}
@@ -0,0 +1,153 @@
+use super::{FillError, FillJsonFeeder, JsonFeeder};
+
+/// A [`JsonFeeder`] that wraps around another feeder and counts the bytes
+/// and lines that have been consumed from it. This is useful to report
+/// progress or throughput while parsing.
+///
+/// ```
+/// use actson::{JsonParser, JsonEvent};
+/// use actson::feeder::{MeteredJsonFeeder, SliceJsonFeeder};
+///
+/// let json = b"{\n  \"name\": \"Elvis\"\n}";
+///
+/// let feeder = MeteredJsonFeeder::new(SliceJsonFeeder::new(json));
+/// let mut parser = JsonParser::new(feeder);
+/// while parser.next_event().unwrap().is_some() {}
+///
+/// assert_eq!(parser.feeder.bytes_read(), 21);
+/// assert_eq!(parser.feeder.lines_read(), 2);
+/// ```
+pub struct MeteredJsonFeeder<F> {
+    inner: F,
+    bytes_read: usize,
+    lines_read: usize,
+}
+
+impl<F> MeteredJsonFeeder<F>
+where
+    F: JsonFeeder,
+{
+    /// Create a new feeder that wraps around the given one
+    pub fn new(inner: F) -> Self {
+        MeteredJsonFeeder {
+            inner,
+            bytes_read: 0,
+            lines_read: 0,
+        }
+    }
+
+    /// Return the number of bytes that have been consumed so far
+    pub fn bytes_read(&self) -> usize {
+        self.bytes_read
+    }
+
+    /// Return the number of line feeds (`\n`) that have been consumed so far
+    pub fn lines_read(&self) -> usize {
+        self.lines_read
+    }
+
+    /// Get a reference to the wrapped feeder
+    pub fn get_ref(&self) -> &F {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the wrapped feeder (e.g. to fill its
+    /// buffer or to push more data to it)
+    pub fn get_mut(&mut self) -> &mut F {
+        &mut self.inner
+    }
+
+    /// Consume this feeder and return the wrapped one
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+}
+
+impl<F> FillJsonFeeder for MeteredJsonFeeder<F>
+where
+    F: FillJsonFeeder,
+{
+    fn fill(&mut self) -> Result<(), FillError> {
+        self.inner.fill()
+    }
+}
+
+impl<F> JsonFeeder for MeteredJsonFeeder<F>
+where
+    F: JsonFeeder,
+{
+    fn has_input(&self) -> bool {
+        self.inner.has_input()
+    }
+
+    fn is_done(&self) -> bool {
+        self.inner.is_done()
+    }
+
+    fn next_input(&mut self) -> Option<u8> {
+        let r = self.inner.next_input();
+        if let Some(b) = r {
+            self.bytes_read += 1;
+            if b == b'\n' {
+                self.lines_read += 1;
+            }
+        }
+        r
+    }
+
+    fn input_slice(&self) -> &[u8] {
+        self.inner.input_slice()
+    }
+
+    fn consume_input(&mut self, n: usize) {
+        let lines = self.inner.input_slice()[..n]
+            .iter()
+            .filter(|&&b| b == b'\n')
+            .count();
+        self.bytes_read += n;
+        self.lines_read += lines;
+        self.inner.consume_input(n)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::feeder::{JsonFeeder, MeteredJsonFeeder, PushJsonFeeder, SliceJsonFeeder};
+
+    /// Test that consumed bytes and lines are counted
+    #[test]
+    fn count() {
+        let mut feeder = MeteredJsonFeeder::new(SliceJsonFeeder::new(b"a\nbc\n\nd"));
+        assert_eq!(feeder.next_input(), Some(b'a'));
+        assert_eq!(feeder.next_input(), Some(b'\n'));
+        assert_eq!(feeder.bytes_read(), 2);
+        assert_eq!(feeder.lines_read(), 1);
+
+        assert_eq!(feeder.input_slice(), b"bc\n\nd");
+        feeder.consume_input(4);
+        assert_eq!(feeder.bytes_read(), 6);
+        assert_eq!(feeder.lines_read(), 3);
+
+        assert_eq!(feeder.next_input(), Some(b'd'));
+        assert_eq!(feeder.next_input(), None);
+        assert_eq!(feeder.bytes_read(), 7);
+        assert_eq!(feeder.lines_read(), 3);
+    }
+
+    /// Test that the wrapped feeder can still be accessed
+    #[test]
+    fn push_to_inner() {
+        let mut feeder = MeteredJsonFeeder::new(PushJsonFeeder::new());
+        assert!(!feeder.has_input());
+        feeder.get_mut().push_bytes(b"x\n");
+        assert_eq!(feeder.next_input(), Some(b'x'));
+        assert_eq!(feeder.next_input(), Some(b'\n'));
+        assert_eq!(feeder.next_input(), None);
+        assert!(!feeder.is_done());
+        feeder.get_mut().done();
+        assert!(feeder.is_done());
+        assert_eq!(feeder.bytes_read(), 2);
+        assert_eq!(feeder.lines_read(), 1);
+        assert!(!feeder.into_inner().has_input());
+    }
+}
@@ -1,17 +1,50 @@
+#[cfg(feature = "std")]
 mod bufreader;
+#[cfg(feature = "bytes")]
+mod bytes;
+mod chain;
+mod detached;
+#[cfg(feature = "flate2")]
+mod gzip;
+mod limited;
+mod metered;
 mod push;
 mod slice;
+#[cfg(feature = "std")]
+mod stdin;
+mod str;
+mod vec;
 
+#[cfg(feature = "bytes")]
+pub use self::bytes::BytesJsonFeeder;
+#[cfg(feature = "std")]
 pub use bufreader::BufReaderJsonFeeder;
+pub use chain::ChainJsonFeeder;
+pub use detached::DetachedJsonFeeder;
+#[cfg(feature = "flate2")]
+pub use gzip::GzipJsonFeeder;
+pub use limited::LimitedJsonFeeder;
+pub use metered::MeteredJsonFeeder;
 pub use push::{PushError, PushJsonFeeder};
 pub use slice::SliceJsonFeeder;
+#[cfg(feature = "std")]
+pub use stdin::StdinJsonFeeder;
+pub use str::StrJsonFeeder;
+pub use vec::VecJsonFeeder;
 
+use alloc::boxed::Box;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum FillError {
+    #[cfg(feature = "std")]
     #[error("{0}")]
     Io(#[from] std::io::Error),
+
+    /// The feeder cannot provide more input because it has reached its
+    /// limit (see [`LimitedJsonFeeder`])
+    #[error("the input limit has been reached")]
+    LimitReached,
 }
 
 /// A feeder can be used to provide more input data to the
@@ -25,4 +58,66 @@ pub trait JsonFeeder {
 
     /// Decode and return the next character to be parsed
     fn next_input(&mut self) -> Option<u8>;
+
+    /// Return the input data that is currently available as a contiguous
+    /// slice without consuming it. Feeders that cannot provide such a slice
+    /// return an empty one. The parser uses this to process runs of bytes
+    /// at once.
+    fn input_slice(&self) -> &[u8] {
+        &[]
+    }
+
+    /// Consume the given number of bytes from the beginning of the slice
+    /// returned by [`Self::input_slice()`]
+    fn consume_input(&mut self, n: usize) {
+        for _ in 0..n {
+            self.next_input();
+        }
+    }
+}
+
+/// A [`JsonFeeder`] that is able to synchronously provide more input data
+/// on its own (e.g. by reading from a blocking reader). Used by
+/// [`JsonParser::drive()`](crate::JsonParser::drive()).
+pub trait FillJsonFeeder: JsonFeeder {
+    /// Fill the feeder with more input data. Blocks until data is available
+    /// or until the end of the input has been reached.
+    fn fill(&mut self) -> Result<(), FillError>;
+}
+
+/// Delegates to the boxed feeder. This allows the type of the feeder to be
+/// erased (e.g. `JsonParser<Box<dyn JsonFeeder>>`) if it is only known at
+/// runtime.
+impl<F> JsonFeeder for Box<F>
+where
+    F: JsonFeeder + ?Sized,
+{
+    fn has_input(&self) -> bool {
+        (**self).has_input()
+    }
+
+    fn is_done(&self) -> bool {
+        (**self).is_done()
+    }
+
+    fn next_input(&mut self) -> Option<u8> {
+        (**self).next_input()
+    }
+
+    fn input_slice(&self) -> &[u8] {
+        (**self).input_slice()
+    }
+
+    fn consume_input(&mut self, n: usize) {
+        (**self).consume_input(n)
+    }
+}
+
+impl<F> FillJsonFeeder for Box<F>
+where
+    F: FillJsonFeeder + ?Sized,
+{
+    fn fill(&mut self) -> Result<(), FillError> {
+        (**self).fill()
+    }
 }
@@ -1,5 +1,5 @@
-use std::cmp::min;
-use std::collections::VecDeque;
+use alloc::collections::VecDeque;
+use core::cmp::min;
 
 use thiserror::Error;
 
@@ -22,20 +22,49 @@ pub enum PushError {
 /// returns [`JsonEvent::NeedMoreInput`](crate::JsonEvent::NeedMoreInput).
 /// Repeat pushing and parsing until all input data has been consumed. Finally,
 /// call [`done()`](Self::done()) to indicate the end of the JSON text.
+///
+/// If you don't need backpressure, create the feeder with
+/// [`unbounded()`](Self::unbounded()). It will then accept all pushed data
+/// and grow its internal buffer as necessary.
 pub struct PushJsonFeeder {
     input: VecDeque<u8>,
+    capacity: Option<usize>,
     done: bool,
 }
 
 impl PushJsonFeeder {
     /// Create a new push-based feeder
     pub fn new() -> Self {
+        Self::with_capacity(1024)
+    }
+
+    /// Create a new push-based feeder that accepts at most `capacity` bytes
+    /// at a time
+    pub fn with_capacity(capacity: usize) -> Self {
+        PushJsonFeeder {
+            input: VecDeque::with_capacity(capacity),
+            capacity: Some(capacity),
+            done: false,
+        }
+    }
+
+    /// Create a new push-based feeder that is never full and that grows its
+    /// internal buffer as necessary
+    pub fn unbounded() -> Self {
         PushJsonFeeder {
             input: VecDeque::with_capacity(1024),
+            capacity: None,
             done: false,
         }
     }
 
+    /// Reserve space for at least `additional` more bytes. This is only
+    /// useful for unbounded feeders (see [`unbounded()`](Self::unbounded()))
+    /// to avoid reallocations while pushing.
+    pub fn reserve(&mut self, additional: usize) {
+        self.input.reserve(additional);
+    }
+
     /// Provide more data to the [`JsonParser`](crate::JsonParser). Should only
     /// be called if [`is_full()`](Self::is_full()) returns `false`.
     pub fn push_byte(&mut self, b: u8) -> Result<(), PushError> {
@@ -51,19 +80,37 @@ impl PushJsonFeeder {
     /// until all bytes have been consumed or until the feeder is full
     /// (see [`is_full()`](Self::is_full())). The method will return the number
     /// of bytes consumed (which can be 0 if the parser does not accept more
-    /// input at the moment).
+    /// input at the moment). Unbounded feeders always consume all bytes.
     pub fn push_bytes(&mut self, buf: &[u8]) -> usize {
-        let n = min(buf.len(), self.input.capacity() - self.input.len());
+        let n = match self.capacity {
+            Some(capacity) => min(buf.len(), capacity.saturating_sub(self.input.len())),
+            None => buf.len(),
+        };
         self.input.extend(buf.iter().take(n));
         n
     }
 
+    /// Provide all bytes from the given buffer to the
+    /// [`JsonParser`](crate::JsonParser) at once, regardless of the feeder's
+    /// capacity. The internal buffer grows as necessary, so this method
+    /// bypasses backpressure and the feeder may need as much memory as the
+    /// buffer occupies. Only use it if you manage backpressure yourself or
+    /// if you have the whole input in memory anyway. Otherwise, use
+    /// [`push_bytes()`](Self::push_bytes()). The feeder is full (see
+    /// [`is_full()`](Self::is_full())) until the parser has consumed enough
+    /// bytes to get below its capacity again.
+    pub fn push_all(&mut self, buf: &[u8]) {
+        self.input.extend(buf);
+    }
+
     /// Checks if the parser accepts more input at the moment. If it doesn't,
     /// you have to call [`JsonParser::next_event()`](crate::JsonParser::next_event())
     /// until it returns [`JsonEvent::NeedMoreInput`](crate::JsonEvent::NeedMoreInput).
-    /// Only then, new input can be provided to the parser.
+    /// Only then, new input can be provided to the parser. Unbounded feeders
+    /// are never full.
     pub fn is_full(&self) -> bool {
-        self.input.len() == self.input.capacity()
+        self.capacity
+            .is_some_and(|capacity| self.input.len() >= capacity)
     }
 
     /// Call this method to indicate that the end of the JSON text has been
@@ -95,8 +142,6 @@ impl JsonFeeder for PushJsonFeeder {
 
 #[cfg(test)]
 mod test {
-    use std::collections::VecDeque;
-
     use crate::feeder::{JsonFeeder, PushError, PushJsonFeeder};
 
     /// Test if the feeder is empty at the beginning
@@ -120,10 +165,7 @@ mod test {
     /// actually full
     #[test]
     fn is_full() {
-        let mut feeder = PushJsonFeeder {
-            input: VecDeque::with_capacity(16),
-            done: false,
-        };
+        let mut feeder = PushJsonFeeder::with_capacity(16);
         for i in 0..16 {
             assert!(!feeder.is_full());
             feeder.push_byte(b'a' + i).unwrap();
@@ -134,10 +176,7 @@ mod test {
     /// Test if the feeder accepts a byte array
     #[test]
     fn feed_buf() {
-        let mut feeder = PushJsonFeeder {
-            input: VecDeque::with_capacity(16),
-            done: false,
-        };
+        let mut feeder = PushJsonFeeder::with_capacity(16);
         let buf = "abcd".as_bytes();
 
         assert!(!feeder.is_full());
@@ -179,13 +218,32 @@ mod test {
         assert!(feeder.is_done());
     }
 
+    /// Test that all bytes can be pushed regardless of the capacity
+    #[test]
+    fn push_all() {
+        let mut feeder = PushJsonFeeder::with_capacity(4);
+        feeder.push_all(b"abcdef");
+        assert!(feeder.is_full());
+        assert_eq!(feeder.push_bytes(b"g"), 0);
+        assert!(matches!(feeder.push_byte(b'g'), Err(PushError::Full)));
+
+        assert_eq!(feeder.next_input(), Some(b'a'));
+        assert_eq!(feeder.next_input(), Some(b'b'));
+        assert_eq!(feeder.next_input(), Some(b'c'));
+        assert!(!feeder.is_full());
+        assert_eq!(feeder.push_bytes(b"gh"), 1);
+        assert!(feeder.is_full());
+
+        for b in b"defg" {
+            assert_eq!(feeder.next_input(), Some(*b));
+        }
+        assert!(!feeder.has_input());
+    }
+
     /// Test that the feeder returns an error if it is full
     #[test]
     fn too_full() {
-        let mut feeder = PushJsonFeeder {
-            input: VecDeque::with_capacity(16),
-            done: false,
-        };
+        let mut feeder = PushJsonFeeder::with_capacity(16);
         for i in 0..16 {
             feeder.push_byte(b'a' + i).unwrap();
         }
@@ -215,10 +273,7 @@ mod test {
     /// Test if a short string can be decoded correctly
     #[test]
     fn short_string() {
-        let mut feeder = PushJsonFeeder {
-            input: VecDeque::with_capacity(16),
-            done: false,
-        };
+        let mut feeder = PushJsonFeeder::with_capacity(16);
         assert_buf_eq(b"abcdef", &mut feeder);
     }
 
@@ -226,10 +281,7 @@ mod test {
     /// decoded correctly
     #[test]
     fn long_string() {
-        let mut feeder = PushJsonFeeder {
-            input: VecDeque::with_capacity(16),
-            done: false,
-        };
+        let mut feeder = PushJsonFeeder::with_capacity(16);
         assert_buf_eq(b"abcdefghijklmnopqrstuvwxyz", &mut feeder);
     }
 
@@ -237,13 +289,31 @@ mod test {
     /// can be decoded correctly
     #[test]
     fn very_long_string() {
-        let mut feeder = PushJsonFeeder {
-            input: VecDeque::with_capacity(16),
-            done: false,
-        };
+        let mut feeder = PushJsonFeeder::with_capacity(16);
         assert_buf_eq(
             b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
             &mut feeder,
         );
     }
+
+    /// Test that an unbounded feeder accepts all bytes
+    #[test]
+    fn unbounded() {
+        let mut feeder = PushJsonFeeder::unbounded();
+        let buf = [b'a'; 4096];
+        assert_eq!(feeder.push_bytes(&buf), 4096);
+        assert!(!feeder.is_full());
+        feeder.push_byte(b'b').unwrap();
+        assert_eq!(feeder.push_bytes(&buf), 4096);
+        assert!(!feeder.is_full());
+
+        for _ in 0..4096 {
+            assert_eq!(feeder.next_input(), Some(b'a'));
+        }
+        assert_eq!(feeder.next_input(), Some(b'b'));
+        for _ in 0..4096 {
+            assert_eq!(feeder.next_input(), Some(b'a'));
+        }
+        assert!(!feeder.has_input());
+    }
 }
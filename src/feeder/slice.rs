@@ -1,6 +1,10 @@
-use super::JsonFeeder;
+use super::{FillError, FillJsonFeeder, JsonFeeder};
+use crate::parser::ParserError;
+use crate::stack::ModeStack;
+use crate::{JsonEvent, JsonParser};
 
 /// A [`JsonFeeder`] that feeds the [`JsonParser`](crate::JsonParser) from a slice of bytes
+#[derive(Clone)]
 pub struct SliceJsonFeeder<'a> {
     slice: &'a [u8],
     pos: usize,
@@ -11,6 +15,91 @@ impl<'a> SliceJsonFeeder<'a> {
     pub fn new(slice: &'a [u8]) -> Self {
         SliceJsonFeeder { slice, pos: 0 }
     }
+
+    /// Get the bytes that have not been consumed yet. Note that the parser
+    /// may have consumed one more byte than it has processed. Use
+    /// [`JsonParser::remaining_input()`] to take this into account.
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.slice[self.pos..]
+    }
+}
+
+impl<'a, S> JsonParser<SliceJsonFeeder<'a>, S>
+where
+    S: ModeStack,
+{
+    /// Get the part of the input that the parser has not processed yet.
+    /// In streaming mode, call this method after the parser has returned
+    /// the last event of a top-level value to get the input from which the
+    /// next value can be parsed (e.g. by another component). Leading white
+    /// space is not skipped. If you have peeked an event (see
+    /// [`JsonParser::peek_event()`]), the input it has been parsed from is
+    /// not included.
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = br#"1{"name": "Elvis"}"#;
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_streaming(true)
+    ///         .build(),
+    /// );
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.remaining_input(), br#"{"name": "Elvis"}"#);
+    /// ```
+    pub fn remaining_input(&self) -> &'a [u8] {
+        let pos = self.feeder.pos - self.unprocessed_bytes();
+        &self.feeder.slice[pos..]
+    }
+
+    /// Get the raw bytes of the object or array that has just been started,
+    /// from the opening to the matching closing bracket. Call this method
+    /// after you've received [`JsonEvent::StartObject`] or
+    /// [`JsonEvent::StartArray`]. The parser processes the whole object or
+    /// array (so it is still validated) and continues after its end. The
+    /// events in between are not returned.
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = br#"[{"name": "Elvis", "albums": ["}"]}, 1]"#;
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+    /// assert_eq!(
+    ///     parser.capture_subtree().unwrap(),
+    ///     br#"{"name": "Elvis", "albums": ["}"]}"#
+    /// );
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// ```
+    pub fn capture_subtree(&mut self) -> Result<&'a [u8], ParserError> {
+        let event = self.current_event();
+        if !matches!(event, Some(JsonEvent::StartObject | JsonEvent::StartArray)) {
+            return Err(ParserError::UnexpectedEvent {
+                expected: JsonEvent::StartObject,
+                got: event,
+            });
+        }
+
+        // the opening bracket is the last byte that has been processed
+        let start = self.feeder.slice.len() - self.remaining_input().len() - 1;
+        let depth = self.container_stack().count();
+        while self.container_stack().count() >= depth {
+            if self.next_event()?.is_none() {
+                break;
+            }
+        }
+
+        let end = self.feeder.slice.len() - self.remaining_input().len();
+        Ok(&self.feeder.slice[start..end])
+    }
 }
 
 impl<'a> JsonFeeder for SliceJsonFeeder<'a> {
@@ -31,11 +120,29 @@ impl<'a> JsonFeeder for SliceJsonFeeder<'a> {
             r
         }
     }
+
+    fn input_slice(&self) -> &[u8] {
+        &self.slice[self.pos..]
+    }
+
+    fn consume_input(&mut self, n: usize) {
+        self.pos = (self.pos + n).min(self.slice.len());
+    }
+}
+
+impl FillJsonFeeder for SliceJsonFeeder<'_> {
+    fn fill(&mut self) -> Result<(), FillError> {
+        // the whole input is already available
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::feeder::JsonFeeder;
+    use crate::feeder::{JsonFeeder, SliceJsonFeeder};
+    use crate::options::JsonParserOptionsBuilder;
+    use crate::parser::ParserError;
+    use crate::{JsonEvent, JsonParser};
 
     #[test]
     fn empty() {
@@ -57,4 +164,90 @@ mod test {
         assert!(!feeder.has_input());
         assert!(feeder.is_done());
     }
+
+    #[test]
+    fn input_slice() {
+        let mut feeder = super::SliceJsonFeeder::new(b"Elvis");
+        assert_eq!(feeder.next_input(), Some(b'E'));
+        assert_eq!(feeder.input_slice(), b"lvis");
+        feeder.consume_input(3);
+        assert_eq!(feeder.input_slice(), b"s");
+        assert_eq!(feeder.next_input(), Some(b's'));
+        assert!(feeder.input_slice().is_empty());
+        assert!(feeder.is_done());
+    }
+
+    #[test]
+    fn remaining_input() {
+        let json = br#"[1] "a" 2{}"#;
+        let mut parser = JsonParser::new_with_options(
+            SliceJsonFeeder::new(json),
+            JsonParserOptionsBuilder::default()
+                .with_streaming(true)
+                .build(),
+        );
+        assert_eq!(parser.remaining_input(), json);
+
+        let mut remaining = Vec::new();
+        while let Some(e) = parser.next_event().unwrap() {
+            remaining.push((e, parser.remaining_input()));
+        }
+        assert_eq!(
+            remaining,
+            vec![
+                (JsonEvent::StartArray, &br#"1] "a" 2{}"#[..]),
+                // the `]` has already been processed together with the `1`
+                (JsonEvent::ValueInt, br#" "a" 2{}"#),
+                (JsonEvent::EndArray, br#" "a" 2{}"#),
+                (JsonEvent::ValueString, br#" 2{}"#),
+                (JsonEvent::ValueInt, br#"{}"#),
+                (JsonEvent::StartObject, br#"}"#),
+                (JsonEvent::EndObject, br#""#),
+            ]
+        );
+        assert_eq!(parser.feeder.remaining(), b"");
+    }
+
+    #[test]
+    fn capture_subtree() {
+        let json = br#"{"a": [1, {"b": "]}"}, [[]]], "c": {"d": 2}, "e": 3}"#;
+        let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+        let mut captured = Vec::new();
+        while let Some(e) = parser.next_event().unwrap() {
+            if parser.unclosed_containers() > 1 && e == JsonEvent::StartArray
+                || e == JsonEvent::StartObject && parser.parent_field_name() == Some("c")
+            {
+                captured.push(parser.capture_subtree().unwrap());
+            }
+        }
+        assert_eq!(
+            captured,
+            vec![&br#"[1, {"b": "]}"}, [[]]]"#[..], br#"{"d": 2}"#]
+        );
+
+        // the parser must be at the start of an object or array
+        let mut parser = JsonParser::new(SliceJsonFeeder::new(b"[1]"));
+        assert!(matches!(
+            parser.capture_subtree(),
+            Err(ParserError::UnexpectedEvent { got: None, .. })
+        ));
+        assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+        assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+        assert!(matches!(
+            parser.capture_subtree(),
+            Err(ParserError::UnexpectedEvent {
+                got: Some(JsonEvent::ValueInt),
+                ..
+            })
+        ));
+
+        // errors inside the subtree are reported
+        let mut parser = JsonParser::new(SliceJsonFeeder::new(b"[[1, }]"));
+        assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+        assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+        assert!(matches!(
+            parser.capture_subtree(),
+            Err(ParserError::SyntaxError(_))
+        ));
+    }
 }
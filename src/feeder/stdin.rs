@@ -0,0 +1,102 @@
+use std::io::{stdin, StdinLock};
+
+use super::{BufReaderJsonFeeder, FillError, FillJsonFeeder, JsonFeeder};
+
+/// The default size of the buffer for data read from standard input
+const DEFAULT_CAPACITY: usize = 64 * 1024;
+
+/// A [`JsonFeeder`] that reads from standard input. This is useful for
+/// command-line tools that process piped JSON (e.g.
+/// `cat big.json | mytool`). The feeder holds a lock on standard input
+/// while it exists.
+///
+/// ```no_run
+/// use actson::feeder::StdinJsonFeeder;
+/// use actson::{JsonEvent, JsonParser};
+///
+/// let mut parser = JsonParser::new(StdinJsonFeeder::new());
+/// while let Some(event) = parser.next_event().unwrap() {
+///     match event {
+///         JsonEvent::NeedMoreInput => parser.feeder.fill_buf().unwrap(),
+///         JsonEvent::FieldName => println!("{}", parser.current_str().unwrap()),
+///         _ => {}
+///     }
+/// }
+/// ```
+pub struct StdinJsonFeeder<'a> {
+    inner: BufReaderJsonFeeder<StdinLock<'a>>,
+}
+
+impl StdinJsonFeeder<'static> {
+    /// Create a new feeder that locks standard input and reads from it in
+    /// chunks of 64 KiB
+    pub fn new() -> Self {
+        Self::from_lock(stdin().lock())
+    }
+}
+
+impl Default for StdinJsonFeeder<'static> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> StdinJsonFeeder<'a> {
+    /// Create a new feeder that reads from the given lock on standard input
+    pub fn from_lock(stdin: StdinLock<'a>) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, stdin)
+    }
+
+    /// Create a new feeder that reads from the given lock on standard input
+    /// in chunks of the given size
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn with_capacity(capacity: usize, stdin: StdinLock<'a>) -> Self {
+        assert!(capacity > 0, "capacity must not be 0");
+        StdinJsonFeeder {
+            inner: BufReaderJsonFeeder::with_capacity(capacity, stdin),
+        }
+    }
+
+    /// Fill the feeder's internal buffer. Blocks until data is available or
+    /// until the end of the input has been reached.
+    pub fn fill_buf(&mut self) -> Result<(), FillError> {
+        self.inner.fill_buf()
+    }
+
+    /// Get the number of bytes in the feeder's internal buffer that have not
+    /// been consumed by the parser yet
+    pub fn buffered_len(&self) -> usize {
+        self.inner.buffered_len()
+    }
+}
+
+impl FillJsonFeeder for StdinJsonFeeder<'_> {
+    fn fill(&mut self) -> Result<(), FillError> {
+        self.fill_buf()
+    }
+}
+
+impl JsonFeeder for StdinJsonFeeder<'_> {
+    fn has_input(&self) -> bool {
+        self.inner.has_input()
+    }
+
+    fn is_done(&self) -> bool {
+        self.inner.is_done()
+    }
+
+    fn next_input(&mut self) -> Option<u8> {
+        self.inner.next_input()
+    }
+
+    fn input_slice(&self) -> &[u8] {
+        self.inner.input_slice()
+    }
+
+    fn consume_input(&mut self, n: usize) {
+        self.inner.consume_input(n)
+    }
+}
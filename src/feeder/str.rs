@@ -0,0 +1,117 @@
+use super::{FillError, FillJsonFeeder, JsonFeeder, SliceJsonFeeder};
+
+/// A [`JsonFeeder`] that feeds the [`JsonParser`](crate::JsonParser) from a
+/// string slice. It behaves like a [`SliceJsonFeeder`] but saves you from
+/// calling [`str::as_bytes()`] and guarantees that the input is valid UTF-8.
+///
+/// ```
+/// use actson::feeder::StrJsonFeeder;
+/// use actson::{JsonEvent, JsonParser};
+///
+/// let mut parser = JsonParser::new(StrJsonFeeder::new(r#"{"name": "Elvis"}"#));
+/// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+/// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+/// assert_eq!(parser.current_str().unwrap(), "name");
+/// ```
+#[derive(Clone)]
+pub struct StrJsonFeeder<'a> {
+    input: &'a str,
+    inner: SliceJsonFeeder<'a>,
+}
+
+impl<'a> StrJsonFeeder<'a> {
+    /// Create a new feeder that wraps around the given string slice
+    pub fn new(input: &'a str) -> Self {
+        StrJsonFeeder {
+            input,
+            inner: SliceJsonFeeder::new(input.as_bytes()),
+        }
+    }
+
+    /// Get the whole input string
+    pub fn as_str(&self) -> &'a str {
+        self.input
+    }
+
+    /// Get the bytes that have not been consumed yet. This may start in the
+    /// middle of a multi-byte character if the parser has stopped inside a
+    /// string. See [`SliceJsonFeeder::remaining()`].
+    pub fn remaining(&self) -> &'a [u8] {
+        self.inner.remaining()
+    }
+}
+
+impl<'a> From<&'a str> for StrJsonFeeder<'a> {
+    fn from(input: &'a str) -> Self {
+        Self::new(input)
+    }
+}
+
+impl JsonFeeder for StrJsonFeeder<'_> {
+    fn has_input(&self) -> bool {
+        self.inner.has_input()
+    }
+
+    fn is_done(&self) -> bool {
+        self.inner.is_done()
+    }
+
+    fn next_input(&mut self) -> Option<u8> {
+        self.inner.next_input()
+    }
+
+    fn input_slice(&self) -> &[u8] {
+        self.inner.input_slice()
+    }
+
+    fn consume_input(&mut self, n: usize) {
+        self.inner.consume_input(n)
+    }
+}
+
+impl FillJsonFeeder for StrJsonFeeder<'_> {
+    fn fill(&mut self) -> Result<(), FillError> {
+        // the whole input is already available
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::feeder::{JsonFeeder, StrJsonFeeder};
+    use crate::{JsonEvent, JsonParser};
+
+    #[test]
+    fn consume_all() {
+        let mut feeder = StrJsonFeeder::new("Grü");
+        assert_eq!(feeder.as_str(), "Grü");
+        assert_eq!(feeder.next_input(), Some(b'G'));
+        assert_eq!(feeder.input_slice(), "rü".as_bytes());
+        feeder.consume_input(2);
+        assert_eq!(feeder.remaining(), &"ü".as_bytes()[1..]);
+        assert_eq!(feeder.next_input(), Some("ü".as_bytes()[1]));
+        assert!(!feeder.has_input());
+        assert!(feeder.is_done());
+    }
+
+    #[test]
+    fn parse() {
+        let mut parser = JsonParser::new(StrJsonFeeder::from(r#"["Grüße", 1]"#));
+        let mut events = Vec::new();
+        while let Some(e) = parser.next_event().unwrap() {
+            if e == JsonEvent::ValueString {
+                assert_eq!(parser.current_str().unwrap(), "Grüße");
+            }
+            events.push(e);
+        }
+        assert_eq!(
+            events,
+            vec![
+                JsonEvent::StartArray,
+                JsonEvent::ValueString,
+                JsonEvent::ValueInt,
+                JsonEvent::EndArray
+            ]
+        );
+    }
+}
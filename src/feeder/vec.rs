@@ -0,0 +1,157 @@
+use alloc::vec::Vec;
+
+use super::JsonFeeder;
+
+/// A feeder that owns a growable buffer to which more input data can be
+/// appended at any time. In contrast to the
+/// [`PushJsonFeeder`](super::PushJsonFeeder), the bytes are not copied into
+/// a separate queue. The parser reads them directly from the buffer and the
+/// feeder only keeps track of the read position. Append data with
+/// [`push_bytes()`](Self::push_bytes()) or through
+/// [`buffer_mut()`](Self::buffer_mut()) (e.g. by reading from a socket into
+/// it), and call [`done()`](Self::done()) at the end of the JSON text.
+///
+/// Consumed bytes stay in the buffer until you call
+/// [`compact()`](Self::compact()).
+///
+/// ```
+/// use actson::feeder::VecJsonFeeder;
+/// use actson::{JsonEvent, JsonParser};
+///
+/// let mut parser = JsonParser::new(VecJsonFeeder::new());
+/// parser.feeder.push_bytes(br#"{"name": "#);
+/// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+/// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+/// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::NeedMoreInput));
+///
+/// parser.feeder.buffer_mut().extend_from_slice(br#""Elvis"}"#);
+/// parser.feeder.done();
+/// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+/// assert_eq!(parser.current_str().unwrap(), "Elvis");
+/// ```
+#[derive(Default, Clone)]
+pub struct VecJsonFeeder {
+    buf: Vec<u8>,
+    pos: usize,
+    done: bool,
+}
+
+impl VecJsonFeeder {
+    /// Create a new feeder with an empty buffer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new feeder that starts reading from the beginning of the
+    /// given buffer
+    pub fn from_vec(buf: Vec<u8>) -> Self {
+        VecJsonFeeder {
+            buf,
+            pos: 0,
+            done: false,
+        }
+    }
+
+    /// Append the given bytes to the buffer
+    pub fn push_bytes(&mut self, buf: &[u8]) {
+        self.buf.extend_from_slice(buf);
+    }
+
+    /// Get mutable access to the buffer so that more data can be appended
+    /// to it. Bytes before [`position()`](Self::position()) have already
+    /// been consumed by the parser. Changing them has no effect. Removing
+    /// bytes from the buffer is not supported (use
+    /// [`compact()`](Self::compact()) instead).
+    pub fn buffer_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.buf
+    }
+
+    /// Get the position in the buffer from which the next byte will be read
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Remove all consumed bytes from the beginning of the buffer to free
+    /// memory. This moves the remaining bytes to the front, so only call it
+    /// occasionally (e.g. when most of the buffer has been consumed).
+    pub fn compact(&mut self) {
+        self.buf.drain(..self.pos.min(self.buf.len()));
+        self.pos = 0;
+    }
+
+    /// Call this method to indicate that the end of the JSON text has been
+    /// reached and that there is no more input to parse.
+    pub fn done(&mut self) {
+        self.done = true;
+    }
+
+    /// Consume the feeder and return its buffer, including the bytes that
+    /// have already been consumed
+    pub fn into_inner(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+impl From<Vec<u8>> for VecJsonFeeder {
+    fn from(buf: Vec<u8>) -> Self {
+        Self::from_vec(buf)
+    }
+}
+
+impl JsonFeeder for VecJsonFeeder {
+    fn has_input(&self) -> bool {
+        self.pos < self.buf.len()
+    }
+
+    fn is_done(&self) -> bool {
+        self.done && !self.has_input()
+    }
+
+    fn next_input(&mut self) -> Option<u8> {
+        let b = *self.buf.get(self.pos)?;
+        self.pos += 1;
+        Some(b)
+    }
+
+    fn input_slice(&self) -> &[u8] {
+        self.buf.get(self.pos..).unwrap_or_default()
+    }
+
+    fn consume_input(&mut self, n: usize) {
+        self.pos += n;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use crate::feeder::{JsonFeeder, VecJsonFeeder};
+
+    /// Test that appended bytes are read and that consumed bytes can be
+    /// removed
+    #[test]
+    fn append_and_compact() {
+        let mut feeder = VecJsonFeeder::from(vec![b'a', b'b']);
+        assert_eq!(feeder.next_input(), Some(b'a'));
+        feeder.push_bytes(b"cd");
+        assert_eq!(feeder.input_slice(), b"bcd");
+        feeder.consume_input(2);
+        assert_eq!(feeder.position(), 3);
+
+        feeder.compact();
+        assert_eq!(feeder.position(), 0);
+        assert_eq!(feeder.input_slice(), b"d");
+        assert_eq!(feeder.next_input(), Some(b'd'));
+        assert_eq!(feeder.next_input(), None);
+        assert!(!feeder.is_done());
+
+        feeder.buffer_mut().push(b'e');
+        assert!(feeder.has_input());
+        feeder.done();
+        assert!(!feeder.is_done());
+        assert_eq!(feeder.next_input(), Some(b'e'));
+        assert!(feeder.is_done());
+        assert_eq!(feeder.into_inner(), b"de");
+    }
+}
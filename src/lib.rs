@@ -209,16 +209,62 @@
 //!     JsonEvent::ValueTrue,
 //! ]);
 //! ```
+//!
+//! ### `no_std` support
+//!
+//! Actson can be used without the standard library (but with an allocator).
+//! Disable the default `std` feature for this. The parser, the
+//! [`PushJsonFeeder`](feeder::PushJsonFeeder), and the
+//! [`SliceJsonFeeder`](feeder::SliceJsonFeeder) will still be available.
+//! Everything that depends on I/O (e.g. the
+//! [`BufReaderJsonFeeder`](feeder::BufReaderJsonFeeder) or the `tokio`,
+//! `serde`, and `serde_json` features) requires `std`.
+//!
+//! ### Faster string scanning
+//!
+//! Enable the `simd` feature to let the parser skip through the bodies of
+//! strings with vectorized search (using the `memchr` crate) instead of
+//! byte by byte. This only works with feeders that can provide their input
+//! as a contiguous slice, such as the
+//! [`SliceJsonFeeder`](feeder::SliceJsonFeeder) and the
+//! [`BufReaderJsonFeeder`](feeder::BufReaderJsonFeeder).
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod dom;
 pub mod event;
+pub mod extract;
 pub mod feeder;
 pub mod options;
 pub mod parser;
+pub mod splitter;
+pub mod stack;
+pub mod token;
+pub mod visitor;
+#[cfg(feature = "std")]
+pub mod writer;
 
 #[cfg(feature = "tokio")]
 pub mod tokio;
 
+#[cfg(feature = "serde")]
+pub mod serde;
+
 #[cfg(feature = "serde_json")]
 pub mod serde_json;
 
+#[cfg(feature = "rust_decimal")]
+pub mod decimal;
+
+#[cfg(feature = "num-bigint")]
+pub mod bigint;
+
 pub use event::JsonEvent;
+pub use extract::{collect_string_map, extract};
 pub use parser::JsonParser;
+pub use splitter::DocumentSplitter;
+pub use token::JsonToken;
+pub use visitor::drive_slice;
+#[cfg(feature = "std")]
+pub use writer::{minify, JsonWriter};
@@ -1,6 +1,30 @@
 /// Options for [`JsonParser`](super::JsonParser). Use [`JsonParserOptionsBuilder`]
 /// to create instances of this struct.
+///
+/// If the `serde` feature is enabled, the options can be serialized and
+/// deserialized (e.g. to load them from a configuration file). Missing
+/// fields are set to their default values.
+///
+/// ```rust
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use actson::options::{JsonParserOptions, JsonParserOptionsBuilder};
+///
+/// let options: JsonParserOptions =
+///     serde_json::from_str(r#"{"streaming": true, "max_depth": 16}"#).unwrap();
+/// assert!(options.streaming());
+/// assert_eq!(options.max_depth(), 16);
+///
+/// let options = JsonParserOptionsBuilder::from(options)
+///     .with_allow_comments(true)
+///     .build();
+/// assert!(options.streaming());
+/// assert!(options.allow_comments());
+/// # }
+/// ```
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct JsonParserOptions {
     /// The maximum stack depth
     pub(super) max_depth: usize,
@@ -8,6 +32,98 @@ pub struct JsonParserOptions {
     /// `true` if streaming mode should be enabled, which means that the parser
     /// will be able to handle a stream of multiple JSON values
     pub(super) streaming: bool,
+
+    /// The number of bytes after which a string value should be returned in
+    /// chunks
+    pub(super) max_string_chunk: Option<usize>,
+
+    /// The maximum number of bytes a string or field name may have after
+    /// decoding
+    pub(super) max_string_length: Option<usize>,
+
+    /// `true` if objects containing the same field name more than once
+    /// should be rejected
+    pub(super) reject_duplicate_keys: bool,
+
+    /// The capacity in bytes above which the parser's internal buffer
+    /// should be shrunk
+    pub(super) buffer_shrink_threshold: Option<usize>,
+
+    /// `true` if strings and field names may also be enclosed in single
+    /// quotes
+    pub(super) allow_single_quotes: bool,
+
+    /// `true` if field names may also be unquoted identifiers
+    pub(super) allow_unquoted_keys: bool,
+
+    /// `true` if the parser should try to continue with the next value after
+    /// an error in streaming mode
+    pub(super) error_recovery: bool,
+
+    /// `true` if integers that fit neither into an `i64` nor into a `u64`
+    /// should be returned as floating point values
+    pub(super) int_overflow_as_float: bool,
+
+    /// `true` if the parser should only validate the JSON text and not
+    /// collect the values of strings and numbers
+    pub(super) validate_only: bool,
+
+    /// `true` if the parser should return runs of white space between
+    /// tokens as events
+    pub(super) emit_whitespace: bool,
+
+    /// `true` if `//` and `/* */` comments are allowed between tokens
+    pub(super) allow_comments: bool,
+
+    /// `true` if the parser should return comments as events
+    pub(super) emit_comments: bool,
+
+    /// `true` if invalid UTF-16 surrogates should be replaced with U+FFFD
+    /// instead of causing an error
+    pub(super) lenient_unicode: bool,
+
+    /// `true` if the parser should stop as soon as the first top-level
+    /// value is complete
+    pub(super) stop_after_first_value: bool,
+
+    /// `true` if numbers may have leading zeros (e.g. `007`)
+    pub(super) allow_leading_zeros: bool,
+
+    /// `true` if hexadecimal integers (e.g. `0xFF`) are allowed
+    pub(super) allow_hex_numbers: bool,
+
+    /// The maximum number of events the parser may return
+    pub(super) max_events: Option<usize>,
+
+    /// `true` if strings may contain unescaped control characters
+    pub(super) allow_control_chars_in_strings: bool,
+
+    /// `true` if field names and the scalar values following them should be
+    /// returned as a single [`JsonEvent::Entry`](crate::JsonEvent::Entry)
+    pub(super) entry_events: bool,
+
+    /// `true` if numbers may start with a plus sign (e.g. `+1.5`)
+    pub(super) allow_plus_sign: bool,
+
+    /// `true` if strings should be validated to be UTF-8 while they are
+    /// parsed
+    pub(super) validate_utf8: bool,
+
+    /// `true` if each top-level value should be enclosed in
+    /// [`JsonEvent::StartDocument`](crate::JsonEvent::StartDocument) and
+    /// [`JsonEvent::EndDocument`](crate::JsonEvent::EndDocument) events
+    pub(super) document_events: bool,
+
+    /// The byte that must separate top-level values in streaming mode, or
+    /// `None` if any white space is sufficient
+    pub(super) record_separator: Option<u8>,
+
+    /// `true` if arrays and objects may end with a comma (e.g. `[1, 2,]`)
+    pub(super) allow_trailing_commas: bool,
+
+    /// `true` if a UTF-8 byte order mark at the beginning of the input
+    /// should be skipped
+    pub(super) skip_bom: bool,
 }
 
 /// A builder for [`JsonParserOptions`]
@@ -30,12 +146,45 @@ pub struct JsonParserOptionsBuilder {
     options: JsonParserOptions,
 }
 
+/// Create a builder that starts with the given options, so they can be
+/// modified further
+impl From<JsonParserOptions> for JsonParserOptionsBuilder {
+    fn from(options: JsonParserOptions) -> Self {
+        Self { options }
+    }
+}
+
 impl Default for JsonParserOptions {
     /// Returns default JSON parser options
     fn default() -> Self {
         Self {
             max_depth: 2048,
             streaming: false,
+            max_string_chunk: None,
+            max_string_length: None,
+            reject_duplicate_keys: false,
+            buffer_shrink_threshold: None,
+            allow_single_quotes: false,
+            allow_unquoted_keys: false,
+            error_recovery: false,
+            int_overflow_as_float: false,
+            validate_only: false,
+            emit_whitespace: false,
+            allow_comments: false,
+            emit_comments: false,
+            lenient_unicode: false,
+            stop_after_first_value: false,
+            allow_leading_zeros: false,
+            allow_hex_numbers: false,
+            max_events: None,
+            allow_control_chars_in_strings: false,
+            entry_events: false,
+            allow_plus_sign: false,
+            validate_utf8: false,
+            document_events: false,
+            record_separator: None,
+            allow_trailing_commas: false,
+            skip_bom: false,
         }
     }
 }
@@ -51,9 +200,197 @@ impl JsonParserOptions {
     pub fn streaming(&self) -> bool {
         self.streaming
     }
+
+    /// Returns the number of bytes after which a string value should be
+    /// returned in chunks, or `None` if string values should always be
+    /// returned as a whole
+    pub fn max_string_chunk(&self) -> Option<usize> {
+        self.max_string_chunk
+    }
+
+    /// Returns the maximum number of bytes a string or field name may have
+    /// after decoding, or `None` if the length is unlimited
+    pub fn max_string_length(&self) -> Option<usize> {
+        self.max_string_length
+    }
+
+    /// Returns `true` if objects containing the same field name more than
+    /// once should be rejected
+    pub fn reject_duplicate_keys(&self) -> bool {
+        self.reject_duplicate_keys
+    }
+
+    /// Returns the capacity in bytes above which the parser's internal
+    /// buffer should be shrunk, or `None` if the buffer should keep its
+    /// capacity
+    pub fn buffer_shrink_threshold(&self) -> Option<usize> {
+        self.buffer_shrink_threshold
+    }
+
+    /// Returns `true` if strings and field names may also be enclosed in
+    /// single quotes
+    pub fn allow_single_quotes(&self) -> bool {
+        self.allow_single_quotes
+    }
+
+    /// Returns `true` if field names may also be unquoted identifiers
+    pub fn allow_unquoted_keys(&self) -> bool {
+        self.allow_unquoted_keys
+    }
+
+    /// Returns `true` if the parser should try to continue with the next
+    /// value after an error in streaming mode
+    pub fn error_recovery(&self) -> bool {
+        self.error_recovery
+    }
+
+    /// Returns `true` if integers that fit neither into an `i64` nor into a
+    /// `u64` should be returned as floating point values
+    pub fn int_overflow_as_float(&self) -> bool {
+        self.int_overflow_as_float
+    }
+
+    /// Returns `true` if the parser should only validate the JSON text and
+    /// not collect the values of strings and numbers
+    pub fn validate_only(&self) -> bool {
+        self.validate_only
+    }
+
+    /// Returns `true` if the parser should return runs of white space
+    /// between tokens as events
+    pub fn emit_whitespace(&self) -> bool {
+        self.emit_whitespace
+    }
+
+    /// Returns `true` if `//` and `/* */` comments are allowed between
+    /// tokens
+    pub fn allow_comments(&self) -> bool {
+        self.allow_comments
+    }
+
+    /// Returns `true` if the parser should return comments as events
+    pub fn emit_comments(&self) -> bool {
+        self.emit_comments
+    }
+
+    /// Returns `true` if invalid UTF-16 surrogates should be replaced with
+    /// U+FFFD instead of causing an error
+    pub fn lenient_unicode(&self) -> bool {
+        self.lenient_unicode
+    }
+
+    /// Returns `true` if the parser should stop as soon as the first
+    /// top-level value is complete
+    pub fn stop_after_first_value(&self) -> bool {
+        self.stop_after_first_value
+    }
+
+    /// Returns `true` if numbers may have leading zeros (e.g. `007`)
+    pub fn allow_leading_zeros(&self) -> bool {
+        self.allow_leading_zeros
+    }
+
+    /// Returns `true` if hexadecimal integers (e.g. `0xFF`) are allowed
+    pub fn allow_hex_numbers(&self) -> bool {
+        self.allow_hex_numbers
+    }
+
+    /// Returns the maximum number of events the parser may return, or
+    /// `None` if the number is unlimited
+    pub fn max_events(&self) -> Option<usize> {
+        self.max_events
+    }
+
+    /// Returns `true` if strings may contain unescaped control characters
+    pub fn allow_control_chars_in_strings(&self) -> bool {
+        self.allow_control_chars_in_strings
+    }
+
+    /// Returns `true` if field names and the scalar values following them
+    /// are returned as a single [`JsonEvent::Entry`](crate::JsonEvent::Entry)
+    pub fn entry_events(&self) -> bool {
+        self.entry_events
+    }
+
+    /// Returns `true` if numbers may start with a plus sign
+    pub fn allow_plus_sign(&self) -> bool {
+        self.allow_plus_sign
+    }
+
+    /// Returns `true` if strings are validated to be UTF-8 while they are
+    /// parsed
+    pub fn validate_utf8(&self) -> bool {
+        self.validate_utf8
+    }
+
+    /// Returns `true` if each top-level value is enclosed in
+    /// [`JsonEvent::StartDocument`](crate::JsonEvent::StartDocument) and
+    /// [`JsonEvent::EndDocument`](crate::JsonEvent::EndDocument) events
+    pub fn document_events(&self) -> bool {
+        self.document_events
+    }
+
+    /// Returns the byte that must separate top-level values in streaming
+    /// mode, or `None` if any white space is sufficient
+    pub fn record_separator(&self) -> Option<u8> {
+        self.record_separator
+    }
+
+    /// Returns `true` if arrays and objects may end with a comma
+    pub fn allow_trailing_commas(&self) -> bool {
+        self.allow_trailing_commas
+    }
+
+    /// Returns `true` if a UTF-8 byte order mark at the beginning of the
+    /// input is skipped
+    pub fn skip_bom(&self) -> bool {
+        self.skip_bom
+    }
 }
 
 impl JsonParserOptionsBuilder {
+    /// Create a builder with lenient options that are useful for parsing
+    /// hand-written files such as configuration files. It enables comments
+    /// (see [`Self::with_allow_comments()`]), single-quoted strings (see
+    /// [`Self::with_allow_single_quotes()`]), unquoted field names (see
+    /// [`Self::with_allow_unquoted_keys()`]), trailing commas (see
+    /// [`Self::with_allow_trailing_commas()`]), and a leading byte order
+    /// mark (see [`Self::with_skip_bom()`]). You can change the options
+    /// further before calling [`Self::build()`].
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"{
+    ///     // the name
+    ///     name: 'Elvis',
+    /// }"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::relaxed()
+    ///         .with_max_depth(16)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+    /// assert_eq!(parser.current_str().unwrap(), "name");
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    /// assert_eq!(parser.current_str().unwrap(), "Elvis");
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndObject));
+    /// ```
+    pub fn relaxed() -> Self {
+        Self::default()
+            .with_allow_comments(true)
+            .with_allow_single_quotes(true)
+            .with_allow_unquoted_keys(true)
+            .with_allow_trailing_commas(true)
+            .with_skip_bom(true)
+    }
+
     /// Set the maximum stack depth
     pub fn with_max_depth(mut self, max_depth: usize) -> Self {
         self.options.max_depth = max_depth;
@@ -119,6 +456,813 @@ impl JsonParserOptionsBuilder {
         self
     }
 
+    /// Return string values in chunks of at least the given number of bytes
+    /// instead of collecting them completely in memory. As soon as a string
+    /// value has reached this size, the parser returns a
+    /// [`JsonEvent::PartialValueString`](crate::JsonEvent::PartialValueString)
+    /// event with the current chunk and then continues with an empty buffer.
+    /// The last chunk will be returned with a
+    /// [`JsonEvent::ValueString`](crate::JsonEvent::ValueString) event.
+    ///
+    /// Chunks are never split in the middle of an escape sequence or a UTF-8
+    /// character, so they may be slightly larger than the given size. Field
+    /// names are not affected by this option.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"["Elvis Presley"]"#.as_bytes();
+    ///
+    /// let feeder = SliceJsonFeeder::new(json);
+    /// let mut parser = JsonParser::new_with_options(
+    ///     feeder,
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_max_string_chunk(6)
+    ///         .build(),
+    /// );
+    ///
+    /// let mut chunks = Vec::new();
+    /// while let Some(e) = parser.next_event().unwrap() {
+    ///     match e {
+    ///         JsonEvent::PartialValueString | JsonEvent::ValueString => {
+    ///             chunks.push((e, parser.current_str().unwrap().to_string()));
+    ///         }
+    ///         _ => {}
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(chunks, vec![
+    ///     (JsonEvent::PartialValueString, "Elvis ".to_string()),
+    ///     (JsonEvent::PartialValueString, "Presle".to_string()),
+    ///     (JsonEvent::ValueString, "y".to_string()),
+    /// ]);
+    /// ```
+    pub fn with_max_string_chunk(mut self, max_string_chunk: usize) -> Self {
+        self.options.max_string_chunk = Some(max_string_chunk);
+        self
+    }
+
+    /// Set the maximum number of bytes a string or field name may have after
+    /// decoding. The parser will return
+    /// [`ParserError::StringTooLong`](crate::parser::ParserError::StringTooLong)
+    /// as soon as a string exceeds this length. If
+    /// [`with_max_string_chunk()`](Self::with_max_string_chunk()) is set,
+    /// the maximum length applies to each chunk. By default, the length is
+    /// unlimited.
+    pub fn with_max_string_length(mut self, max_string_length: usize) -> Self {
+        self.options.max_string_length = Some(max_string_length);
+        self
+    }
+
+    /// Reject objects that contain the same field name more than once. The
+    /// parser will return
+    /// [`ParserError::DuplicateKey`](crate::parser::ParserError::DuplicateKey)
+    /// as soon as it encounters a repeated field name. Field names are
+    /// compared after decoding, so `"a"` and `"\u0061"` are considered equal.
+    /// The field names of all currently open objects have to be kept in
+    /// memory, which is why this option is disabled by default.
+    pub fn with_reject_duplicate_keys(mut self, reject_duplicate_keys: bool) -> Self {
+        self.options.reject_duplicate_keys = reject_duplicate_keys;
+        self
+    }
+
+    /// Shrink the parser's internal buffer to the given number of bytes
+    /// whenever a new value starts and the buffer's capacity exceeds this
+    /// threshold (e.g. after a very long string has been parsed). By
+    /// default, the buffer keeps its capacity to avoid reallocations, which
+    /// is faster but means that a long-lived parser may hold on to a lot of
+    /// memory.
+    pub fn with_buffer_shrink_threshold(mut self, buffer_shrink_threshold: usize) -> Self {
+        self.options.buffer_shrink_threshold = Some(buffer_shrink_threshold);
+        self
+    }
+
+    /// Allow strings and field names to be enclosed in single quotes (as in
+    /// JSON5). Single-quoted strings support the same escape sequences as
+    /// double-quoted ones plus `\'`. Inside a single-quoted string, `"` is a
+    /// regular character and vice versa.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"{'name': 'Elvis "The King" Presley'}"#.as_bytes();
+    ///
+    /// let feeder = SliceJsonFeeder::new(json);
+    /// let mut parser = JsonParser::new_with_options(
+    ///     feeder,
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_allow_single_quotes(true)
+    ///         .build(),
+    /// );
+    ///
+    /// while let Some(e) = parser.next_event().unwrap() {
+    ///     match e {
+    ///         JsonEvent::FieldName => assert_eq!(parser.current_str().unwrap(), "name"),
+    ///         JsonEvent::ValueString => {
+    ///             assert_eq!(parser.current_str().unwrap(), r#"Elvis "The King" Presley"#)
+    ///         }
+    ///         _ => {}
+    ///     }
+    /// }
+    /// ```
+    pub fn with_allow_single_quotes(mut self, allow_single_quotes: bool) -> Self {
+        self.options.allow_single_quotes = allow_single_quotes;
+        self
+    }
+
+    /// Allow field names to be unquoted identifiers (as in JSON5). An
+    /// identifier consists of ASCII letters, digits, `_`, and `$`, and must
+    /// not start with a digit. Values must still be quoted.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"{ name: "Elvis" }"#.as_bytes();
+    ///
+    /// let feeder = SliceJsonFeeder::new(json);
+    /// let mut parser = JsonParser::new_with_options(
+    ///     feeder,
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_allow_unquoted_keys(true)
+    ///         .build(),
+    /// );
+    ///
+    /// while let Some(e) = parser.next_event().unwrap() {
+    ///     match e {
+    ///         JsonEvent::FieldName => assert_eq!(parser.current_str().unwrap(), "name"),
+    ///         JsonEvent::ValueString => assert_eq!(parser.current_str().unwrap(), "Elvis"),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// ```
+    pub fn with_allow_unquoted_keys(mut self, allow_unquoted_keys: bool) -> Self {
+        self.options.allow_unquoted_keys = allow_unquoted_keys;
+        self
+    }
+
+    /// Try to continue parsing after an error in streaming mode (see
+    /// [`Self::with_streaming()`]). This is useful to process a log of
+    /// concatenated JSON values where a single corrupt value should not
+    /// abort the whole stream. The option has no effect if streaming mode is
+    /// disabled.
+    ///
+    /// After an error, [`JsonParser::next_event()`](crate::JsonParser::next_event())
+    /// returns it as usual, but the parser is not left in an unusable state.
+    /// Instead, it discards all following input until it finds the start of
+    /// an object or array (`{` or `[`) that is preceded by white space, and
+    /// then continues from there. If the last value is cut off by the end
+    /// of the input, the error is returned once and the stream ends.
+    ///
+    /// Recovery is best-effort. Valid values that directly follow a corrupt
+    /// one without white space in between, as well as top-level values
+    /// other than objects or arrays, may be skipped. On the other hand, an
+    /// object or array nested in the corrupt value may be returned as if it
+    /// was a top-level value.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::JsonParser;
+    ///
+    /// let json = br#"{"name": "Elvis"}
+    /// {"name" "Max"}
+    /// {"name": "Bob"}"#;
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_streaming(true)
+    ///         .with_error_recovery(true)
+    ///         .build(),
+    /// );
+    ///
+    /// let mut names = Vec::new();
+    /// let mut errors = 0;
+    /// loop {
+    ///     match parser.next_event() {
+    ///         Ok(Some(actson::JsonEvent::ValueString)) => {
+    ///             names.push(parser.current_str().unwrap().to_string());
+    ///         }
+    ///         Ok(Some(_)) => {}
+    ///         Ok(None) => break,
+    ///         Err(_) => errors += 1,
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(names, vec!["Elvis", "Bob"]);
+    /// assert_eq!(errors, 1);
+    /// ```
+    pub fn with_error_recovery(mut self, error_recovery: bool) -> Self {
+        self.options.error_recovery = error_recovery;
+        self
+    }
+
+    /// Return integers that fit neither into an `i64` nor into a `u64` as
+    /// [`JsonEvent::ValueFloat`](crate::JsonEvent::ValueFloat) instead of
+    /// [`JsonEvent::ValueInt`](crate::JsonEvent::ValueInt), so they can be
+    /// read with [`JsonParser::current_float()`](crate::JsonParser::current_float())
+    /// (at the cost of precision). By default, such integers are returned as
+    /// [`JsonEvent::ValueInt`](crate::JsonEvent::ValueInt) and
+    /// [`JsonParser::current_int()`](crate::JsonParser::current_int())
+    /// fails for them.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"[1, 100000000000000000000]"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_int_overflow_as_float(true)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueFloat));
+    /// assert_eq!(parser.current_float().unwrap(), 1e20);
+    /// ```
+    pub fn with_int_overflow_as_float(mut self, int_overflow_as_float: bool) -> Self {
+        self.options.int_overflow_as_float = int_overflow_as_float;
+        self
+    }
+
+    /// Only validate the JSON text. The parser still returns all events, but
+    /// it does not collect the values of strings, field names, and numbers,
+    /// which saves time and memory. Methods such as
+    /// [`JsonParser::current_str()`](crate::JsonParser::current_str()) and
+    /// [`JsonParser::current_int()`](crate::JsonParser::current_int()) will
+    /// return empty strings or errors. Options that depend on these values
+    /// ([`Self::with_max_string_length()`], [`Self::with_max_string_chunk()`],
+    /// [`Self::with_reject_duplicate_keys()`], and
+    /// [`Self::with_int_overflow_as_float()`]) have no effect.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::JsonParser;
+    ///
+    /// fn is_valid(json: &[u8]) -> bool {
+    ///     let mut parser = JsonParser::new_with_options(
+    ///         SliceJsonFeeder::new(json),
+    ///         JsonParserOptionsBuilder::default()
+    ///             .with_validate_only(true)
+    ///             .build(),
+    ///     );
+    ///     loop {
+    ///         match parser.next_event() {
+    ///             Ok(Some(_)) => {}
+    ///             Ok(None) => return true,
+    ///             Err(_) => return false,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// assert!(is_valid(br#"{"name": "Elvis", "albums": [1, 2]}"#));
+    /// assert!(!is_valid(br#"{"name": "Elvis", "albums": [1, 2}"#));
+    /// ```
+    pub fn with_validate_only(mut self, validate_only: bool) -> Self {
+        self.options.validate_only = validate_only;
+        self
+    }
+
+    /// Return each run of white space between tokens as a
+    /// [`JsonEvent::Whitespace`](crate::JsonEvent::Whitespace). Call
+    /// [`JsonParser::current_str()`](crate::JsonParser::current_str()) to
+    /// get the white space characters. This is useful if you want to
+    /// reproduce the original formatting of a JSON text.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = "[1,\n  2]".as_bytes();
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_emit_whitespace(true)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::Whitespace));
+    /// assert_eq!(parser.current_str().unwrap(), "\n  ");
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// ```
+    pub fn with_emit_whitespace(mut self, emit_whitespace: bool) -> Self {
+        self.options.emit_whitespace = emit_whitespace;
+        self
+    }
+
+    /// Allow `//` line comments and `/* */` block comments wherever white
+    /// space is allowed (as in JSONC). By default, comments are skipped.
+    /// See [`Self::with_emit_comments()`] if you want to receive them.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"{
+    ///     // the name
+    ///     "name": "Elvis" /* the King */
+    /// }"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_allow_comments(true)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndObject));
+    /// ```
+    pub fn with_allow_comments(mut self, allow_comments: bool) -> Self {
+        self.options.allow_comments = allow_comments;
+        self
+    }
+
+    /// Return each comment as a [`JsonEvent::Comment`](crate::JsonEvent::Comment).
+    /// Call [`JsonParser::current_str()`](crate::JsonParser::current_str())
+    /// to get the text of the comment without its delimiters. This option
+    /// only has an effect if comments are allowed (see
+    /// [`Self::with_allow_comments()`]).
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"[1 /* one */]"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_allow_comments(true)
+    ///         .with_emit_comments(true)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::Comment));
+    /// assert_eq!(parser.current_str().unwrap(), " one ");
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndArray));
+    /// ```
+    pub fn with_emit_comments(mut self, emit_comments: bool) -> Self {
+        self.options.emit_comments = emit_comments;
+        self
+    }
+
+    /// Replace lone or mismatched UTF-16 surrogates in `\u` escape sequences
+    /// with the replacement character U+FFFD instead of returning
+    /// [`ParserError::InvalidSurrogate`](crate::parser::ParserError::InvalidSurrogate).
+    /// This is similar to what [`char::decode_utf16()`] and many other JSON
+    /// consumers (including web browsers) do.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#""a\ud83db\ude00""#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_lenient_unicode(true)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    /// assert_eq!(parser.current_str().unwrap(), "a\u{fffd}b\u{fffd}");
+    /// ```
+    pub fn with_lenient_unicode(mut self, lenient_unicode: bool) -> Self {
+        self.options.lenient_unicode = lenient_unicode;
+        self
+    }
+
+    /// Stop as soon as the first top-level value is complete. The parser
+    /// then returns `None` without reading any further input and without
+    /// waiting for the feeder to be done. This is useful if the JSON text
+    /// is followed by other data. A top-level number can only end at the
+    /// first byte that does not belong to it. This byte is not consumed.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = br#"{"a": 1} trailing data"#;
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_stop_after_first_value(true)
+    ///         .build(),
+    /// );
+    ///
+    /// while let Some(e) = parser.next_event().unwrap() {
+    ///     // ...
+    /// }
+    ///
+    /// assert_eq!(parser.remaining_input(), b" trailing data");
+    /// ```
+    pub fn with_stop_after_first_value(mut self, stop_after_first_value: bool) -> Self {
+        self.options.stop_after_first_value = stop_after_first_value;
+        self
+    }
+
+    /// Allow numbers with leading zeros (e.g. `007` or `-00.5`), which are
+    /// not valid JSON. The zeros are ignored when the value is read (e.g.
+    /// with [`JsonParser::current_int()`](crate::JsonParser::current_int())).
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"[007, 0010.5]"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_allow_leading_zeros(true)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.current_int::<i64>().unwrap(), 7);
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueFloat));
+    /// assert_eq!(parser.current_float().unwrap(), 10.5);
+    /// ```
+    pub fn with_allow_leading_zeros(mut self, allow_leading_zeros: bool) -> Self {
+        self.options.allow_leading_zeros = allow_leading_zeros;
+        self
+    }
+
+    /// Allow hexadecimal integers with a `0x` or `0X` prefix (e.g. `0xFF` or
+    /// `-0x1a`) as in JSON5. They are returned as [`JsonEvent::ValueInt`](crate::JsonEvent::ValueInt)
+    /// and [`JsonParser::current_int()`](crate::JsonParser::current_int())
+    /// converts them to their value. [`JsonParser::current_str()`](crate::JsonParser::current_str())
+    /// returns them as they appear in the JSON text.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"{"color": 0xFF8000}"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_allow_hex_numbers(true)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.current_int::<u32>().unwrap(), 0xFF8000);
+    /// ```
+    pub fn with_allow_hex_numbers(mut self, allow_hex_numbers: bool) -> Self {
+        self.options.allow_hex_numbers = allow_hex_numbers;
+        self
+    }
+
+    /// Set the maximum number of events (not counting
+    /// [`JsonEvent::NeedMoreInput`](crate::JsonEvent::NeedMoreInput)) the
+    /// parser may return. If the JSON text contains more tokens, the parser
+    /// returns [`ParserError::TooManyEvents`](crate::parser::ParserError::TooManyEvents).
+    /// Together with [`with_max_depth()`](Self::with_max_depth()) and
+    /// [`with_max_string_length()`](Self::with_max_string_length()), this
+    /// bounds the work the parser does for untrusted input.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::parser::ParserError;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"[1, 2, 3]"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_max_events(3)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert!(matches!(
+    ///     parser.next_event(),
+    ///     Err(ParserError::TooManyEvents(3))
+    /// ));
+    /// ```
+    pub fn with_max_events(mut self, max_events: usize) -> Self {
+        self.options.max_events = Some(max_events);
+        self
+    }
+
+    /// Allow unescaped control characters (bytes `0x00` to `0x1F`, e.g. raw
+    /// tabs or line breaks) in strings and field names, which is not valid
+    /// JSON. They are copied into the value verbatim. Otherwise, the parser
+    /// returns [`ParserError::UnescapedControlCharacter`](crate::parser::ParserError::UnescapedControlCharacter).
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = "\"first line\nsecond line\"".as_bytes();
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_allow_control_chars_in_strings(true)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    /// assert_eq!(parser.current_str().unwrap(), "first line\nsecond line");
+    /// ```
+    pub fn with_allow_control_chars_in_strings(
+        mut self,
+        allow_control_chars_in_strings: bool,
+    ) -> Self {
+        self.options.allow_control_chars_in_strings = allow_control_chars_in_strings;
+        self
+    }
+
+    /// Return a field name and a scalar value (string, number, boolean, or
+    /// `null`) that directly follows it as a single
+    /// [`JsonEvent::Entry`](crate::JsonEvent::Entry) instead of a
+    /// [`JsonEvent::FieldName`](crate::JsonEvent::FieldName) and a value
+    /// event. The field name can be obtained with
+    /// [`JsonParser::current_str()`](crate::JsonParser::current_str()) and
+    /// the value with
+    /// [`JsonParser::entry_value()`](crate::JsonParser::entry_value()).
+    /// Fields with objects or arrays as values are still returned as
+    /// [`JsonEvent::FieldName`](crate::JsonEvent::FieldName) followed by
+    /// [`JsonEvent::StartObject`](crate::JsonEvent::StartObject) or
+    /// [`JsonEvent::StartArray`](crate::JsonEvent::StartArray).
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"{"name": "Elvis", "albums": [], "age": 42}"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_entry_events(true)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::Entry));
+    /// assert_eq!(parser.current_str().unwrap(), "name");
+    /// assert_eq!(parser.entry_value().unwrap().as_str().unwrap(), "Elvis");
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndArray));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::Entry));
+    /// assert_eq!(parser.current_str().unwrap(), "age");
+    /// assert_eq!(parser.entry_value().unwrap().as_int::<i64>().unwrap(), 42);
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndObject));
+    /// ```
+    pub fn with_entry_events(mut self, entry_events: bool) -> Self {
+        self.options.entry_events = entry_events;
+        self
+    }
+
+    /// Allow numbers to start with a plus sign (e.g. `+1.5`), which is not
+    /// valid JSON. Such numbers are positive.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"[+1, +1.5e3]"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_allow_plus_sign(true)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.current_int::<i64>().unwrap(), 1);
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueFloat));
+    /// assert_eq!(parser.current_float().unwrap(), 1500.0);
+    /// ```
+    pub fn with_allow_plus_sign(mut self, allow_plus_sign: bool) -> Self {
+        self.options.allow_plus_sign = allow_plus_sign;
+        self
+    }
+
+    /// Validate that strings and field names are valid UTF-8 while they are
+    /// parsed. By default, the parser only checks this when you call
+    /// [`JsonParser::current_str()`](crate::JsonParser::current_str()). If
+    /// this option is enabled, invalid byte sequences are reported by
+    /// [`JsonParser::next_event()`](crate::JsonParser::next_event()) as
+    /// [`ParserError::InvalidUtf8`](crate::parser::ParserError::InvalidUtf8).
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::parser::ParserError;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = b"[\"Gr\xfc\xdfe\"]";
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_validate_utf8(true)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    /// assert!(matches!(
+    ///     parser.next_event(),
+    ///     Err(ParserError::InvalidUtf8(p)) if p.offset == 4
+    /// ));
+    /// ```
+    pub fn with_validate_utf8(mut self, validate_utf8: bool) -> Self {
+        self.options.validate_utf8 = validate_utf8;
+        self
+    }
+
+    /// Enclose each top-level value in a
+    /// [`JsonEvent::StartDocument`](crate::JsonEvent::StartDocument) and a
+    /// [`JsonEvent::EndDocument`](crate::JsonEvent::EndDocument) event. This
+    /// is mostly useful in streaming mode (see [`Self::with_streaming()`])
+    /// to find out where one value ends and the next one begins. White space
+    /// and comments between top-level values are not enclosed.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"{"name": "Elvis"} 42"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_streaming(true)
+    ///         .with_document_events(true)
+    ///         .build(),
+    /// );
+    ///
+    /// let mut events = Vec::new();
+    /// while let Some(e) = parser.next_event().unwrap() {
+    ///     events.push(e);
+    /// }
+    /// assert_eq!(events, vec![
+    ///     JsonEvent::StartDocument,
+    ///     JsonEvent::StartObject,
+    ///     JsonEvent::FieldName,
+    ///     JsonEvent::ValueString,
+    ///     JsonEvent::EndObject,
+    ///     JsonEvent::EndDocument,
+    ///     JsonEvent::StartDocument,
+    ///     JsonEvent::ValueInt,
+    ///     JsonEvent::EndDocument,
+    /// ]);
+    /// ```
+    pub fn with_document_events(mut self, document_events: bool) -> Self {
+        self.options.document_events = document_events;
+        self
+    }
+
+    /// Require top-level values to be separated by the given byte in
+    /// streaming mode (default: `None`). For example, use `b'\n'` for
+    /// newline-delimited JSON. Two values that are not separated by it
+    /// cause a [`ParserError::SyntaxError`](crate::parser::ParserError::SyntaxError),
+    /// even if there is other white space between them. The separator may
+    /// also be a byte that is not white space (such as the record separator
+    /// `0x1E` used in JSON text sequences). In this case, it is only
+    /// allowed before and between top-level values. The option has no effect if
+    /// streaming mode is disabled.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::parser::ParserError;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = b"{\"a\": 1}\n{\"b\": 2} {\"c\": 3}";
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_streaming(true)
+    ///         .with_record_separator(Some(b'\n'))
+    ///         .build(),
+    /// );
+    ///
+    /// let mut objects = 0;
+    /// let err = loop {
+    ///     match parser.next_event() {
+    ///         Ok(Some(JsonEvent::StartObject)) => objects += 1,
+    ///         Ok(_) => {}
+    ///         Err(err) => break err,
+    ///     }
+    /// };
+    /// assert_eq!(objects, 2);
+    /// assert!(matches!(err, ParserError::SyntaxError(_)));
+    /// ```
+    pub fn with_record_separator(mut self, record_separator: Option<u8>) -> Self {
+        self.options.record_separator = record_separator;
+        self
+    }
+
+    /// Allow a comma after the last element of an array or the last field
+    /// of an object (e.g. `[1, 2,]` or `{"a": 1,}`), which is not valid
+    /// JSON. An array or object that only consists of a comma (e.g. `[,]`)
+    /// is still rejected.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"{"a": [1,],}"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_allow_trailing_commas(true)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndArray));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndObject));
+    /// assert_eq!(parser.next_event().unwrap(), None);
+    /// ```
+    pub fn with_allow_trailing_commas(mut self, allow_trailing_commas: bool) -> Self {
+        self.options.allow_trailing_commas = allow_trailing_commas;
+        self
+    }
+
+    /// Skip a UTF-8 byte order mark (`EF BB BF`) at the beginning of the
+    /// input instead of rejecting it. Text editors on Windows often add one
+    /// when they save a file.
+    ///
+    /// ```rust
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = b"\xEF\xBB\xBF[true]";
+    ///
+    /// let mut parser = JsonParser::new_with_options(
+    ///     SliceJsonFeeder::new(json),
+    ///     JsonParserOptionsBuilder::default()
+    ///         .with_skip_bom(true)
+    ///         .build(),
+    /// );
+    ///
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueTrue));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndArray));
+    /// assert_eq!(parser.next_event().unwrap(), None);
+    /// ```
+    pub fn with_skip_bom(mut self, skip_bom: bool) -> Self {
+        self.options.skip_bom = skip_bom;
+        self
+    }
+
     /// Create a new [`JsonParserOptions`] object
     pub fn build(self) -> JsonParserOptions {
         self.options
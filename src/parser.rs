@@ -1,10 +1,24 @@
-use std::{
-    collections::VecDeque,
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
+    fmt,
+    iter::FusedIterator,
+    mem,
     num::ParseFloatError,
+    ops::Range,
     str::{from_utf8, Utf8Error},
 };
 
-use crate::{feeder::JsonFeeder, options::JsonParserOptions, JsonEvent};
+use crate::{
+    feeder::{DetachedJsonFeeder, JsonFeeder},
+    options::{JsonParserOptions, JsonParserOptionsBuilder},
+    stack::{HeapStack, InlineStack, ModeStack},
+    JsonEvent,
+};
 use btoi::ParseIntegerError;
 use num_traits::{CheckedAdd, CheckedMul, CheckedSub, FromPrimitive, Zero};
 use thiserror::Error;
@@ -103,8 +117,14 @@ const F4: i8 = 27; // false
 const N1: i8 = 28; // nu
 const N2: i8 = 29; // nul
 const N3: i8 = 30; // null
+const UK: i8 = 31; // unquoted key (handled outside the state transition table)
+const HX: i8 = 32; // 0x (handled outside the state transition table)
+const HD: i8 = 33; // hex digits (handled outside the state transition table)
 const RC: i8 = 99; // recover if in streaming mode, error otherwise
 
+/// The UTF-8 byte order mark that may appear at the beginning of the input
+const UTF8_BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
+
 /// The state transition table takes the current state and the current symbol,
 /// and returns either a new state or an action. An action is represented as a
 /// negative number. A JSON text is accepted if at the end of the text the
@@ -154,13 +174,39 @@ const MODE_OBJECT: i8 = 3;
 
 /// An error that can happen when reading the current value as a string
 #[derive(Error, Debug)]
-#[error("invalid string: {0}")]
-pub struct InvalidStringValueError(#[from] Utf8Error);
+pub enum InvalidStringValueError {
+    /// The current value is not valid UTF-8
+    #[error("invalid string: {0}")]
+    Utf8(#[from] Utf8Error),
+
+    /// The current event does not have a string value (e.g. it is a
+    /// [`JsonEvent::ValueInt`]). `None` if no event has been returned yet.
+    #[error("the current event does not have a string value: {0:?}")]
+    UnexpectedEvent(Option<JsonEvent>),
+}
 
 /// An error that can happen when trying to parse the current value to an integer
 #[derive(Error, Debug)]
-#[error("invalid integer: {0}")]
-pub struct InvalidIntValueError(#[from] ParseIntegerError);
+pub enum InvalidIntValueError {
+    /// The current value is an integer, but it does not fit into the
+    /// requested type (e.g. it is too large or it is negative and the type
+    /// is unsigned). You may try again with a wider type.
+    #[error("integer out of range for the requested type")]
+    Overflow,
+
+    /// The current value is not an integer
+    #[error("invalid integer: {0}")]
+    Invalid(
+        // `btoi` only implements `Error` if the `std` feature is enabled
+        #[cfg_attr(feature = "std", source)] ParseIntegerError,
+    ),
+
+    /// The current event is not a number (e.g. it is a
+    /// [`JsonEvent::ValueString`]). `None` if no event has been returned
+    /// yet.
+    #[error("the current event is not a number: {0:?}")]
+    UnexpectedEvent(Option<JsonEvent>),
+}
 
 /// An error that can happen when trying to parse the current value to a float
 #[derive(Error, Debug)]
@@ -170,19 +216,169 @@ pub enum InvalidFloatValueError {
 
     #[error("unable to parse current value to float: {0}")]
     Float(#[from] ParseFloatError),
+
+    /// The value is too large to be represented by a float (see
+    /// [`JsonParser::current_float_checked()`])
+    #[error("float value out of range")]
+    OutOfRange,
+}
+
+/// The shape of a number that has just been parsed (see
+/// [`JsonParser::number_kind()`])
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NumberKind {
+    /// A number without fraction and exponent (e.g. `42`)
+    Integer,
+
+    /// A number with a fraction but without exponent (e.g. `4.2`)
+    Fraction,
+
+    /// A number with an exponent and an optional fraction (e.g. `4.2e1`)
+    Exponent,
+}
+
+/// The kind of an object or array the parser is currently in (see
+/// [`JsonParser::container_stack()`])
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContainerKind {
+    /// A JSON object
+    Object,
+
+    /// A JSON array
+    Array,
+}
+
+/// The value of a [`JsonEvent::Entry`] (see [`JsonParser::entry_value()`])
+#[derive(Clone, Copy, Debug)]
+pub struct EntryValue<'a> {
+    event: JsonEvent,
+    buf: &'a [u8],
+    allow_hex_numbers: bool,
+}
+
+impl<'a> EntryValue<'a> {
+    /// Get the event the value would have been returned with if it was not
+    /// part of an entry (e.g. [`JsonEvent::ValueInt`])
+    pub fn event(&self) -> JsonEvent {
+        self.event
+    }
+
+    /// Get the value if it is a string (see [`JsonParser::current_str()`])
+    pub fn as_str(&self) -> Result<&'a str, InvalidStringValueError> {
+        Ok(from_utf8(self.buf)?)
+    }
+
+    /// Get the raw bytes of the value (see [`JsonParser::current_bytes()`])
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.buf
+    }
+
+    /// Get the value if it is an integer (see [`JsonParser::current_int()`])
+    pub fn as_int<I>(&self) -> Result<I, InvalidIntValueError>
+    where
+        I: FromPrimitive + Zero + CheckedAdd + CheckedSub + CheckedMul,
+    {
+        parse_int(self.buf, self.allow_hex_numbers)
+    }
+
+    /// Get the value if it is a number (see [`JsonParser::current_float()`])
+    pub fn as_float(&self) -> Result<f64, InvalidFloatValueError> {
+        Ok(self.as_str()?.parse()?)
+    }
+
+    /// Get the value if it is a boolean (see [`JsonParser::current_bool()`])
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.event {
+            JsonEvent::ValueTrue => Some(true),
+            JsonEvent::ValueFalse => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// A position in the JSON text
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Position {
+    /// The offset in bytes from the beginning of the JSON text
+    pub offset: usize,
+
+    /// The line number (starting at 1)
+    pub line: usize,
+
+    /// The column in bytes (starting at 1)
+    pub column: usize,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Details about a [`ParserError::SyntaxError`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxErrorDetails {
+    /// The byte that caused the error or `None` if the error was not caused
+    /// by a specific byte
+    pub byte: Option<u8>,
+
+    /// The name of the parser's internal state at the time of the error
+    /// (e.g. `VA` if the parser expected a value). Only meant for diagnostic
+    /// purposes.
+    pub state: &'static str,
+
+    /// The position of the error
+    pub position: Position,
+}
+
+impl fmt::Display for SyntaxErrorDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "syntax error at {}", self.position)?;
+        if let Some(b) = self.byte {
+            write!(f, ": unexpected '{}'", b.escape_ascii())?;
+        }
+        write!(f, " (state {})", self.state)
+    }
 }
 
 /// An error that can happen during parsing
-#[derive(Error, Debug, Clone, Copy)]
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
 pub enum ParserError {
     /// The JSON text contains an illegal byte (e.g. a non-whitespace control
-    /// character)
-    #[error("JSON text contains an illegal byte: `{0}'")]
-    IllegalInput(u8),
+    /// character) at the given position
+    #[error("JSON text contains an illegal byte at {1}: `{0}'")]
+    IllegalInput(u8, Position),
+
+    /// A string contains a control character (a byte below `0x20`) that is
+    /// not escaped. Control characters in strings must be written as escape
+    /// sequences (e.g. `\n` or `\u0001`).
+    #[error("unescaped control character {0:#04x} in string at {1}")]
+    UnescapedControlCharacter(u8, Position),
 
     /// The parsed text is not valid JSON
-    #[error("syntax error: the parsed text is not valid JSON")]
-    SyntaxError,
+    #[error("{0}")]
+    SyntaxError(SyntaxErrorDetails),
+
+    /// A `\u` escape sequence contains a UTF-16 surrogate that is not part
+    /// of a valid pair (i.e. a lone high or low surrogate, or a high
+    /// surrogate followed by something other than a low surrogate). The
+    /// position refers to the byte at which the error has been detected.
+    #[error("invalid UTF-16 surrogate at {0}")]
+    InvalidSurrogate(Position),
+
+    /// A string contains a byte sequence that is not valid UTF-8. Only
+    /// returned if UTF-8 validation is enabled (see
+    /// [`JsonParserOptionsBuilder::with_validate_utf8()`]). The position
+    /// refers to the first byte that does not fit into the sequence.
+    #[error("invalid UTF-8 in string at {0}")]
+    InvalidUtf8(Position),
+
+    /// The JSON text contains more than white space after the end of the
+    /// top-level value. Only returned if streaming mode is not enabled (see
+    /// [`JsonParserOptionsBuilder::with_streaming()`]).
+    #[error("unexpected data after the end of the JSON text at {0}")]
+    TrailingData(Position),
 
     /// There is nothing more to parse. The feeder is done and does not provide
     /// more input. Either the JSON text ended prematurely or
@@ -190,22 +386,343 @@ pub enum ParserError {
     /// too many times (i.e. after the end of a valid JSON text was reached).
     #[error("nothing more to parse")]
     NoMoreInput,
+
+    /// The JSON text ended before any value was found. It was either empty
+    /// or only contained white space.
+    #[error("empty input")]
+    EmptyInput,
+
+    /// The JSON text ended while the given number of objects and arrays
+    /// were still open (see [`JsonParser::unclosed_containers()`](crate::JsonParser::unclosed_containers()))
+    #[error("unexpected end of input: {open_containers} object(s) or array(s) left unclosed")]
+    UnexpectedEof { open_containers: usize },
+
+    /// A string or field name is longer than the configured maximum number
+    /// of bytes (see [`JsonParserOptionsBuilder::with_max_string_length()`])
+    #[error("string exceeds maximum length of {0} bytes")]
+    StringTooLong(usize),
+
+    /// An object contains the same field name more than once (see
+    /// [`JsonParserOptionsBuilder::with_reject_duplicate_keys()`])
+    #[error("duplicate field name: `{0}'")]
+    DuplicateKey(String),
+
+    /// The next event was not the expected one (see
+    /// [`JsonParser::expect()`](crate::JsonParser::expect())). `got` is
+    /// `None` if the end of the JSON text has been reached.
+    #[error("expected {expected:?} but got {got:?}")]
+    UnexpectedEvent {
+        expected: JsonEvent,
+        got: Option<JsonEvent>,
+    },
+
+    /// The JSON text is nested deeper than the maximum stack depth
+    /// (see [`JsonParserOptionsBuilder::with_max_depth()`])
+    #[error("maximum stack depth of {0} exceeded")]
+    MaxDepthExceeded(usize),
+
+    /// The JSON text contains more tokens than the maximum number of events
+    /// (see [`JsonParserOptionsBuilder::with_max_events()`])
+    #[error("maximum number of {0} events exceeded")]
+    TooManyEvents(usize),
+}
+
+impl ParserError {
+    /// Get a machine-readable code that describes the kind of this error.
+    /// In contrast to the error message, the code is stable and can be
+    /// passed across API boundaries (e.g. FFI).
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::parser::ErrorCode;
+    /// use actson::JsonParser;
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(b"[1}"));
+    /// let err = loop {
+    ///     if let Err(e) = parser.next_event() {
+    ///         break e;
+    ///     }
+    /// };
+    /// assert_eq!(err.code(), ErrorCode::Syntax);
+    /// assert_eq!(err.code() as u32, 3);
+    /// ```
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ParserError::IllegalInput(..) => ErrorCode::IllegalInput,
+            ParserError::UnescapedControlCharacter(..) => ErrorCode::UnescapedControlCharacter,
+            ParserError::SyntaxError(_) => ErrorCode::Syntax,
+            ParserError::InvalidSurrogate(_) => ErrorCode::InvalidSurrogate,
+            ParserError::InvalidUtf8(_) => ErrorCode::InvalidUtf8,
+            ParserError::TrailingData(_) => ErrorCode::TrailingData,
+            ParserError::NoMoreInput => ErrorCode::NoMoreInput,
+            ParserError::EmptyInput => ErrorCode::EmptyInput,
+            ParserError::UnexpectedEof { .. } => ErrorCode::UnexpectedEof,
+            ParserError::StringTooLong(_) => ErrorCode::StringTooLong,
+            ParserError::DuplicateKey(_) => ErrorCode::DuplicateKey,
+            ParserError::UnexpectedEvent { .. } => ErrorCode::UnexpectedEvent,
+            ParserError::MaxDepthExceeded(_) => ErrorCode::MaxDepth,
+            ParserError::TooManyEvents(_) => ErrorCode::TooManyEvents,
+        }
+    }
+}
+
+/// A machine-readable code for each kind of [`ParserError`] (see
+/// [`ParserError::code()`]). The numeric values of the codes will not
+/// change in future versions, but new codes may be added.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[repr(u32)]
+pub enum ErrorCode {
+    /// See [`ParserError::IllegalInput`]
+    IllegalInput = 1,
+
+    /// See [`ParserError::UnescapedControlCharacter`]
+    UnescapedControlCharacter = 2,
+
+    /// See [`ParserError::SyntaxError`]
+    Syntax = 3,
+
+    /// See [`ParserError::InvalidSurrogate`]
+    InvalidSurrogate = 4,
+
+    /// See [`ParserError::TrailingData`]
+    TrailingData = 5,
+
+    /// See [`ParserError::NoMoreInput`]
+    NoMoreInput = 6,
+
+    /// See [`ParserError::EmptyInput`]
+    EmptyInput = 7,
+
+    /// See [`ParserError::UnexpectedEof`]
+    UnexpectedEof = 8,
+
+    /// See [`ParserError::StringTooLong`]
+    StringTooLong = 9,
+
+    /// See [`ParserError::DuplicateKey`]
+    DuplicateKey = 10,
+
+    /// See [`ParserError::UnexpectedEvent`]
+    UnexpectedEvent = 11,
+
+    /// See [`ParserError::MaxDepthExceeded`]
+    MaxDepth = 12,
+
+    /// See [`ParserError::TooManyEvents`]
+    TooManyEvents = 13,
+
+    /// See [`ParserError::InvalidUtf8`]
+    InvalidUtf8 = 14,
+}
+
+/// Check if the given byte is JSON white space
+fn is_whitespace(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+/// Get the name of the given state
+fn state_name(state: i8) -> &'static str {
+    const NAMES: [&str; 34] = [
+        "GO", "OK", "OB", "KE", "CO", "VA", "AR", "ST", "ES", "U1", "U2", "U3", "U4", "MI", "ZE",
+        "IN", "F0", "FR", "E1", "E2", "E3", "T1", "T2", "T3", "F1", "F2", "F3", "F4", "N1", "N2",
+        "N3", "UK", "HX", "HD",
+    ];
+    NAMES.get(state as usize).copied().unwrap_or("??")
+}
+
+/// Check if the given buffer does not end with an incomplete UTF-8 character
+fn ends_with_complete_char(buf: &[u8]) -> bool {
+    for (i, &b) in buf.iter().rev().take(4).enumerate() {
+        if b & 0xC0 != 0x80 {
+            let len = match b {
+                0xC0..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                0xF0..=0xF7 => 4,
+                _ => 1,
+            };
+            return i + 1 >= len;
+        }
+    }
+    true
+}
+
+/// Parse the given buffer to an integer. Hexadecimal integers are only
+/// accepted if `allow_hex` is `true`.
+fn parse_int<I>(buf: &[u8], allow_hex: bool) -> Result<I, InvalidIntValueError>
+where
+    I: FromPrimitive + Zero + CheckedAdd + CheckedSub + CheckedMul,
+{
+    if allow_hex {
+        if let Some((negative, digits)) = split_hex(buf) {
+            return parse_hex(negative, digits);
+        }
+    }
+    btoi::btoi(buf).map_err(|e| {
+        // if the value is syntactically an integer, it is out of range
+        let digits = buf
+            .strip_prefix(b"-")
+            .or_else(|| buf.strip_prefix(b"+"))
+            .unwrap_or(buf);
+        if !digits.is_empty() && digits.iter().all(u8::is_ascii_digit) {
+            InvalidIntValueError::Overflow
+        } else {
+            InvalidIntValueError::Invalid(e)
+        }
+    })
+}
+
+/// If the given buffer contains a hexadecimal integer, return whether it is
+/// negative and its digits
+fn split_hex(buf: &[u8]) -> Option<(bool, &[u8])> {
+    let (negative, rest) = match buf.strip_prefix(b"-") {
+        Some(rest) => (true, rest),
+        None => (false, buf.strip_prefix(b"+").unwrap_or(buf)),
+    };
+    let digits = rest
+        .strip_prefix(b"0x")
+        .or_else(|| rest.strip_prefix(b"0X"))?;
+    Some((negative, digits))
+}
+
+/// Parse the given hexadecimal digits to an integer
+fn parse_hex<I>(negative: bool, digits: &[u8]) -> Result<I, InvalidIntValueError>
+where
+    I: FromPrimitive + Zero + CheckedAdd + CheckedSub + CheckedMul,
+{
+    let map_err = |e| {
+        if !digits.is_empty() && digits.iter().all(u8::is_ascii_hexdigit) {
+            InvalidIntValueError::Overflow
+        } else {
+            InvalidIntValueError::Invalid(e)
+        }
+    };
+
+    if !negative {
+        return btoi::btou_radix(digits, 16).map_err(map_err);
+    }
+
+    // prepend the sign so that the most negative value can be parsed
+    let mut buf = Vec::with_capacity(digits.len() + 1);
+    buf.push(b'-');
+    buf.extend_from_slice(digits);
+    btoi::btoi_radix(&buf, 16).map_err(map_err)
+}
+
+/// Proceed parsing with input from the given feeder and return the next
+/// event. This is a macro and not a method, so that it can borrow the
+/// parser's own feeder and its other fields at the same time.
+macro_rules! next_event_from {
+    ($self:ident, $feeder:expr) => {{
+        if let Some(e) = $self.deferred_event.take() {
+            // the event's buffer has been put aside while the field name
+            // before it was returned
+            mem::swap(&mut $self.current_buffer, &mut $self.entry_key);
+            mem::swap(&mut $self.token_span, &mut $self.entry_key_span);
+            return Ok(Some(e));
+        }
+
+        if $self.partial_string {
+            // the chunk of the string value has been returned
+            $self.clear_buffer();
+            $self.partial_string = false;
+        }
+
+        loop {
+            while $self.event1 == JsonEvent::NeedMoreInput {
+                if $self.options.stop_after_first_value
+                    && $self.state == OK
+                    && $self.stack.len() <= 1
+                {
+                    // the top-level value is complete. do not read any further.
+                    return $self.end_of_input();
+                }
+
+                // copy runs of regular characters in strings at once
+                #[cfg(feature = "simd")]
+                if $self.state == ST
+                    && !$self.high_surrogate_pair
+                    && $self.utf8_pending == 0
+                    && $self.putback_character.is_none()
+                {
+                    let input = $feeder.input_slice();
+                    let n = $self.string_run_len(input);
+                    if n > 0 {
+                        if $self.keep_string_bytes() {
+                            $self.current_buffer.extend_from_slice(&input[..n]);
+                        }
+                        if !$self.raw_buffer.is_empty() {
+                            $self.raw_buffer.extend_from_slice(&input[..n]);
+                        }
+                        $feeder.consume_input(n);
+                        $self.parsed_bytes += n;
+                        $self.check_string_chunk();
+                        continue;
+                    }
+                }
+
+                match $self.putback_character.take() {
+                    Some(b) => $self.process_byte(b)?,
+                    None => match $feeder.next_input() {
+                        Some(b) => $self.process_byte(b)?,
+                        None if $feeder.is_done() => return $self.end_of_input(),
+                        None => return Ok(Some(JsonEvent::NeedMoreInput)),
+                    },
+                }
+            }
+
+            let e = $self.take_event()?;
+            if !$self.options.entry_events {
+                return Ok(Some(e));
+            }
+            if let Some(e) = $self.pair_entry(e) {
+                return Ok(Some(e));
+            }
+        }
+    }};
+}
+
+/// The part of a comment the parser is currently in
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Comment {
+    /// The opening `/` has been read
+    Start,
+
+    /// Inside a `//` comment
+    Line,
+
+    /// Inside a `/* */` comment
+    Block,
+
+    /// Inside a `/* */` comment directly after a `*`
+    BlockStar,
+}
+
+/// An object or array the events returned by the parser are currently in
+#[derive(Clone)]
+struct Container {
+    /// The number of elements seen so far if the container is an array or
+    /// `None` if it is an object
+    elements: Option<usize>,
+
+    /// The most recent field name if the container is an object
+    field_name: Option<Vec<u8>>,
 }
 
 /// A non-blocking, event-based JSON parser.
-pub struct JsonParser<T> {
+#[derive(Clone)]
+pub struct JsonParser<T, S = HeapStack> {
     pub feeder: T,
 
     /// The stack containing the current modes
-    stack: VecDeque<i8>,
+    stack: S,
+
+    /// The options the parser has been created with
+    options: JsonParserOptions,
 
     /// The maximum number of modes on the stack
     depth: usize,
 
-    /// `true` if streaming mode is enabled, which means that the parser can
-    /// handle a stream of multiple JSON values
-    streaming: bool,
-
     /// The current state
     state: i8,
 
@@ -213,6 +730,11 @@ pub struct JsonParser<T> {
     /// IN (Integer), FR (Fraction) or the like
     current_buffer: Vec<u8>,
 
+    /// Collects the bytes of the current string as they appear in the JSON
+    /// text, but only once the string contains an escape sequence. Until
+    /// then, the raw bytes equal the ones in [`Self::current_buffer`].
+    raw_buffer: Vec<u8>,
+
     /// The first event returned by [`Self::parse()`]
     event1: JsonEvent,
 
@@ -222,12 +744,124 @@ pub struct JsonParser<T> {
     /// Tracks the number of bytes that have been processed
     parsed_bytes: usize,
 
+    /// The current line number (starting at 1)
+    line: usize,
+
+    /// The offset of the first byte of the current line
+    line_start: usize,
+
+    /// The byte range of the string, number, literal, or unquoted key that
+    /// has been parsed most recently
+    token_span: Range<usize>,
+
     /// A character that has been put back to be parsed at the next call
     /// of [`Self::next_event()`]
     putback_character: Option<u8>,
 
     /// Tracks if a UTF-16 high surrogate has been encountered
     high_surrogate_pair: bool,
+
+    /// The number of continuation bytes of the current UTF-8 character in a
+    /// string that still have to be validated
+    utf8_pending: u8,
+
+    /// The range the next continuation byte must be in
+    utf8_range: (u8, u8),
+
+    /// An event that has been returned by [`Self::peek_event()`] and that
+    /// will be returned by the next call of [`Self::next_event()`]
+    peeked: Option<Option<JsonEvent>>,
+
+    /// `true` if a [`JsonEvent::PartialValueString`] has been returned and
+    /// the buffer has to be cleared before parsing continues
+    partial_string: bool,
+
+    /// The quote character that has opened the current string
+    string_quote: u8,
+
+    /// The kind of the current number
+    number_kind: NumberKind,
+
+    /// The field names seen so far in each of the objects the parser is
+    /// currently in. `None` if duplicate field names should not be rejected.
+    object_keys: Option<Vec<BTreeSet<Vec<u8>>>>,
+
+    /// `true` if the parser should try to continue after an error in
+    /// streaming mode
+    error_recovery: bool,
+
+    /// Collects the current run of white space between tokens if
+    /// [`JsonParserOptions::emit_whitespace()`] is enabled
+    whitespace: Vec<u8>,
+
+    /// `true` if comments should be returned as [`JsonEvent::Comment`]
+    emit_comments: bool,
+
+    /// The part of the comment the parser is currently in or `None` if it
+    /// is not in a comment
+    comment: Option<Comment>,
+
+    /// The number of events returned so far
+    events: usize,
+
+    /// `true` if the parser is discarding input after an error
+    recovering: bool,
+
+    /// `true` if the last byte discarded while recovering was white space
+    recovery_after_whitespace: bool,
+
+    /// The containers the returned events are currently in
+    containers: Vec<Container>,
+
+    /// The index of the current event's value in its enclosing array
+    array_index: Option<usize>,
+
+    /// `true` if a string value is currently being returned in chunks
+    partial_element: bool,
+
+    /// The most recent event that has been returned (apart from
+    /// [`JsonEvent::NeedMoreInput`])
+    last_event: Option<JsonEvent>,
+
+    /// `true` if a field name has been put aside into [`Self::entry_key`]
+    /// to combine it with the following value into a [`JsonEvent::Entry`]
+    entry_pending: bool,
+
+    /// Holds the field name while waiting for the value of an entry, or the
+    /// buffer of [`Self::deferred_event`]
+    entry_key: Vec<u8>,
+
+    /// The byte range of the token in [`Self::entry_key`]
+    entry_key_span: Range<usize>,
+
+    /// The value of the current [`JsonEvent::Entry`]
+    entry_value: Vec<u8>,
+
+    /// The event the value of the current [`JsonEvent::Entry`] has been
+    /// parsed as
+    entry_value_event: JsonEvent,
+
+    /// An event that followed a field name but could not be combined with
+    /// it into a [`JsonEvent::Entry`]. It will be returned by the next call
+    /// of [`Self::next_event()`].
+    deferred_event: Option<JsonEvent>,
+
+    /// `true` if a [`JsonEvent::StartDocument`] has been returned but no
+    /// [`JsonEvent::EndDocument`] yet
+    in_document: bool,
+
+    /// The event that has been held back to return a
+    /// [`JsonEvent::StartDocument`] first, or the
+    /// [`JsonEvent::EndDocument`] that has to be returned next
+    document_event: Option<Option<JsonEvent>>,
+
+    /// `true` if the record separator has been found after the current
+    /// top-level value (see [`JsonParserOptions::record_separator()`])
+    record_separated: bool,
+
+    /// The number of bytes of the UTF-8 byte order mark that have been
+    /// skipped at the beginning of the input
+    bom_len: usize,
 }
 
 impl<T> JsonParser<T>
@@ -236,162 +870,1149 @@ where
 {
     /// Create a new JSON parser using the given [`JsonFeeder`]
     pub fn new(feeder: T) -> Self {
+        Self::new_with_options(feeder, JsonParserOptions::default())
+    }
+
+    /// Create a new JSON parser using the given [`JsonFeeder`] and with a
+    /// defined maximum stack depth
+    #[deprecated(since = "1.1.0", note = "use `new_with_options` instead")]
+    pub fn new_with_max_depth(feeder: T, max_depth: usize) -> Self {
+        Self::new_with_options(
+            feeder,
+            JsonParserOptionsBuilder::default()
+                .with_max_depth(max_depth)
+                .build(),
+        )
+    }
+
+    /// Create a new JSON parser using the given [`JsonFeeder`] and
+    /// [`JsonParserOptions`]
+    pub fn new_with_options(feeder: T, options: JsonParserOptions) -> Self {
+        JsonParser::new_with_stack(feeder, HeapStack::default(), options)
+    }
+
+    /// Create a new JSON parser using the given [`JsonFeeder`] that keeps
+    /// its stack inline instead of allocating it on the heap. The maximum
+    /// stack depth is `N`. If it is exceeded, the parser returns
+    /// [`ParserError::MaxDepthExceeded`].
+    ///
+    /// ```
+    /// use actson::JsonParser;
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::parser::ParserError;
+    ///
+    /// let feeder = SliceJsonFeeder::new(b"[[[1]]]");
+    /// let mut parser = JsonParser::new_with_inline_stack::<4>(feeder);
+    /// while parser.next_event().unwrap().is_some() {}
+    ///
+    /// let feeder = SliceJsonFeeder::new(b"[[[[1]]]]");
+    /// let mut parser = JsonParser::new_with_inline_stack::<4>(feeder);
+    /// assert!(matches!(parser.next_event(), Ok(Some(_))));
+    /// assert!(matches!(parser.next_event(), Ok(Some(_))));
+    /// assert!(matches!(parser.next_event(), Ok(Some(_))));
+    /// assert!(matches!(parser.next_event(), Err(ParserError::MaxDepthExceeded(4))));
+    /// ```
+    pub fn new_with_inline_stack<const N: usize>(feeder: T) -> JsonParser<T, InlineStack<N>> {
+        Self::new_with_inline_stack_and_options(feeder, JsonParserOptions::default())
+    }
+
+    /// Create a new JSON parser using the given [`JsonFeeder`] and
+    /// [`JsonParserOptions`] that keeps its stack inline instead of
+    /// allocating it on the heap. The maximum stack depth is the minimum of
+    /// `N` and [`JsonParserOptions::max_depth()`].
+    pub fn new_with_inline_stack_and_options<const N: usize>(
+        feeder: T,
+        options: JsonParserOptions,
+    ) -> JsonParser<T, InlineStack<N>> {
+        JsonParser::new_with_stack(feeder, InlineStack::default(), options)
+    }
+}
+
+impl JsonParser<DetachedJsonFeeder> {
+    /// Create a new JSON parser without a feeder of its own. Pass a feeder
+    /// to [`Self::next_event_with()`] each time you need the next event.
+    /// This makes it easy to feed the parser from different sources or to
+    /// keep parsers in a pool.
+    pub fn stateless() -> Self {
+        Self::new(DetachedJsonFeeder)
+    }
+
+    /// Create a new JSON parser without a feeder of its own but with the
+    /// given [`JsonParserOptions`] (see [`Self::stateless()`])
+    pub fn stateless_with_options(options: JsonParserOptions) -> Self {
+        Self::new_with_options(DetachedJsonFeeder, options)
+    }
+}
+
+impl<T, S> JsonParser<T, S>
+where
+    T: JsonFeeder,
+    S: ModeStack,
+{
+    /// Create a new JSON parser using the given [`JsonFeeder`], empty
+    /// stack, and [`JsonParserOptions`]
+    fn new_with_stack(feeder: T, mut stack: S, options: JsonParserOptions) -> Self {
+        let depth = stack
+            .capacity()
+            .map_or(options.max_depth, |c| c.min(options.max_depth));
+        stack.push(MODE_DONE);
         JsonParser {
             feeder,
-            stack: VecDeque::from([MODE_DONE]),
-            depth: 2048,
-            streaming: false,
+            stack,
+            options,
+            depth,
             state: GO,
             current_buffer: vec![],
+            raw_buffer: vec![],
             event1: JsonEvent::NeedMoreInput,
             event2: JsonEvent::NeedMoreInput,
             parsed_bytes: 0,
+            line: 1,
+            line_start: 0,
+            token_span: 0..0,
             putback_character: None,
             high_surrogate_pair: false,
+            utf8_pending: 0,
+            utf8_range: (0x80, 0xBF),
+            peeked: None,
+            partial_string: false,
+            object_keys: (options.reject_duplicate_keys && !options.validate_only).then(Vec::new),
+            string_quote: b'"',
+            number_kind: NumberKind::Integer,
+            error_recovery: options.streaming && options.error_recovery,
+            whitespace: vec![],
+            emit_comments: options.allow_comments && options.emit_comments,
+            comment: None,
+            events: 0,
+            recovering: false,
+            recovery_after_whitespace: false,
+            containers: vec![],
+            array_index: None,
+            partial_element: false,
+            last_event: None,
+            entry_pending: false,
+            entry_key: vec![],
+            entry_key_span: 0..0,
+            entry_value: vec![],
+            entry_value_event: JsonEvent::NeedMoreInput,
+            deferred_event: None,
+            in_document: false,
+            document_event: None,
+            record_separated: false,
+            bom_len: 0,
+        }
+    }
+
+    /// Push to the stack. Return [`ParserError::MaxDepthExceeded`] if the
+    /// maximum stack depth has been exceeded.
+    fn push(&mut self, mode: i8) -> Result<(), ParserError> {
+        if self.stack.len() >= self.depth {
+            return Err(ParserError::MaxDepthExceeded(self.depth));
+        }
+        self.stack.push(mode);
+        Ok(())
+    }
+
+    /// Pop the stack, assuring that the current mode matches the expectation.
+    /// Return `false` if there is underflow or if the modes mismatch.
+    fn pop(&mut self, mode: i8) -> bool {
+        if self.stack.last() != Some(mode) {
+            return false;
+        }
+        self.stack.pop();
+        true
+    }
+
+    /// Handle the given character if it starts or continues an unquoted
+    /// field name. Returns `true` if the character has been consumed.
+    fn parse_unquoted_key(&mut self, next_char: u8) -> Result<bool, ParserError> {
+        let is_ident = next_char.is_ascii_alphanumeric() || next_char == b'_' || next_char == b'$';
+        match self.state {
+            OB | KE if is_ident && !next_char.is_ascii_digit() => {
+                self.clear_buffer();
+                if !self.options.validate_only {
+                    self.current_buffer.push(next_char);
+                }
+                self.state = UK;
+                self.check_string_length()?;
+                Ok(true)
+            }
+
+            UK if is_ident => {
+                if !self.options.validate_only {
+                    self.current_buffer.push(next_char);
+                }
+                self.check_string_length()?;
+                Ok(true)
+            }
+
+            UK => {
+                // The field name has ended. Put back the character so it
+                // will be parsed again in the colon state.
+                self.check_duplicate_key()?;
+                self.state = CO;
+                self.event1 = JsonEvent::FieldName;
+                self.put_back(next_char);
+                Ok(true)
+            }
+
+            _ => Ok(false),
+        }
+    }
+
+    /// Handle the given character if it is part of a hexadecimal integer.
+    /// Returns `true` if the character has been consumed.
+    fn parse_hex_number(&mut self, next_char: u8) -> Result<bool, ParserError> {
+        match self.state {
+            ZE if next_char == b'x' || next_char == b'X' => self.state = HX,
+            HX | HD if next_char.is_ascii_hexdigit() => self.state = HD,
+            HX => return Err(self.syntax_error(Some(next_char))),
+            HD if next_char == b'.' => return Err(self.syntax_error(Some(next_char))),
+            HD => {
+                // The number has ended. Handle the character as if it
+                // followed a decimal integer.
+                self.state = IN;
+                return Ok(false);
+            }
+            _ => return Ok(false),
+        }
+        if !self.options.validate_only {
+            self.current_buffer.push(next_char);
+        }
+        Ok(true)
+    }
+
+    /// Skip the given character if it is part of the UTF-8 byte order mark at
+    /// the beginning of the input. Returns `true` if the character has been
+    /// consumed.
+    fn skip_bom(&mut self, next_char: u8) -> Result<bool, ParserError> {
+        if self.parsed_bytes != self.bom_len + 1 || self.bom_len == UTF8_BOM.len() {
+            // the byte order mark has already been skipped or the input
+            // does not start with one
+            return Ok(false);
+        }
+        if next_char == UTF8_BOM[self.bom_len] {
+            self.bom_len += 1;
+            return Ok(true);
+        }
+        if self.bom_len > 0 {
+            // an incomplete byte order mark
+            return Err(self.syntax_error(Some(next_char)));
+        }
+        Ok(false)
+    }
+
+    /// Handle the given character if it is the record separator before or
+    /// between top-level values. Returns `true` if the character has been consumed.
+    /// Record separators that are white space are never consumed, so they
+    /// can be handled like any other white space.
+    fn parse_record_separator(&mut self, next_char: u8) -> Result<bool, ParserError> {
+        if self.options.record_separator != Some(next_char)
+            || self.stack.len() != 1
+            || self.comment.is_some()
+            || (ST..=U4).contains(&self.state)
+        {
+            return Ok(false);
+        }
+        if self.state != GO {
+            self.record_separated = true;
+        }
+        if is_whitespace(next_char) {
+            return Ok(false);
+        }
+        if self.state != GO && self.state != OK {
+            // the separator ends the current value
+            self.event1 = self.state_to_event();
+            if self.event1 == JsonEvent::NeedMoreInput {
+                return Err(self.syntax_error(Some(next_char)));
+            }
+            self.state = OK;
+        }
+        Ok(true)
+    }
+
+    /// Handle the given character if it starts, continues, or ends a
+    /// comment. Returns `true` if the character has been consumed.
+    fn parse_comment(&mut self, next_char: u8) -> Result<bool, ParserError> {
+        let Some(comment) = self.comment else {
+            if next_char != b'/' {
+                return Ok(false);
+            }
+            match self.state {
+                GO | OK | OB | KE | CO | VA | AR => {}
+
+                ZE | IN | FR | E3 => {
+                    // the comment ends the current number
+                    self.event1 = self.state_to_event();
+                    self.state = OK;
+                }
+
+                _ => return Ok(false),
+            }
+            self.comment = Some(Comment::Start);
+            return Ok(true);
+        };
+
+        match (comment, next_char) {
+            (Comment::Start, b'/') => self.start_comment(Comment::Line),
+            (Comment::Start, b'*') => self.start_comment(Comment::Block),
+            (Comment::Start, _) => return Err(self.syntax_error(Some(next_char))),
+
+            (Comment::Line, b'\n') => {
+                // the line break is white space and will be parsed again
+                self.put_back(next_char);
+                self.end_comment();
+            }
+
+            (Comment::Block, b'*') => self.comment = Some(Comment::BlockStar),
+            (Comment::BlockStar, b'/') => self.end_comment(),
+            (Comment::BlockStar, b'*') => self.push_comment_byte(b'*')?,
+
+            (Comment::BlockStar, _) => {
+                self.comment = Some(Comment::Block);
+                self.push_comment_byte(b'*')?;
+                self.push_comment_byte(next_char)?;
+            }
+
+            (Comment::Line | Comment::Block, _) => self.push_comment_byte(next_char)?,
+        }
+
+        Ok(true)
+    }
+
+    /// Enter the body of a comment
+    fn start_comment(&mut self, comment: Comment) {
+        if self.emit_comments {
+            self.clear_buffer();
+        }
+        self.comment = Some(comment);
+    }
+
+    /// Leave the current comment and return it as an event if necessary
+    fn end_comment(&mut self) {
+        self.comment = None;
+        if self.emit_comments {
+            self.event1 = JsonEvent::Comment;
+        }
+    }
+
+    /// Collect a byte of the current comment if comments should be returned
+    fn push_comment_byte(&mut self, b: u8) -> Result<(), ParserError> {
+        if self.emit_comments {
+            self.current_buffer.push(b);
+            self.check_string_length()?;
+        }
+        Ok(())
+    }
+
+    /// Get the character class of the given quote character. Inside a
+    /// string, only the quote that has opened it can close it. All other
+    /// quotes are regular characters.
+    fn quote_class(&self, quote: u8) -> i8 {
+        if self.state == ST && self.string_quote != quote {
+            C_ETC
+        } else {
+            C_QUOTE
+        }
+    }
+
+    /// Clear the current buffer and release memory if its capacity exceeds
+    /// the configured threshold
+    fn clear_buffer(&mut self) {
+        self.current_buffer.clear();
+        self.raw_buffer.clear();
+        if let Some(threshold) = self.options.buffer_shrink_threshold {
+            if self.current_buffer.capacity() > threshold {
+                self.current_buffer.shrink_to(threshold);
+            }
+            if self.raw_buffer.capacity() > threshold {
+                self.raw_buffer.shrink_to(threshold);
+            }
+        }
+    }
+
+    /// Record the field name in the current buffer and return
+    /// [`ParserError::DuplicateKey`] if the current object already contains it
+    fn check_duplicate_key(&mut self) -> Result<(), ParserError> {
+        if let Some(keys) = self.object_keys.as_mut().and_then(|k| k.last_mut()) {
+            if keys.contains(&self.current_buffer) {
+                return Err(ParserError::DuplicateKey(
+                    String::from_utf8_lossy(&self.current_buffer).into_owned(),
+                ));
+            }
+            keys.insert(self.current_buffer.clone());
+        }
+        Ok(())
+    }
+
+    /// Forget the field names of the object that has just been closed
+    fn end_object_keys(&mut self) {
+        if let Some(keys) = &mut self.object_keys {
+            keys.pop();
+        }
+    }
+
+    /// Get the number of bytes that have been read from the feeder but
+    /// that have not been processed yet
+    pub(crate) fn unprocessed_bytes(&self) -> usize {
+        usize::from(self.putback_character.is_some())
+    }
+
+    /// Put back the given character to be parsed at the next call of
+    /// [`Self::next_event()`]
+    fn put_back(&mut self, c: u8) {
+        assert!(
+            self.putback_character.is_none(),
+            "Only one character can be put back"
+        );
+        self.putback_character = Some(c);
+        self.parsed_bytes -= 1;
+    }
+
+    /// Call this method to proceed parsing the JSON text and to get the next
+    /// event. The method returns [`Some(JsonEvent::NeedMoreInput)`](JsonEvent::NeedMoreInput)
+    /// if it needs more input data from the feeder or `None` if the end of the
+    /// JSON text has been reached.
+    ///
+    /// Note that a number at the top level of the JSON text (e.g. `42`) is
+    /// only returned once the parser has seen the byte after it or once the
+    /// feeder is done. Call [`Self::flush()`] to finalize such a number
+    /// earlier.
+    pub fn next_event(&mut self) -> Result<Option<JsonEvent>, ParserError> {
+        if let Some(e) = self.peeked.take() {
+            return Ok(e);
+        }
+        if let Some(e) = self.take_document_event() {
+            return Ok(e);
+        }
+        let e = self.next_parsed_event()?;
+        Ok(self.hand_out(e))
+    }
+
+    /// Get the next event from the feeder without tracking containers and
+    /// without enclosing top-level values in [`JsonEvent::StartDocument`]
+    /// and [`JsonEvent::EndDocument`]
+    fn next_parsed_event(&mut self) -> Result<Option<JsonEvent>, ParserError> {
+        next_event_from!(self, self.feeder)
+    }
+
+    /// Same as [`Self::next_event()`] but take the input from the given
+    /// feeder instead of [`Self::feeder`]. This decouples the parser from a
+    /// specific feeder, so it can be fed from different sources over its
+    /// life. Create the parser with [`JsonParser::stateless()`] if you
+    /// always want to pass the feeder to this method.
+    ///
+    /// ```
+    /// use actson::feeder::PushJsonFeeder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let mut parser = JsonParser::stateless();
+    ///
+    /// let mut header = PushJsonFeeder::new();
+    /// header.push_bytes(br#"{"name":"#);
+    /// assert_eq!(parser.next_event_with(&mut header).unwrap(), Some(JsonEvent::StartObject));
+    /// assert_eq!(parser.next_event_with(&mut header).unwrap(), Some(JsonEvent::FieldName));
+    /// assert_eq!(parser.next_event_with(&mut header).unwrap(), Some(JsonEvent::NeedMoreInput));
+    ///
+    /// let mut body = PushJsonFeeder::new();
+    /// body.push_bytes(br#""Elvis"}"#);
+    /// body.done();
+    /// assert_eq!(parser.next_event_with(&mut body).unwrap(), Some(JsonEvent::ValueString));
+    /// assert_eq!(parser.current_str().unwrap(), "Elvis");
+    /// assert_eq!(parser.next_event_with(&mut body).unwrap(), Some(JsonEvent::EndObject));
+    /// assert_eq!(parser.next_event_with(&mut body).unwrap(), None);
+    /// ```
+    pub fn next_event_with<F>(&mut self, feeder: &mut F) -> Result<Option<JsonEvent>, ParserError>
+    where
+        F: JsonFeeder,
+    {
+        if let Some(e) = self.peeked.take() {
+            return Ok(e);
+        }
+        if let Some(e) = self.take_document_event() {
+            return Ok(e);
+        }
+        let e = self.next_parsed_event_with(feeder)?;
+        Ok(self.hand_out(e))
+    }
+
+    /// Same as [`Self::next_parsed_event()`] but take the input from the
+    /// given feeder
+    fn next_parsed_event_with<F>(
+        &mut self,
+        feeder: &mut F,
+    ) -> Result<Option<JsonEvent>, ParserError>
+    where
+        F: JsonFeeder,
+    {
+        next_event_from!(self, feeder)
+    }
+
+    /// Finalize a number at the top level of the JSON text that has been
+    /// read completely but not returned yet. The parser cannot know that a
+    /// number like `42` at the end of the available input is complete,
+    /// because the next byte might be another digit. Normally, it waits for
+    /// more input or for the feeder to be done. This method behaves as if
+    /// the number was followed by the end of the input but, unlike
+    /// [`PushJsonFeeder::done()`](crate::feeder::PushJsonFeeder::done()),
+    /// it does not end the JSON text.
+    ///
+    /// Returns [`JsonEvent::ValueInt`] or [`JsonEvent::ValueFloat`] if a
+    /// number has been pending and `None` otherwise. If
+    /// [`document events`](crate::options::JsonParserOptionsBuilder::with_document_events())
+    /// are enabled and the number starts a new document, the method returns
+    /// [`JsonEvent::StartDocument`] and the number will be returned by the
+    /// next call to [`Self::next_event()`].
+    ///
+    /// ```
+    /// use actson::feeder::PushJsonFeeder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let mut feeder = PushJsonFeeder::new();
+    /// feeder.push_bytes(b"42");
+    /// let mut parser = JsonParser::new(feeder);
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::NeedMoreInput));
+    ///
+    /// assert_eq!(parser.flush().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.current_int::<i64>().unwrap(), 42);
+    /// assert_eq!(parser.flush().unwrap(), None);
+    /// ```
+    pub fn flush(&mut self) -> Result<Option<JsonEvent>, ParserError> {
+        if self.peeked.is_some()
+            || self.event1 != JsonEvent::NeedMoreInput
+            || self.putback_character.is_some()
+            || !self.whitespace.is_empty()
+            || self.stack.len() != 1
+            || !matches!(self.state, ZE | IN | FR | E3 | HD)
+        {
+            return Ok(None);
+        }
+        let e = self.state_to_event();
+        self.count_event()?;
+        self.state = OK;
+        Ok(self.hand_out(Some(e)))
+    }
+
+    /// Prepare the given event to be returned to the caller. Track the
+    /// containers it is in, and enclose it in document events if necessary.
+    /// Containers are only tracked here and not when the event is parsed,
+    /// because the parser may have to read ahead and hold back the event.
+    fn hand_out(&mut self, e: Option<JsonEvent>) -> Option<JsonEvent> {
+        if self.options.document_events {
+            return self.bracket_document(e);
+        }
+        if let Some(e) = e {
+            self.track_containers(e);
+        }
+        e
+    }
+
+    /// Return the event that has been held back by
+    /// [`Self::bracket_document()`] or a pending
+    /// [`JsonEvent::EndDocument`]
+    fn take_document_event(&mut self) -> Option<Option<JsonEvent>> {
+        let e = self.document_event.take()?;
+        if let Some(e) = e {
+            self.track_containers(e);
+        }
+        if e == Some(JsonEvent::EndDocument) {
+            self.in_document = false;
+        } else {
+            self.check_document_end(e);
+        }
+        Some(e)
+    }
+
+    /// Enclose top-level values in [`JsonEvent::StartDocument`] and
+    /// [`JsonEvent::EndDocument`]. If the given event starts a new
+    /// top-level value, hold it back and return a
+    /// [`JsonEvent::StartDocument`] instead.
+    fn bracket_document(&mut self, e: Option<JsonEvent>) -> Option<JsonEvent> {
+        let event = e?;
+        match event {
+            JsonEvent::NeedMoreInput | JsonEvent::Whitespace | JsonEvent::Comment => {
+                self.track_containers(event);
+            }
+            _ if !self.in_document => {
+                // the containers will be tracked when the event is returned
+                self.in_document = true;
+                self.document_event = Some(e);
+                self.track_containers(JsonEvent::StartDocument);
+                return Some(JsonEvent::StartDocument);
+            }
+            _ => {
+                self.track_containers(event);
+                self.check_document_end(e);
+            }
+        }
+        e
+    }
+
+    /// Check if the given event that is about to be returned completes the
+    /// current top-level value. If so, schedule a
+    /// [`JsonEvent::EndDocument`].
+    fn check_document_end(&mut self, e: Option<JsonEvent>) {
+        let Some(e) = e else {
+            return;
+        };
+        if self.containers.is_empty()
+            && !matches!(
+                e,
+                JsonEvent::NeedMoreInput
+                    | JsonEvent::Whitespace
+                    | JsonEvent::Comment
+                    | JsonEvent::PartialValueString
+            )
+        {
+            self.document_event = Some(Some(JsonEvent::EndDocument));
+        }
+    }
+
+    /// Process the given byte from the input
+    #[inline(always)]
+    fn process_byte(&mut self, b: u8) -> Result<(), ParserError> {
+        self.parsed_bytes += 1;
+        if self.recovering && self.discard(b) {
+            return Ok(());
+        }
+        let state = self.state;
+        let r = if self.state == ST
+            && !self.high_surrogate_pair
+            && self.utf8_pending == 0
+            && (32..=127).contains(&b)
+            && b != b'\\'
+            && b != b'"'
+            && b != self.string_quote
+        {
+            // shortcut
+            if self.keep_string_bytes() {
+                self.current_buffer.push(b);
+            }
+            if !self.raw_buffer.is_empty() {
+                self.raw_buffer.push(b);
+            }
+            self.check_string_length()
+                .map(|_| self.check_string_chunk())
+        } else {
+            self.parse(b)
+        };
+        if self.state >= ST || matches!(state, ST | T3 | F4 | N3) {
+            // the byte belongs to a string, number, literal, or unquoted key.
+            // closing quotes and the last bytes of literals end the token.
+            if state < ST {
+                self.token_span.start = self.parsed_bytes - 1;
+            }
+            self.token_span.end = self.parsed_bytes;
+        }
+        if b == b'\n' && self.putback_character.is_none() {
+            self.line += 1;
+            self.line_start = self.parsed_bytes;
+        }
+        if r.is_err() {
+            self.start_recovery(b);
+        }
+        r
+    }
+
+    /// Get the remaining events after the feeder is done
+    fn end_of_input(&mut self) -> Result<Option<JsonEvent>, ParserError> {
+        if let Some(comment) = self.comment.take() {
+            // only a line comment may end at the end of the text
+            if comment != Comment::Line {
+                let err = self.syntax_error(None);
+                return self.eof_error(err);
+            }
+            if self.emit_comments {
+                self.count_event()?;
+                return Ok(Some(JsonEvent::Comment));
+            }
+        }
+        if !self.whitespace.is_empty() {
+            // return the white space at the end of the text first
+            self.count_event()?;
+            self.take_whitespace();
+            self.event1 = JsonEvent::NeedMoreInput;
+            return Ok(Some(JsonEvent::Whitespace));
+        }
+        if self.state != OK {
+            let r = self.state_to_event();
+            if r != JsonEvent::NeedMoreInput {
+                self.count_event()?;
+                self.state = OK;
+                return Ok(Some(r));
+            }
+        }
+        // in streaming mode, a stream without any values is valid
+        let empty_stream = self.options.streaming && self.state == GO;
+        if (self.state == OK || empty_stream) && self.pop(MODE_DONE) {
+            Ok(None)
+        } else if self.state == GO {
+            Err(ParserError::EmptyInput)
+        } else if self.unclosed_containers() > 0 {
+            let err = ParserError::UnexpectedEof {
+                open_containers: self.unclosed_containers(),
+            };
+            self.eof_error(err)
+        } else {
+            self.eof_error(ParserError::NoMoreInput)
+        }
+    }
+
+    /// Return an error about a value that has been cut off by the end of
+    /// the input. If error recovery is enabled, the incomplete value is
+    /// discarded, so the error is only reported once and the next call
+    /// ends the stream.
+    fn eof_error(&mut self, err: ParserError) -> Result<Option<JsonEvent>, ParserError> {
+        if self.error_recovery {
+            self.reset_document();
+            self.recovering = false;
+        }
+        Err(err)
+    }
+
+    /// Count an event that is about to be returned. Return
+    /// [`ParserError::TooManyEvents`] if the maximum number of events has
+    /// been exceeded.
+    fn count_event(&mut self) -> Result<(), ParserError> {
+        if let Some(max_events) = self.options.max_events {
+            if self.events >= max_events {
+                return Err(ParserError::TooManyEvents(max_events));
+            }
+            self.events += 1;
+        }
+        Ok(())
+    }
+
+    /// Take the event that [`Self::parse()`] has produced
+    fn take_event(&mut self) -> Result<JsonEvent, ParserError> {
+        self.count_event()?;
+        let r = self.event1;
+        self.event1 = self.event2;
+        self.event2 = JsonEvent::NeedMoreInput;
+        Ok(r)
+    }
+
+    /// Combine a field name and a scalar value directly following it into a
+    /// [`JsonEvent::Entry`]. Return `None` if the given event is a field name
+    /// that has been put aside to wait for the value.
+    fn pair_entry(&mut self, event: JsonEvent) -> Option<JsonEvent> {
+        if !self.entry_pending {
+            if event != JsonEvent::FieldName {
+                return Some(event);
+            }
+            mem::swap(&mut self.current_buffer, &mut self.entry_key);
+            self.current_buffer.clear();
+            self.entry_key_span = self.token_span.clone();
+            self.entry_pending = true;
+            return None;
+        }
+
+        self.entry_pending = false;
+        match event {
+            JsonEvent::ValueString
+            | JsonEvent::ValueInt
+            | JsonEvent::ValueFloat
+            | JsonEvent::ValueTrue
+            | JsonEvent::ValueFalse
+            | JsonEvent::ValueNull => {
+                mem::swap(&mut self.current_buffer, &mut self.entry_value);
+                mem::swap(&mut self.current_buffer, &mut self.entry_key);
+                self.token_span.start = self.entry_key_span.start;
+                self.entry_value_event = event;
+                Some(JsonEvent::Entry)
+            }
+
+            _ => {
+                // return the field name now and the event afterwards
+                mem::swap(&mut self.current_buffer, &mut self.entry_key);
+                mem::swap(&mut self.token_span, &mut self.entry_key_span);
+                self.deferred_event = Some(event);
+                Some(JsonEvent::FieldName)
+            }
+        }
+    }
+
+    /// Update the element indexes and field names of the containers the
+    /// events are currently in based on the event that is about to be
+    /// returned
+    fn track_containers(&mut self, event: JsonEvent) {
+        if event != JsonEvent::NeedMoreInput {
+            self.last_event = Some(event);
+        }
+        match event {
+            JsonEvent::NeedMoreInput
+            | JsonEvent::Whitespace
+            | JsonEvent::Comment
+            | JsonEvent::StartDocument
+            | JsonEvent::EndDocument => {}
+
+            // the current buffer of an entry contains its field name
+            JsonEvent::FieldName | JsonEvent::Entry => {
+                self.array_index = None;
+                if let Some(c) = self.containers.last_mut() {
+                    let name = c.field_name.get_or_insert_with(Vec::new);
+                    name.clear();
+                    name.extend_from_slice(&self.current_buffer);
+                }
+            }
+
+            JsonEvent::StartObject | JsonEvent::StartArray => {
+                self.array_index = self.start_element();
+                self.containers.push(Container {
+                    elements: (event == JsonEvent::StartArray).then_some(0),
+                    field_name: None,
+                });
+            }
+
+            JsonEvent::EndObject | JsonEvent::EndArray => {
+                self.containers.pop();
+                self.array_index = match self.containers.last() {
+                    Some(Container {
+                        elements: Some(n), ..
+                    }) => (*n).checked_sub(1),
+                    _ => None,
+                };
+            }
+
+            JsonEvent::PartialValueString => {
+                // only the first chunk starts a new element
+                if !self.partial_element {
+                    self.array_index = self.start_element();
+                    self.partial_element = true;
+                }
+            }
+
+            JsonEvent::ValueString if self.partial_element => self.partial_element = false,
+
+            JsonEvent::ValueString
+            | JsonEvent::ValueInt
+            | JsonEvent::ValueFloat
+            | JsonEvent::ValueTrue
+            | JsonEvent::ValueFalse
+            | JsonEvent::ValueNull => self.array_index = self.start_element(),
+        }
+    }
+
+    /// Count a new element in the innermost container and return its index
+    /// if the container is an array
+    fn start_element(&mut self) -> Option<usize> {
+        match self.containers.last_mut() {
+            Some(Container {
+                elements: Some(n), ..
+            }) => {
+                *n += 1;
+                Some(*n - 1)
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the length of the run of regular characters at the beginning of
+    /// the given input, which can be copied into the current string at once.
+    /// The run ends at the next quote, backslash, or control character
+    /// (or non-ASCII character if UTF-8 validation is enabled), which have
+    /// to be handled by [`Self::parse()`]. The run is also
+    /// shortened so that it does not exceed the maximum string length or
+    /// chunk size.
+    #[cfg(feature = "simd")]
+    fn string_run_len(&self, input: &[u8]) -> usize {
+        let mut n = memchr::memchr3(b'"', b'\\', self.string_quote, input).unwrap_or(input.len());
+        if let Some(max_string_length) = self.options.max_string_length {
+            n = n.min(max_string_length.saturating_sub(self.current_buffer.len()));
+        }
+        if let Some(max_string_chunk) = self.options.max_string_chunk {
+            n = n.min(
+                max_string_chunk
+                    .max(1)
+                    .saturating_sub(self.current_buffer.len()),
+            );
+        }
+
+        let run = &input[..n];
+        if run.iter().fold(false, |c, &b| c | (b < 0x20)) {
+            // control characters are not allowed in strings
+            n = run.iter().position(|&b| b < 0x20).unwrap();
+        }
+        if self.options.validate_utf8 {
+            // non-ASCII characters have to be validated by Self::parse()
+            n = input[..n].iter().position(|&b| b >= 0x80).unwrap_or(n);
         }
+        n
     }
 
-    /// Create a new JSON parser using the given [`JsonFeeder`] and with a
-    /// defined maximum stack depth
-    #[deprecated(since = "1.1.0", note = "use `new_with_options` instead")]
-    pub fn new_with_max_depth(feeder: T, max_depth: usize) -> Self {
-        JsonParser {
-            feeder,
-            stack: VecDeque::from([MODE_DONE]),
-            depth: max_depth,
-            streaming: false,
-            state: GO,
-            current_buffer: vec![],
-            event1: JsonEvent::NeedMoreInput,
-            event2: JsonEvent::NeedMoreInput,
-            parsed_bytes: 0,
-            putback_character: None,
-            high_surrogate_pair: false,
+    /// Reset the parser after an error so that it can continue with the next
+    /// top-level value, provided that error recovery is enabled
+    fn start_recovery(&mut self, b: u8) {
+        if !self.error_recovery {
+            return;
         }
+        self.reset_document();
+        self.recovering = true;
+        self.recovery_after_whitespace = is_whitespace(b);
     }
 
-    /// Create a new JSON parser using the given [`JsonFeeder`] and
-    /// [`JsonParserOptions`]
-    pub fn new_with_options(feeder: T, options: JsonParserOptions) -> Self {
-        JsonParser {
-            feeder,
-            stack: VecDeque::from([MODE_DONE]),
-            depth: options.max_depth,
-            streaming: options.streaming,
-            state: GO,
-            current_buffer: vec![],
-            event1: JsonEvent::NeedMoreInput,
-            event2: JsonEvent::NeedMoreInput,
-            parsed_bytes: 0,
-            putback_character: None,
-            high_surrogate_pair: false,
+    /// Reset the state of the current JSON text so the parser can start
+    /// with a new one at the current position
+    fn reset_document(&mut self) {
+        while self.stack.pop().is_some() {}
+        self.stack.push(MODE_DONE);
+        if let Some(keys) = &mut self.object_keys {
+            keys.clear();
         }
+        self.state = GO;
+        self.clear_buffer();
+        self.event1 = JsonEvent::NeedMoreInput;
+        self.event2 = JsonEvent::NeedMoreInput;
+        self.putback_character = None;
+        self.high_surrogate_pair = false;
+        self.utf8_pending = 0;
+        self.partial_string = false;
+        self.whitespace.clear();
+        self.comment = None;
+        self.containers.clear();
+        self.array_index = None;
+        self.partial_element = false;
+        self.last_event = None;
+        self.entry_pending = false;
+        self.deferred_event = None;
+        self.in_document = false;
+        self.document_event = None;
+        self.record_separated = false;
     }
 
-    /// Push to the stack. Return `false` if the maximum stack depth has been
-    /// exceeded.
-    fn push(&mut self, mode: i8) -> bool {
-        if self.stack.len() >= self.depth {
+    /// Check if the given byte should be discarded while recovering from an
+    /// error. Recovery ends at the first `{` or `[` after white space.
+    fn discard(&mut self, b: u8) -> bool {
+        if b == b'\n' {
+            self.line += 1;
+            self.line_start = self.parsed_bytes;
+        }
+        if self.recovery_after_whitespace && (b == b'{' || b == b'[') {
+            self.recovering = false;
             return false;
         }
-        self.stack.push_back(mode);
+        self.recovery_after_whitespace = is_whitespace(b);
         true
     }
 
-    /// Pop the stack, assuring that the current mode matches the expectation.
-    /// Return `false` if there is underflow or if the modes mismatch.
-    fn pop(&mut self, mode: i8) -> bool {
-        if self.stack.is_empty() || *self.stack.back().unwrap() != mode {
-            return false;
+    /// Get the next event without consuming it. The following call of
+    /// [`Self::next_event()`] will return the same event. Accessors such as
+    /// [`Self::current_str()`] already reflect the peeked event.
+    ///
+    /// If the method returns [`Some(JsonEvent::NeedMoreInput)`](JsonEvent::NeedMoreInput),
+    /// nothing is cached, so you can provide more input to the feeder and then
+    /// call [`Self::peek_event()`] or [`Self::next_event()`] again.
+    pub fn peek_event(&mut self) -> Result<Option<JsonEvent>, ParserError> {
+        if let Some(e) = self.peeked {
+            return Ok(e);
         }
-        self.stack.pop_back();
-        true
+        let e = self.next_event()?;
+        if e != Some(JsonEvent::NeedMoreInput) {
+            self.peeked = Some(e);
+        }
+        Ok(e)
     }
 
-    /// Get the next input character either from [`Self::putback_character`] or
-    /// from [`Self::feeder`]
-    fn get_next_input(&mut self) -> Option<u8> {
-        self.putback_character
-            .take()
-            .or_else(|| self.feeder.next_input())
+    /// Get the next event and check if it matches the expected one. Return
+    /// [`ParserError::UnexpectedEvent`] otherwise. This is useful for
+    /// consumers that know the structure of the JSON text in advance.
+    ///
+    /// The method does not fill the feeder. It has to provide enough input
+    /// for the next event. If it does not,
+    /// [`JsonEvent::NeedMoreInput`] is treated like any other event and
+    /// does not match unless it is expected.
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::parser::ParserError;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"{"name": "Elvis"}"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// parser.expect(JsonEvent::StartObject).unwrap();
+    /// parser.expect(JsonEvent::FieldName).unwrap();
+    /// assert_eq!(parser.current_str().unwrap(), "name");
+    /// parser.expect(JsonEvent::ValueString).unwrap();
+    /// assert_eq!(parser.current_str().unwrap(), "Elvis");
+    ///
+    /// assert!(matches!(
+    ///     parser.expect(JsonEvent::FieldName),
+    ///     Err(ParserError::UnexpectedEvent {
+    ///         expected: JsonEvent::FieldName,
+    ///         got: Some(JsonEvent::EndObject),
+    ///     })
+    /// ));
+    /// ```
+    pub fn expect(&mut self, event: JsonEvent) -> Result<(), ParserError> {
+        match self.next_event()? {
+            Some(e) if e == event => Ok(()),
+            got => Err(ParserError::UnexpectedEvent {
+                expected: event,
+                got,
+            }),
+        }
     }
 
-    /// Put back the given character to be parsed at the next call of
-    /// [`Self::next_event()`]
-    fn put_back(&mut self, c: u8) {
-        assert!(
-            self.putback_character.is_none(),
-            "Only one character can be put back"
-        );
-        self.putback_character = Some(c);
-        self.parsed_bytes -= 1;
-    }
+    /// This function is called for each character (or partial character) in the
+    /// JSON text. It will set [`self::event1`] and [`self::event2`] accordingly.
+    /// As a precondition, these fields should have a value of [`JsonEvent::NeedMoreInput`].
+    fn parse(&mut self, next_char: u8) -> Result<(), ParserError> {
+        if self.options.skip_bom
+            && self.parsed_bytes <= UTF8_BOM.len()
+            && self.skip_bom(next_char)?
+        {
+            return Ok(());
+        }
 
-    /// Call this method to proceed parsing the JSON text and to get the next
-    /// event. The method returns [`Some(JsonEvent::NeedMoreInput)`](JsonEvent::NeedMoreInput)
-    /// if it needs more input data from the feeder or `None` if the end of the
-    /// JSON text has been reached.
-    pub fn next_event(&mut self) -> Result<Option<JsonEvent>, ParserError> {
-        while self.event1 == JsonEvent::NeedMoreInput {
-            if let Some(b) = self.get_next_input() {
-                self.parsed_bytes += 1;
-                if self.state == ST && (32..=127).contains(&b) && b != b'\\' && b != b'"' {
-                    // shortcut
-                    self.current_buffer.push(b);
-                } else {
-                    self.parse(b)?;
-                }
-            } else {
-                if self.feeder.is_done() {
-                    if self.state != OK {
-                        let r = self.state_to_event();
-                        if r != JsonEvent::NeedMoreInput {
-                            self.state = OK;
-                            return Ok(Some(r));
-                        }
-                    }
-                    return if self.state == OK && self.pop(MODE_DONE) {
-                        Ok(None)
-                    } else {
-                        Err(ParserError::NoMoreInput)
-                    };
-                }
-                return Ok(Some(JsonEvent::NeedMoreInput));
+        if self.options.allow_hex_numbers && self.parse_hex_number(next_char)? {
+            return Ok(());
+        }
+
+        if self.options.stop_after_first_value
+            && self.stack.len() == 1
+            && matches!(self.state, ZE | IN | FR | E3)
+            && !matches!(next_char, b'0'..=b'9' | b'.' | b'e' | b'E' | b'+' | b'-')
+        {
+            // A top-level number has ended. Leave the character that ended
+            // it in the input.
+            self.put_back(next_char);
+            self.event1 = self.state_to_event();
+            self.state = OK;
+            return Ok(());
+        }
+
+        if !self.whitespace.is_empty() && !is_whitespace(next_char) {
+            // The run of white space has ended. Return it first and put back
+            // the character so it will be parsed at the next call.
+            self.put_back(next_char);
+            self.take_whitespace();
+            return Ok(());
+        }
+
+        if self.options.streaming && self.parse_record_separator(next_char)? {
+            return Ok(());
+        }
+
+        if self.options.allow_comments && self.parse_comment(next_char)? {
+            return Ok(());
+        }
+
+        if self.options.allow_unquoted_keys && self.parse_unquoted_key(next_char)? {
+            return Ok(());
+        }
+
+        if self.options.emit_whitespace && self.state != ST && is_whitespace(next_char) {
+            self.whitespace.push(next_char);
+        }
+
+        if self.high_surrogate_pair
+            && match self.state {
+                ST => next_char != b'\\',
+                ES => next_char != b'u',
+                _ => false,
             }
+        {
+            // a high surrogate must be followed by a `\u` escape sequence.
+            // In the ES state, the backslash is already in the buffer.
+            let end = self.current_buffer.len() - usize::from(self.state == ES);
+            self.replace_surrogate(end)?;
         }
 
-        let r = self.event1;
-        self.event1 = self.event2;
-        self.event2 = JsonEvent::NeedMoreInput;
+        if self.state == OK
+            && !self.options.streaming
+            && self.stack.len() == 1
+            && self.stack.last() == Some(MODE_DONE)
+            && !is_whitespace(next_char)
+        {
+            return Err(ParserError::TrailingData(
+                self.position(self.parsed_bytes - 1),
+            ));
+        }
 
-        Ok(Some(r))
-    }
+        if self.options.validate_utf8 && (self.state == ST || self.utf8_pending > 0) {
+            self.validate_utf8_byte(next_char, self.parsed_bytes - 1)?;
+        }
+
+        if self.state == ST && next_char < 0x20 {
+            if !self.options.allow_control_chars_in_strings {
+                let position = self.position(self.parsed_bytes - 1);
+                return Err(ParserError::UnescapedControlCharacter(next_char, position));
+            }
+
+            // copy the control character into the string verbatim
+            if self.keep_string_bytes() {
+                self.current_buffer.push(next_char);
+            }
+            if !self.raw_buffer.is_empty() {
+                self.raw_buffer.push(next_char);
+            }
+            self.check_string_length()?;
+            self.check_string_chunk();
+            return Ok(());
+        }
 
-    /// This function is called for each character (or partial character) in the
-    /// JSON text. It will set [`self::event1`] and [`self::event2`] accordingly.
-    /// As a precondition, these fields should have a value of [`JsonEvent::NeedMoreInput`].
-    fn parse(&mut self, next_char: u8) -> Result<(), ParserError> {
         // determine the character's class.
         let next_class;
         if next_char >= 128 {
             next_class = C_ETC;
+        } else if next_char == b'"' || (next_char == b'\'' && self.options.allow_single_quotes) {
+            next_class = self.quote_class(next_char);
         } else {
             next_class = ASCII_CLASS[next_char as usize];
             if next_class <= __ {
-                return Err(ParserError::IllegalInput(next_char));
+                let position = self.position(self.parsed_bytes - 1);
+                return Err(ParserError::IllegalInput(next_char, position));
             }
         }
 
+        let next_class = if next_class == C_PLUS
+            && self.options.allow_plus_sign
+            && matches!(self.state, GO | OK | VA | AR)
+        {
+            // a plus sign in front of a number. handle it like a minus sign.
+            C_MINUS
+        } else {
+            next_class
+        };
+
         // Get the next state from the state transition table.
         let mut next_state =
             STATE_TRANSITION_TABLE[((self.state as usize) << 5) + next_class as usize];
 
+        if next_state == __
+            && self.state == ZE
+            && self.options.allow_leading_zeros
+            && (next_class == C_ZERO || next_class == C_DIGIT)
+        {
+            // a leading zero. continue with the rest of the integer.
+            next_state = IN;
+        }
+
+        if next_state == __
+            && self.options.allow_trailing_commas
+            && matches!((self.state, next_char), (VA, b']') | (KE, b'}'))
+        {
+            // a comma directly before the end of an array or object. handle
+            // the end like that of an empty array or object.
+            next_state = if next_char == b']' { -7 } else { -9 };
+        }
+
         // Try to recover if in streaming mode.
         if next_state == RC {
-            if self.streaming && self.stack.len() == 1 && *self.stack.back().unwrap() == MODE_DONE {
+            if self.options.streaming
+                && self.stack.len() == 1
+                && self.stack.last().unwrap() == MODE_DONE
+            {
                 // Streaming is enabled and we're in a state where we can handle
                 // another JSON value.
                 if self.state == OK {
+                    if self.options.record_separator.is_some() && !self.record_separated {
+                        // the values are not separated by the record separator
+                        return Err(self.syntax_error(Some(next_char)));
+                    }
+                    self.record_separated = false;
+
                     // The previous value has been converted to an event. Try
                     // again to get the next state but start from the GO state.
                     next_state = STATE_TRANSITION_TABLE[((GO as usize) << 5) + next_class as usize];
@@ -408,6 +2029,11 @@ where
             }
         }
 
+        if next_state == ST && !(ST..=U4).contains(&self.state) {
+            // remember which quote character has opened the string
+            self.string_quote = next_char;
+        }
+
         if next_state >= 0 {
             if (ST..=E3).contains(&next_state) {
                 // According to 'STATE_TRANSITION_TABLE', we don't need to check
@@ -415,6 +2041,15 @@ where
                 // 'state' being less than or equal to E3.
                 // if state >= ST && state <= E3 {
                 if self.state >= ST {
+                    if !self.raw_buffer.is_empty() {
+                        self.raw_buffer.push(next_char);
+                    } else if next_state == ES && self.state == ST && !self.options.validate_only {
+                        // the first escape sequence of the string. Up to
+                        // here, the raw bytes equal the decoded ones.
+                        self.raw_buffer.extend_from_slice(&self.current_buffer);
+                        self.raw_buffer.push(next_char);
+                    }
+
                     if self.state == ES {
                         if let Some(d) = Self::decode_escape_character(next_char) {
                             self.current_buffer.pop();
@@ -428,13 +2063,18 @@ where
 
                         // the last 6 bytes in the buffer will now be an
                         // escaped unicode character in the form \uXXXX
-                        self.decode_utf_escape()?;
-                    } else {
+                        self.decode_utf_escape(next_char)?;
+                    } else if self.keep_string_bytes() || (ES..=U4).contains(&next_state) {
                         self.current_buffer.push(next_char);
                     }
+
+                    if next_state == ST && !self.keep_string_bytes() {
+                        // only escape sequences are buffered for validation
+                        self.current_buffer.clear();
+                    }
                 } else {
-                    self.current_buffer.clear();
-                    if next_state != ST {
+                    self.clear_buffer();
+                    if next_state != ST && !self.options.validate_only {
                         self.current_buffer.push(next_char);
                     }
                 }
@@ -443,16 +2083,148 @@ where
                 self.event1 = self.state_to_event();
             }
 
+            // Keep track of the kind of the current number
+            match next_state {
+                MI => self.number_kind = NumberKind::Integer,
+                ZE | IN if self.state != MI && self.state != IN => {
+                    self.number_kind = NumberKind::Integer
+                }
+                F0 => self.number_kind = NumberKind::Fraction,
+                E1 => self.number_kind = NumberKind::Exponent,
+                _ => {}
+            }
+
             // Change the state.
             self.state = next_state;
+
+            if next_state == ST {
+                self.check_string_length()?;
+                self.check_string_chunk();
+            }
         } else {
             // Or perform one of the actions.
-            self.perform_action(next_state)?;
+            self.perform_action(next_state, next_char)?;
+        }
+
+        Ok(())
+    }
+
+    /// Move the collected run of white space into the current buffer and
+    /// make it the next event
+    fn take_whitespace(&mut self) {
+        self.clear_buffer();
+        self.current_buffer.append(&mut self.whitespace);
+        self.event1 = JsonEvent::Whitespace;
+    }
+
+    /// Get the position of the byte at the given offset. The byte must be
+    /// on the current line.
+    fn position(&self, offset: usize) -> Position {
+        Position {
+            offset,
+            line: self.line,
+            column: offset - self.line_start + 1,
+        }
+    }
+
+    /// Create a [`ParserError::SyntaxError`]. If a byte is given, it is
+    /// expected to be the last one that has been processed. Otherwise, the
+    /// error refers to the position after it.
+    pub(crate) fn syntax_error(&self, byte: Option<u8>) -> ParserError {
+        let offset = if byte.is_some() {
+            self.parsed_bytes - 1
+        } else {
+            self.parsed_bytes
+        };
+        ParserError::SyntaxError(SyntaxErrorDetails {
+            byte,
+            state: state_name(self.state),
+            position: self.position(offset),
+        })
+    }
+
+    /// Handle an invalid UTF-16 surrogate whose `\uXXXX` escape sequence
+    /// ends at the given position in the current buffer. Return
+    /// [`ParserError::InvalidSurrogate`] or, if lenient Unicode handling is
+    /// enabled, replace the escape sequence with U+FFFD.
+    fn replace_surrogate(&mut self, end: usize) -> Result<(), ParserError> {
+        if !self.options.lenient_unicode {
+            return Err(ParserError::InvalidSurrogate(
+                self.position(self.parsed_bytes - 1),
+            ));
+        }
+        let mut replacement = [0; 3];
+        let replacement = char::REPLACEMENT_CHARACTER.encode_utf8(&mut replacement);
+        self.current_buffer
+            .splice(end - 6..end, replacement.bytes());
+        self.high_surrogate_pair = false;
+        Ok(())
+    }
+
+    /// Validate the given byte of a string as part of a UTF-8 sequence.
+    /// Return [`ParserError::InvalidUtf8`] if it is not allowed at this
+    /// point (e.g. if it is a lone continuation byte, if it introduces an
+    /// overlong encoding or a surrogate, or if a multi-byte character is not
+    /// complete). `offset` is the position of the byte in the JSON text.
+    fn validate_utf8_byte(&mut self, b: u8, offset: usize) -> Result<(), ParserError> {
+        if self.utf8_pending > 0 {
+            if b < self.utf8_range.0 || b > self.utf8_range.1 {
+                return Err(ParserError::InvalidUtf8(self.position(offset)));
+            }
+            self.utf8_pending -= 1;
+            self.utf8_range = (0x80, 0xBF);
+            return Ok(());
         }
 
+        (self.utf8_pending, self.utf8_range) = match b {
+            0x00..=0x7F => return Ok(()),
+            0xC2..=0xDF => (1, (0x80, 0xBF)),
+            0xE0 => (2, (0xA0, 0xBF)),
+            0xE1..=0xEC | 0xEE..=0xEF => (2, (0x80, 0xBF)),
+            0xED => (2, (0x80, 0x9F)),
+            0xF0 => (3, (0x90, 0xBF)),
+            0xF1..=0xF3 => (3, (0x80, 0xBF)),
+            0xF4 => (3, (0x80, 0x8F)),
+            _ => return Err(ParserError::InvalidUtf8(self.position(offset))),
+        };
         Ok(())
     }
 
+    /// Check if regular bytes of strings and numbers should be collected in
+    /// the current buffer. In validate-only mode, this is only necessary
+    /// after a UTF-16 high surrogate, which has to be validated together
+    /// with the following escape sequence.
+    fn keep_string_bytes(&self) -> bool {
+        !self.options.validate_only || self.high_surrogate_pair
+    }
+
+    /// Check if the current string exceeds the maximum string length
+    fn check_string_length(&self) -> Result<(), ParserError> {
+        if let Some(max_string_length) = self.options.max_string_length {
+            if self.current_buffer.len() > max_string_length {
+                return Err(ParserError::StringTooLong(max_string_length));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check if the current string value has reached the maximum chunk size
+    /// and, if so, return it as a [`JsonEvent::PartialValueString`]. The
+    /// buffer is only split if it does not end with an incomplete escape
+    /// sequence, surrogate pair, or UTF-8 character.
+    fn check_string_chunk(&mut self) {
+        if let Some(max_string_chunk) = self.options.max_string_chunk {
+            if self.current_buffer.len() >= max_string_chunk.max(1)
+                && !self.high_surrogate_pair
+                && self.stack.last().unwrap() != MODE_KEY
+                && ends_with_complete_char(&self.current_buffer)
+            {
+                self.event1 = JsonEvent::PartialValueString;
+                self.partial_string = true;
+            }
+        }
+    }
+
     /// Decodes an escape character
     fn decode_escape_character(next_char: u8) -> Option<u8> {
         match next_char {
@@ -464,35 +2236,38 @@ where
             b'f' => Some(0x0C),
             b'/' => Some(0x2F),
             b'"' => Some(0x22),
+            b'\'' => Some(0x27),
             _ => None,
         }
     }
 
     /// Decodes a UTF escape sequence (e.g. `\uXXXX`, or a surrogate pair
     /// `\uXXXX\uXXXX`) to a character. Directly modifies the buffer.
-    fn decode_utf_escape(&mut self) -> Result<(), ParserError> {
+    fn decode_utf_escape(&mut self, next_char: u8) -> Result<(), ParserError> {
         // there have to be at least 6 bytes in the buffer
         if self.current_buffer.len() < 6 {
-            return Err(ParserError::SyntaxError);
+            return Err(self.syntax_error(Some(next_char)));
         }
 
         let unicode_in_utf8 = from_utf8(&self.current_buffer[self.current_buffer.len() - 4..])
-            .map_err(|_| ParserError::SyntaxError)?;
+            .map_err(|_| self.syntax_error(Some(next_char)))?;
 
         // convert the UTF-8 encoded unicode code point to a u32
-        let unicode =
-            u32::from_str_radix(unicode_in_utf8, 16).map_err(|_| ParserError::SyntaxError)?;
+        let unicode = u32::from_str_radix(unicode_in_utf8, 16)
+            .map_err(|_| self.syntax_error(Some(next_char)))?;
 
         if (0xD800..=0xDBFF).contains(&unicode) {
             // UTF-16 high pair
             if self.high_surrogate_pair {
-                return Err(ParserError::SyntaxError);
+                // the previous high surrogate is not followed by a low one
+                self.replace_surrogate(self.current_buffer.len() - 6)?;
             }
             self.high_surrogate_pair = true;
         } else if (0xDC00..=0xDFFF).contains(&unicode) {
             // UTF-16 low pair
             if !self.high_surrogate_pair {
-                return Err(ParserError::SyntaxError);
+                // lone low surrogate
+                return self.replace_surrogate(self.current_buffer.len());
             }
             self.high_surrogate_pair = false;
 
@@ -505,7 +2280,7 @@ where
             //   high  low
 
             if self.current_buffer.len() < 12 {
-                return Err(ParserError::SyntaxError);
+                return Err(self.syntax_error(Some(next_char)));
             }
 
             // create the high code point
@@ -514,18 +2289,18 @@ where
                     &self.current_buffer
                         [self.current_buffer.len() - 10..self.current_buffer.len() - 6],
                 )
-                .map_err(|_| ParserError::SyntaxError)?,
+                .map_err(|_| self.syntax_error(Some(next_char)))?,
                 16,
             )
-            .map_err(|_| ParserError::SyntaxError)?;
+            .map_err(|_| self.syntax_error(Some(next_char)))?;
 
             // create the low code point
             let low_code_point = u16::from_str_radix(
                 from_utf8(&self.current_buffer[self.current_buffer.len() - 4..])
-                    .map_err(|_| ParserError::SyntaxError)?,
+                    .map_err(|_| self.syntax_error(Some(next_char)))?,
                 16,
             )
-            .map_err(|_| ParserError::SyntaxError)?;
+            .map_err(|_| self.syntax_error(Some(next_char)))?;
 
             let char = char::decode_utf16([high_code_point, low_code_point].iter().cloned())
                 .map(|r| r.unwrap_or(char::REPLACEMENT_CHARACTER))
@@ -535,8 +2310,14 @@ where
             self.current_buffer.truncate(self.current_buffer.len() - 12);
             self.current_buffer.extend_from_slice(char.as_bytes());
         } else {
+            if self.high_surrogate_pair {
+                // a high surrogate must be followed by a low one
+                self.replace_surrogate(self.current_buffer.len() - 6)?;
+            }
+
             // convert the u32 to a char
-            let unicode_char = char::from_u32(unicode).ok_or(ParserError::SyntaxError)?;
+            let unicode_char =
+                char::from_u32(unicode).ok_or_else(|| self.syntax_error(Some(next_char)))?;
 
             // regular case
             // convert the char to a String and get the u8 bytes
@@ -554,13 +2335,14 @@ where
     }
 
     /// Perform an action that changes the parser state
-    fn perform_action(&mut self, action: i8) -> Result<(), ParserError> {
+    fn perform_action(&mut self, action: i8, next_char: u8) -> Result<(), ParserError> {
         match action {
             // empty }
             -9 => {
                 if !self.pop(MODE_KEY) {
-                    return Err(ParserError::SyntaxError);
+                    return Err(self.syntax_error(Some(next_char)));
                 }
+                self.end_object_keys();
                 self.state = OK;
                 self.event1 = JsonEvent::EndObject;
             }
@@ -568,8 +2350,9 @@ where
             // }
             -8 => {
                 if !self.pop(MODE_OBJECT) {
-                    return Err(ParserError::SyntaxError);
+                    return Err(self.syntax_error(Some(next_char)));
                 }
+                self.end_object_keys();
                 match self.state_to_event() {
                     JsonEvent::NeedMoreInput => self.event1 = JsonEvent::EndObject,
                     e => {
@@ -583,7 +2366,7 @@ where
             // ]
             -7 => {
                 if !self.pop(MODE_ARRAY) {
-                    return Err(ParserError::SyntaxError);
+                    return Err(self.syntax_error(Some(next_char)));
                 }
                 match self.state_to_event() {
                     JsonEvent::NeedMoreInput => self.event1 = JsonEvent::EndArray,
@@ -597,8 +2380,9 @@ where
 
             // {
             -6 => {
-                if !self.push(MODE_KEY) {
-                    return Err(ParserError::SyntaxError);
+                self.push(MODE_KEY)?;
+                if let Some(keys) = &mut self.object_keys {
+                    keys.push(BTreeSet::new());
                 }
                 self.state = OB;
                 self.event1 = JsonEvent::StartObject;
@@ -606,16 +2390,15 @@ where
 
             // [
             -5 => {
-                if !self.push(MODE_ARRAY) {
-                    return Err(ParserError::SyntaxError);
-                }
+                self.push(MODE_ARRAY)?;
                 self.state = AR;
                 self.event1 = JsonEvent::StartArray;
             }
 
             // "
             -4 => {
-                if *self.stack.back().unwrap() == MODE_KEY {
+                if self.stack.last().unwrap() == MODE_KEY {
+                    self.check_duplicate_key()?;
                     self.state = CO;
                     self.event1 = JsonEvent::FieldName;
                 } else {
@@ -626,12 +2409,13 @@ where
 
             // ,
             -3 => {
-                match *self.stack.back().unwrap() {
+                match self.stack.last().unwrap() {
                     MODE_OBJECT => {
                         // A comma causes a flip from object mode to key mode.
-                        if !self.pop(MODE_OBJECT) || !self.push(MODE_KEY) {
-                            return Err(ParserError::SyntaxError);
+                        if !self.pop(MODE_OBJECT) {
+                            return Err(self.syntax_error(Some(next_char)));
                         }
+                        self.push(MODE_KEY)?;
                         self.event1 = self.state_to_event();
                         self.state = KE;
                     }
@@ -642,7 +2426,7 @@ where
                     }
 
                     _ => {
-                        return Err(ParserError::SyntaxError);
+                        return Err(self.syntax_error(Some(next_char)));
                     }
                 }
             }
@@ -650,15 +2434,16 @@ where
             // :
             -2 => {
                 // A colon causes a flip from key mode to object mode.
-                if !self.pop(MODE_KEY) || !self.push(MODE_OBJECT) {
-                    return Err(ParserError::SyntaxError);
+                if !self.pop(MODE_KEY) {
+                    return Err(self.syntax_error(Some(next_char)));
                 }
+                self.push(MODE_OBJECT)?;
                 self.state = VA;
             }
 
             // Bad action.
             _ => {
-                return Err(ParserError::SyntaxError);
+                return Err(self.syntax_error(Some(next_char)));
             }
         }
 
@@ -670,7 +2455,10 @@ where
     /// not produce a JSON event
     fn state_to_event(&self) -> JsonEvent {
         match self.state {
-            IN | ZE => JsonEvent::ValueInt,
+            IN if self.options.int_overflow_as_float && self.int_overflows() => {
+                JsonEvent::ValueFloat
+            }
+            IN | ZE | HD => JsonEvent::ValueInt,
             FR..=E3 => JsonEvent::ValueFloat,
             T3 => JsonEvent::ValueTrue,
             F4 => JsonEvent::ValueFalse,
@@ -679,11 +2467,159 @@ where
         }
     }
 
+    /// Check if the integer in the current buffer fits neither into an `i64`
+    /// nor into a `u64`
+    fn int_overflows(&self) -> bool {
+        // 18 digits always fit into an i64
+        if self.current_buffer.len() <= 18 {
+            return false;
+        }
+        if self.hex_digits().is_some() {
+            // only decimal integers can be converted to floats
+            return false;
+        }
+        btoi::btoi::<i64>(&self.current_buffer).is_err()
+            && btoi::btou::<u64>(&self.current_buffer).is_err()
+    }
+
+    /// Get the event the values returned by the `current_*` methods (e.g.
+    /// [`current_str()`](Self::current_str())) belong to. This is the last
+    /// event that has been returned, not counting
+    /// [`JsonEvent::NeedMoreInput`], or `None` if there was none yet.
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"["Elvis"]"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// assert_eq!(parser.current_event(), None);
+    /// parser.next_event().unwrap();
+    /// parser.next_event().unwrap();
+    /// assert_eq!(parser.current_event(), Some(JsonEvent::ValueString));
+    /// assert_eq!(parser.current_str().unwrap(), "Elvis");
+    /// assert!(parser.current_int::<i64>().is_err());
+    /// ```
+    pub fn current_event(&self) -> Option<JsonEvent> {
+        self.last_event
+    }
+
+    /// Get the last event that has been returned, not counting
+    /// [`JsonEvent::NeedMoreInput`], or `None` if there was none yet. This
+    /// is an alias for [`current_event()`](Self::current_event()).
+    pub fn last_event(&self) -> Option<JsonEvent> {
+        self.current_event()
+    }
+
     /// Get the value of the string that has just been parsed. Call this
     /// function after you've received [`JsonEvent::FieldName`](JsonEvent#variant.FieldName)
     /// or [`JsonEvent::ValueString`](JsonEvent#variant.ValueString).
     pub fn current_str(&self) -> Result<&str, InvalidStringValueError> {
-        Ok(from_utf8(&self.current_buffer)?)
+        match self.last_event {
+            Some(
+                JsonEvent::FieldName
+                | JsonEvent::ValueString
+                | JsonEvent::PartialValueString
+                | JsonEvent::Whitespace
+                | JsonEvent::Comment
+                | JsonEvent::Entry,
+            ) => Ok(from_utf8(&self.current_buffer)?),
+            e => Err(InvalidStringValueError::UnexpectedEvent(e)),
+        }
+    }
+
+    /// Get the number of characters (Unicode scalar values) in the string
+    /// that has just been parsed. In contrast to the length of
+    /// [`current_bytes()`](Self::current_bytes()), this is useful for
+    /// enforcing length limits that are measured in characters.
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"["Grüße"]"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    /// assert_eq!(parser.current_char_count().unwrap(), 5);
+    /// assert_eq!(parser.current_bytes().len(), 7);
+    /// ```
+    pub fn current_char_count(&self) -> Result<usize, InvalidStringValueError> {
+        Ok(self.current_str()?.chars().count())
+    }
+
+    /// Get the text of the number that has just been parsed
+    pub(crate) fn current_number_str(&self) -> Result<&str, InvalidStringValueError> {
+        match self.last_event {
+            Some(JsonEvent::ValueInt | JsonEvent::ValueFloat) => {
+                Ok(from_utf8(&self.current_buffer)?)
+            }
+            e => Err(InvalidStringValueError::UnexpectedEvent(e)),
+        }
+    }
+
+    /// Get the raw bytes of the string that has just been parsed. Escape
+    /// sequences have already been decoded. In contrast to
+    /// [`current_str()`](Self::current_str()), this method does not check
+    /// if the bytes are valid UTF-8. Call this function after you've received
+    /// [`JsonEvent::FieldName`](JsonEvent#variant.FieldName) or
+    /// [`JsonEvent::ValueString`](JsonEvent#variant.ValueString).
+    pub fn current_bytes(&self) -> &[u8] {
+        &self.current_buffer
+    }
+
+    /// Get the bytes of the string that has just been parsed exactly as they
+    /// appear in the JSON text (without the enclosing quotes). In contrast to
+    /// [`current_bytes()`](Self::current_bytes()), escape sequences are not
+    /// decoded. This is useful if you want to reproduce the JSON text byte
+    /// for byte. Call this function after you've received
+    /// [`JsonEvent::FieldName`](JsonEvent#variant.FieldName),
+    /// [`JsonEvent::ValueString`](JsonEvent#variant.ValueString), or
+    /// [`JsonEvent::PartialValueString`](JsonEvent#variant.PartialValueString).
+    /// For all other events, the method returns the same as
+    /// [`current_bytes()`](Self::current_bytes()).
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"["Gr\u00fc\u00dfe\n"]"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    /// assert_eq!(parser.current_str().unwrap(), "Grüße\n");
+    /// assert_eq!(parser.current_raw_str(), br"Gr\u00fc\u00dfe\n");
+    /// ```
+    pub fn current_raw_str(&self) -> &[u8] {
+        if self.raw_buffer.is_empty() {
+            &self.current_buffer
+        } else {
+            &self.raw_buffer
+        }
+    }
+
+    /// Append the value of the string that has just been parsed to the given
+    /// buffer and return the number of bytes written. This is useful if you
+    /// want to keep the value while continuing to parse without having to
+    /// allocate a new string each time. Call this function after you've
+    /// received [`JsonEvent::FieldName`](JsonEvent#variant.FieldName) or
+    /// [`JsonEvent::ValueString`](JsonEvent#variant.ValueString).
+    pub fn copy_current_into(&self, buf: &mut String) -> Result<usize, InvalidStringValueError> {
+        let s = self.current_str()?;
+        buf.push_str(s);
+        Ok(s.len())
+    }
+
+    /// Append the raw bytes of the string that has just been parsed to the
+    /// given buffer and return the number of bytes written. Similar to
+    /// [`current_bytes()`](Self::current_bytes()), this method does not check
+    /// if the bytes are valid UTF-8.
+    pub fn copy_current_bytes_into(&self, buf: &mut Vec<u8>) -> usize {
+        buf.extend_from_slice(&self.current_buffer);
+        self.current_buffer.len()
     }
 
     /// Get the value of the integer that has just been parsed. Call this
@@ -692,17 +2628,573 @@ where
     where
         I: FromPrimitive + Zero + CheckedAdd + CheckedSub + CheckedMul,
     {
-        Ok(btoi::btoi(&self.current_buffer)?)
+        match self.last_event {
+            Some(JsonEvent::ValueInt | JsonEvent::ValueFloat) => {
+                parse_int(&self.current_buffer, self.options.allow_hex_numbers)
+            }
+            e => Err(InvalidIntValueError::UnexpectedEvent(e)),
+        }
+    }
+
+    /// Get the value of the integer that has just been parsed as an `i128`.
+    /// This is useful for values that do not fit into an `i64` (e.g.
+    /// nanosecond timestamps or large IDs). Call this function after you've
+    /// received [`JsonEvent::ValueInt`](JsonEvent#variant.ValueInt).
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"-170141183460469231731687303715884105728"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.current_i128().unwrap(), i128::MIN);
+    /// ```
+    pub fn current_i128(&self) -> Result<i128, InvalidIntValueError> {
+        self.current_int()
+    }
+
+    /// Get the value of the integer that has just been parsed as a `u128`.
+    /// Call this function after you've received
+    /// [`JsonEvent::ValueInt`](JsonEvent#variant.ValueInt).
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"340282366920938463463374607431768211455"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.current_u128().unwrap(), u128::MAX);
+    /// ```
+    pub fn current_u128(&self) -> Result<u128, InvalidIntValueError> {
+        self.current_int()
+    }
+
+    /// Get the value of the boolean that has just been parsed. Returns
+    /// `Some(true)` after [`JsonEvent::ValueTrue`](JsonEvent#variant.ValueTrue),
+    /// `Some(false)` after [`JsonEvent::ValueFalse`](JsonEvent#variant.ValueFalse),
+    /// and `None` after any other event.
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"[true, false, null]"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// let mut values = Vec::new();
+    /// while let Some(event) = parser.next_event().unwrap() {
+    ///     if event != JsonEvent::StartArray && event != JsonEvent::EndArray {
+    ///         values.push(parser.current_bool());
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(values, vec![Some(true), Some(false), None]);
+    /// ```
+    pub fn current_bool(&self) -> Option<bool> {
+        match self.last_event? {
+            JsonEvent::ValueTrue => Some(true),
+            JsonEvent::ValueFalse => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Get the value of the entry that has just been parsed. Returns `None`
+    /// if the last event was not a [`JsonEvent::Entry`] (see
+    /// [`JsonParserOptionsBuilder::with_entry_events()`]). The field name
+    /// can be obtained with [`current_str()`](Self::current_str()).
+    pub fn entry_value(&self) -> Option<EntryValue<'_>> {
+        if self.last_event != Some(JsonEvent::Entry) {
+            return None;
+        }
+        Some(EntryValue {
+            event: self.entry_value_event,
+            buf: &self.entry_value,
+            allow_hex_numbers: self.options.allow_hex_numbers,
+        })
+    }
+
+    /// If hexadecimal integers are allowed and the current buffer contains
+    /// one, return whether it is negative and its digits
+    fn hex_digits(&self) -> Option<(bool, &[u8])> {
+        if !self.options.allow_hex_numbers {
+            return None;
+        }
+        split_hex(&self.current_buffer)
+    }
+
+    /// Get the kind of the number that has just been parsed. Call this
+    /// function after you've received [`JsonEvent::ValueInt`](JsonEvent#variant.ValueInt)
+    /// or [`JsonEvent::ValueFloat`](JsonEvent#variant.ValueFloat).
+    ///
+    /// ```
+    /// use actson::{JsonParser, JsonEvent};
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::parser::NumberKind;
+    ///
+    /// let json = r#"[42, 4.2, 4.2e1, 42E-1]"#.as_bytes();
+    ///
+    /// let feeder = SliceJsonFeeder::new(json);
+    /// let mut parser = JsonParser::new(feeder);
+    /// let mut kinds = Vec::new();
+    /// while let Some(event) = parser.next_event().unwrap() {
+    ///     if event == JsonEvent::ValueInt || event == JsonEvent::ValueFloat {
+    ///         kinds.push(parser.number_kind());
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(kinds, vec![
+    ///     NumberKind::Integer,
+    ///     NumberKind::Fraction,
+    ///     NumberKind::Exponent,
+    ///     NumberKind::Exponent,
+    /// ]);
+    /// ```
+    pub fn number_kind(&self) -> NumberKind {
+        self.number_kind
+    }
+
+    /// Check if the number that has just been parsed has neither a fraction
+    /// nor an exponent. Call this function after you've received
+    /// [`JsonEvent::ValueInt`](JsonEvent#variant.ValueInt) or
+    /// [`JsonEvent::ValueFloat`](JsonEvent#variant.ValueFloat).
+    pub fn number_is_integer(&self) -> bool {
+        self.number_kind == NumberKind::Integer
     }
 
     /// Get the value of the float that has just been parsed. Call this
     /// function after you've received [`JsonEvent::ValueFloat`](JsonEvent#variant.ValueFloat).
     pub fn current_float(&self) -> Result<f64, InvalidFloatValueError> {
-        Ok(self.current_str()?.parse()?)
+        Ok(self.current_number_str()?.parse()?)
+    }
+
+    /// Get the value of the float that has just been parsed, just like
+    /// [`current_float()`](Self::current_float()), but return
+    /// [`InvalidFloatValueError::OutOfRange`] instead of infinity if the
+    /// value is too large to be represented by an `f64` (e.g. `1e400`).
+    pub fn current_float_checked(&self) -> Result<f64, InvalidFloatValueError> {
+        let f = self.current_float()?;
+        if f.is_infinite() {
+            // JSON does not have a literal for infinity
+            return Err(InvalidFloatValueError::OutOfRange);
+        }
+        Ok(f)
     }
 
     /// Return the number of bytes parsed so far
     pub fn parsed_bytes(&self) -> usize {
         self.parsed_bytes
     }
+
+    /// Get the byte range of the token that has just been parsed, relative
+    /// to the beginning of the JSON text. Call this function after you've
+    /// received [`JsonEvent::FieldName`], [`JsonEvent::ValueString`],
+    /// [`JsonEvent::ValueInt`], [`JsonEvent::ValueFloat`],
+    /// [`JsonEvent::ValueTrue`], [`JsonEvent::ValueFalse`], or
+    /// [`JsonEvent::ValueNull`]. For strings and field names, the range
+    /// includes the quotes. For [`JsonEvent::Entry`], it covers everything
+    /// from the field name to the end of the value. The result is
+    /// unspecified for other events.
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = br#"{"name": "Elvis", "age": 42}"#;
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+    /// assert_eq!(&json[parser.current_span()], br#""name""#);
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    /// assert_eq!(&json[parser.current_span()], br#""Elvis""#);
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.current_span(), 25..27);
+    /// ```
+    pub fn current_span(&self) -> Range<usize> {
+        self.token_span.clone()
+    }
+
+    /// Get the options the parser has been created with
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::JsonParser;
+    ///
+    /// let options = JsonParserOptionsBuilder::default()
+    ///     .with_streaming(true)
+    ///     .build();
+    ///
+    /// let parser = JsonParser::new_with_options(SliceJsonFeeder::new(b""), options);
+    /// assert!(parser.options().streaming());
+    /// assert_eq!(parser.options(), options);
+    /// ```
+    pub fn options(&self) -> JsonParserOptions {
+        self.options
+    }
+
+    /// Replace the feeder and reset the parser to its initial state, so it
+    /// can parse a new JSON text. The options are kept, and so is the
+    /// memory that has been allocated for the current value (unless it
+    /// exceeds the threshold configured with
+    /// [`JsonParserOptionsBuilder::with_buffer_shrink_threshold()`]). This
+    /// is useful to reuse parsers, e.g. from a pool.
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(b"[1"));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    ///
+    /// parser.reset_with_feeder(SliceJsonFeeder::new(b"true"));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueTrue));
+    /// assert_eq!(parser.next_event().unwrap(), None);
+    /// ```
+    pub fn reset_with_feeder(&mut self, feeder: T) {
+        self.feeder = feeder;
+        self.reset_document();
+        self.peeked = None;
+        self.parsed_bytes = 0;
+        self.line = 1;
+        self.line_start = 0;
+        self.token_span = 0..0;
+        self.entry_key_span = 0..0;
+        self.bom_len = 0;
+        self.string_quote = b'"';
+        self.number_kind = NumberKind::Integer;
+        self.recovering = false;
+        self.recovery_after_whitespace = false;
+        self.events = 0;
+    }
+
+    /// Consume the parser and return its feeder. This is useful if you want
+    /// to continue reading from the underlying source after the end of the
+    /// JSON text (e.g. see
+    /// [`BufReaderJsonFeeder::into_inner()`](crate::feeder::BufReaderJsonFeeder::into_inner())).
+    /// Note that the parser may have read one byte from the feeder that it
+    /// has not processed yet (e.g. the byte after a top-level number). This
+    /// byte is lost.
+    ///
+    /// ```
+    /// use std::io::{BufReader, Read};
+    ///
+    /// use actson::feeder::BufReaderJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let data = br#"{"length": 5}hello"#;
+    ///
+    /// let feeder = BufReaderJsonFeeder::new(BufReader::new(&data[..]));
+    /// let options = JsonParserOptionsBuilder::default()
+    ///     .with_stop_after_first_value(true)
+    ///     .build();
+    /// let mut parser = JsonParser::new_with_options(feeder, options);
+    /// while let Some(event) = parser.next_event().unwrap() {
+    ///     if event == JsonEvent::NeedMoreInput {
+    ///         parser.feeder.fill_buf().unwrap();
+    ///     }
+    /// }
+    ///
+    /// let mut rest = String::new();
+    /// let mut reader = parser.into_inner().into_inner();
+    /// reader.read_to_string(&mut rest).unwrap();
+    /// assert_eq!(rest, "hello");
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.feeder
+    }
+
+    /// Return the zero-based index of the value of the current event in its
+    /// enclosing array or `None` if the value is not inside an array (e.g.
+    /// because it is the value of an object field or a top-level value). For
+    /// [`JsonEvent::StartArray`], [`JsonEvent::EndArray`],
+    /// [`JsonEvent::StartObject`], and [`JsonEvent::EndObject`], the index
+    /// refers to the container itself. For [`JsonEvent::FieldName`], the
+    /// method always returns `None`.
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"[[1, 2], [3, 4]]"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// let mut indexes = Vec::new();
+    /// while let Some(e) = parser.next_event().unwrap() {
+    ///     indexes.push((e, parser.array_index()));
+    /// }
+    ///
+    /// assert_eq!(indexes, vec![
+    ///     (JsonEvent::StartArray, None),
+    ///     (JsonEvent::StartArray, Some(0)),
+    ///     (JsonEvent::ValueInt, Some(0)),
+    ///     (JsonEvent::ValueInt, Some(1)),
+    ///     (JsonEvent::EndArray, Some(0)),
+    ///     (JsonEvent::StartArray, Some(1)),
+    ///     (JsonEvent::ValueInt, Some(0)),
+    ///     (JsonEvent::ValueInt, Some(1)),
+    ///     (JsonEvent::EndArray, Some(1)),
+    ///     (JsonEvent::EndArray, None),
+    /// ]);
+    /// ```
+    pub fn array_index(&self) -> Option<usize> {
+        self.array_index
+    }
+
+    /// Return the most recent field name of the innermost object that has
+    /// one. This is the name of the field the value of the current event
+    /// belongs to, even if the value is nested in arrays or is an object
+    /// or array itself. For [`JsonEvent::FieldName`], the method returns
+    /// the new field name. Returns `None` if the value is not inside an
+    /// object field (e.g. because it is a top-level value) or if the field
+    /// name is not valid UTF-8.
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"{"name": "Elvis", "albums": [{"year": 1960}]}"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// let mut names = Vec::new();
+    /// while let Some(e) = parser.next_event().unwrap() {
+    ///     if e != JsonEvent::FieldName {
+    ///         names.push((e, parser.parent_field_name().map(String::from)));
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(names, vec![
+    ///     (JsonEvent::StartObject, None),
+    ///     (JsonEvent::ValueString, Some("name".to_string())),
+    ///     (JsonEvent::StartArray, Some("albums".to_string())),
+    ///     (JsonEvent::StartObject, Some("albums".to_string())),
+    ///     (JsonEvent::ValueInt, Some("year".to_string())),
+    ///     (JsonEvent::EndObject, Some("albums".to_string())),
+    ///     (JsonEvent::EndArray, Some("albums".to_string())),
+    ///     (JsonEvent::EndObject, None),
+    /// ]);
+    /// ```
+    pub fn parent_field_name(&self) -> Option<&str> {
+        self.containers
+            .iter()
+            .rev()
+            .find_map(|c| c.field_name.as_deref())
+            .and_then(|name| from_utf8(name).ok())
+    }
+
+    /// Return an iterator over the objects and arrays the current event is
+    /// in, from the outermost to the innermost one
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::parser::ContainerKind;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"{"albums": [{"year": 1956}]}"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// while let Some(e) = parser.next_event().unwrap() {
+    ///     if e == JsonEvent::ValueInt {
+    ///         assert_eq!(
+    ///             parser.container_stack().collect::<Vec<_>>(),
+    ///             vec![ContainerKind::Object, ContainerKind::Array, ContainerKind::Object],
+    ///         );
+    ///     }
+    /// }
+    /// assert_eq!(parser.container_stack().count(), 0);
+    /// ```
+    pub fn container_stack(&self) -> impl Iterator<Item = ContainerKind> + '_ {
+        // use the containers of the returned events and not the mode stack,
+        // which may already be ahead (e.g. if a number is terminated by `}`)
+        self.containers.iter().map(|c| match c.elements {
+            Some(_) => ContainerKind::Array,
+            None => ContainerKind::Object,
+        })
+    }
+
+    /// Return the number of objects and arrays that have been started but
+    /// not closed yet. This is useful to diagnose a
+    /// [`ParserError::UnexpectedEof`].
+    pub fn unclosed_containers(&self) -> usize {
+        // ignore MODE_DONE, which is at the bottom of the stack until the
+        // end of the JSON text has been reached
+        self.stack.len().saturating_sub(1)
+    }
+
+    /// Return an iterator that calls [`Self::next_event()`] until the end of
+    /// the JSON text has been reached or until an error has occurred. This is
+    /// mostly useful for feeders that have all input data available up
+    /// front (such as [`SliceJsonFeeder`](crate::feeder::SliceJsonFeeder)).
+    /// For other feeders, the iterator returns
+    /// [`JsonEvent::NeedMoreInput`] whenever the feeder needs more input.
+    ///
+    /// The iterator borrows the parser mutably. Use [`Events::parser()`]
+    /// to access the values of the current event.
+    ///
+    /// ```
+    /// use actson::{JsonParser, JsonEvent};
+    /// use actson::feeder::SliceJsonFeeder;
+    ///
+    /// let json = r#"{"name": "Elvis"}"#.as_bytes();
+    ///
+    /// let feeder = SliceJsonFeeder::new(json);
+    /// let mut parser = JsonParser::new(feeder);
+    /// let mut events = parser.events();
+    /// while let Some(event) = events.next() {
+    ///     match event.unwrap() {
+    ///         JsonEvent::FieldName => assert!(matches!(events.parser().current_str(), Ok("name"))),
+    ///         JsonEvent::ValueString => assert!(matches!(events.parser().current_str(), Ok("Elvis"))),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// ```
+    pub fn events(&mut self) -> Events<'_, T, S> {
+        Events {
+            parser: self,
+            finished: false,
+        }
+    }
+}
+
+/// A snapshot of the state of a [`JsonParser`] and its feeder. Created by
+/// [`JsonParser::checkpoint()`].
+pub struct Checkpoint<T, S = HeapStack> {
+    parser: JsonParser<T, S>,
+}
+
+impl<T, S> JsonParser<T, S>
+where
+    T: JsonFeeder + Clone,
+    S: ModeStack + Clone,
+{
+    /// Take a snapshot of the parser's state, including the feeder's
+    /// position, so that the parser can later be rewound to this point with
+    /// [`Self::restore()`]. This is useful for speculative parsing. The
+    /// feeder must be cloneable (e.g. a
+    /// [`SliceJsonFeeder`](crate::feeder::SliceJsonFeeder)).
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"[1, "a"]"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    ///
+    /// let checkpoint = parser.checkpoint();
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    ///
+    /// parser.restore(checkpoint);
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    /// assert_eq!(parser.current_int::<i64>().unwrap(), 1);
+    /// ```
+    pub fn checkpoint(&self) -> Checkpoint<T, S> {
+        Checkpoint {
+            parser: self.clone(),
+        }
+    }
+
+    /// Rewind the parser and its feeder to the given snapshot (see
+    /// [`Self::checkpoint()`])
+    pub fn restore(&mut self, checkpoint: Checkpoint<T, S>) {
+        *self = checkpoint.parser;
+    }
+}
+
+/// An iterator over the events of a [`JsonParser`]. Created by
+/// [`JsonParser::events()`].
+pub struct Events<'a, T, S = HeapStack> {
+    parser: &'a mut JsonParser<T, S>,
+    finished: bool,
+}
+
+impl<T, S> Events<'_, T, S> {
+    /// Get a reference to the underlying parser (e.g. to access the values
+    /// of the current event)
+    pub fn parser(&self) -> &JsonParser<T, S> {
+        self.parser
+    }
+}
+
+impl<T, S> Iterator for Events<'_, T, S>
+where
+    T: JsonFeeder,
+    S: ModeStack,
+{
+    type Item = Result<JsonEvent, ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.parser.next_event() {
+            Ok(Some(e)) => Some(Ok(e)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl<T, S> FusedIterator for Events<'_, T, S>
+where
+    T: JsonFeeder,
+    S: ModeStack,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use crate::feeder::SliceJsonFeeder;
+    use crate::options::JsonParserOptionsBuilder;
+    use crate::{JsonEvent, JsonParser};
+
+    /// Test that the buffer is only shrunk if a threshold has been configured
+    #[test]
+    fn buffer_shrink_threshold() {
+        let mut json = Vec::new();
+        json.extend_from_slice(b"[\"");
+        json.resize(json.len() + 10000, b'a');
+        json.extend_from_slice(b"\", \"b\", 1]");
+
+        let mut parser = JsonParser::new(SliceJsonFeeder::new(&json));
+        while let Some(e) = parser.next_event().unwrap() {
+            if e == JsonEvent::ValueInt {
+                assert!(parser.current_buffer.capacity() >= 10000);
+            }
+        }
+
+        let mut parser = JsonParser::new_with_options(
+            SliceJsonFeeder::new(&json),
+            JsonParserOptionsBuilder::default()
+                .with_buffer_shrink_threshold(64)
+                .build(),
+        );
+        while let Some(e) = parser.next_event().unwrap() {
+            match e {
+                JsonEvent::ValueString if parser.current_buffer.len() == 10000 => {
+                    assert!(parser.current_buffer.capacity() >= 10000);
+                }
+                JsonEvent::ValueString | JsonEvent::ValueInt => {
+                    assert!(parser.current_buffer.capacity() <= 64);
+                }
+                _ => {}
+            }
+        }
+    }
 }
@@ -0,0 +1,842 @@
+use std::borrow::BorrowMut;
+use std::marker::PhantomData;
+
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use thiserror::Error;
+
+use crate::feeder::{FillError, JsonFeeder, SliceJsonFeeder};
+use crate::parser::{
+    InvalidFloatValueError, InvalidIntValueError, InvalidStringValueError, ParserError,
+};
+use crate::{JsonEvent, JsonParser};
+
+/// An error that can happen when deserializing JSON with an
+/// [`ActsonDeserializer`]
+#[derive(Error, Debug)]
+pub enum DeserializeError {
+    #[error("{0}")]
+    Parse(#[from] ParserError),
+
+    #[error("{0}")]
+    InvalidStringValue(#[from] InvalidStringValueError),
+
+    #[error("{0}")]
+    InvalidIntValue(#[from] InvalidIntValueError),
+
+    #[error("{0}")]
+    InvalidFloatValue(#[from] InvalidFloatValueError),
+
+    #[error("{0}")]
+    Fill(#[from] FillError),
+
+    /// The feeder needs more input, but the deserializer is not able to
+    /// provide it. Only use feeders that have all input data available up
+    /// front.
+    #[error("the feeder needs more input")]
+    NeedMoreInput,
+
+    /// There is more data after the end of the deserialized value
+    #[error("trailing data after the end of the value")]
+    TrailingData,
+
+    /// The parser returns [`JsonEvent::Entry`]s, which the deserializer
+    /// does not support. Do not enable
+    /// [`with_entry_events()`](crate::options::JsonParserOptionsBuilder::with_entry_events())
+    /// for parsers that drive a deserializer.
+    #[error("entry events are not supported")]
+    EntryEvents,
+
+    #[error("{0}")]
+    Custom(String),
+}
+
+impl de::Error for DeserializeError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        DeserializeError::Custom(msg.to_string())
+    }
+}
+
+/// A Serde [`Deserializer`](serde::Deserializer) that is driven by a
+/// [`JsonParser`]. It allows you to deserialize JSON directly into your own
+/// data types without creating an intermediate value.
+///
+/// The parser's feeder must have all input data available up front (such
+/// as [`SliceJsonFeeder`]).
+///
+/// *Heads up:* You need to enable the `serde` feature for this.
+///
+/// ```
+/// use serde::Deserialize;
+/// use actson::serde::ActsonDeserializer;
+///
+/// #[derive(Deserialize)]
+/// struct Person {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// let json = r#"{"name": "Elvis", "age": 42}"#.as_bytes();
+///
+/// let mut deserializer = ActsonDeserializer::from_slice(json);
+/// let person = Person::deserialize(&mut deserializer).unwrap();
+/// deserializer.end().unwrap();
+///
+/// assert_eq!(person.name, "Elvis");
+/// assert_eq!(person.age, 42);
+/// ```
+pub struct ActsonDeserializer<T, P = JsonParser<T>> {
+    parser: P,
+    feeder: PhantomData<T>,
+}
+
+impl<'a> ActsonDeserializer<SliceJsonFeeder<'a>> {
+    /// Create a new deserializer that reads from the given byte slice
+    pub fn from_slice(v: &'a [u8]) -> Self {
+        Self::new(JsonParser::new(SliceJsonFeeder::new(v)))
+    }
+}
+
+impl<T> ActsonDeserializer<T>
+where
+    T: JsonFeeder,
+{
+    /// Create a new deserializer that is driven by the given parser.
+    /// Whitespace, comments, and document events are skipped, and strings
+    /// that are returned in chunks are put together. The parser must not
+    /// return [`JsonEvent::Entry`]s.
+    pub fn new(parser: JsonParser<T>) -> Self {
+        ActsonDeserializer {
+            parser,
+            feeder: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> ActsonDeserializer<T, &'a mut JsonParser<T>>
+where
+    T: JsonFeeder,
+{
+    /// Create a new deserializer that is driven by the given borrowed
+    /// parser. Use this to deserialize a value from the middle of a JSON
+    /// text. The parser continues after the end of the value.
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::serde::ActsonDeserializer;
+    /// use actson::{JsonEvent, JsonParser};
+    ///
+    /// let json = r#"[{"x": 1}, {"x": 2}]"#.as_bytes();
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Point {
+    ///     x: i32,
+    /// }
+    ///
+    /// let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    /// parser.expect(JsonEvent::StartArray).unwrap();
+    /// let p = Point::deserialize(&mut ActsonDeserializer::from_parser_mut(&mut parser)).unwrap();
+    /// assert_eq!(p.x, 1);
+    /// assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+    /// ```
+    pub fn from_parser_mut(parser: &'a mut JsonParser<T>) -> Self {
+        ActsonDeserializer {
+            parser,
+            feeder: PhantomData,
+        }
+    }
+}
+
+impl<T, P> ActsonDeserializer<T, P>
+where
+    T: JsonFeeder,
+    P: BorrowMut<JsonParser<T>>,
+{
+    /// Make sure the whole input has been consumed. Call this method after
+    /// deserializing a value.
+    pub fn end(&mut self) -> Result<(), DeserializeError> {
+        loop {
+            match self.parser.borrow_mut().next_event()? {
+                None => return Ok(()),
+                Some(JsonEvent::NeedMoreInput) => return Err(DeserializeError::NeedMoreInput),
+                Some(e) if is_skipped(e) => {}
+                Some(_) => return Err(DeserializeError::TrailingData),
+            }
+        }
+    }
+
+    /// Consume this deserializer and return the underlying parser
+    pub fn into_inner(self) -> P {
+        self.parser
+    }
+
+    /// Get the next event that is not skipped and fail if there is none
+    fn next_event(&mut self) -> Result<JsonEvent, DeserializeError> {
+        loop {
+            match self.parser.borrow_mut().next_event()? {
+                Some(JsonEvent::NeedMoreInput) => return Err(DeserializeError::NeedMoreInput),
+                Some(JsonEvent::Entry) => return Err(DeserializeError::EntryEvents),
+                Some(e) if is_skipped(e) => {}
+                Some(e) => return Ok(e),
+                None => return Err(ParserError::NoMoreInput.into()),
+            }
+        }
+    }
+
+    /// Peek the next event that is not skipped and fail if there is none
+    fn peek_event(&mut self) -> Result<JsonEvent, DeserializeError> {
+        loop {
+            let parser = self.parser.borrow_mut();
+            match parser.peek_event()? {
+                Some(JsonEvent::NeedMoreInput) => return Err(DeserializeError::NeedMoreInput),
+                Some(JsonEvent::Entry) => return Err(DeserializeError::EntryEvents),
+                Some(e) if is_skipped(e) => {
+                    parser.next_event()?;
+                }
+                Some(e) => return Ok(e),
+                None => return Err(ParserError::NoMoreInput.into()),
+            }
+        }
+    }
+
+    /// Put together a string that is returned in chunks. Call this method
+    /// after receiving the first [`JsonEvent::PartialValueString`].
+    fn join_partial_string(&mut self) -> Result<String, DeserializeError> {
+        let mut s = self.parser.borrow_mut().current_str()?.to_string();
+        loop {
+            let event = self.next_event()?;
+            s.push_str(self.parser.borrow_mut().current_str()?);
+            match event {
+                JsonEvent::PartialValueString => {}
+                JsonEvent::ValueString => return Ok(s),
+                _ => return Err(self.parser.borrow_mut().syntax_error(None).into()),
+            }
+        }
+    }
+
+    /// Skip the value that starts with the given event (including all nested
+    /// values if it is an object or an array)
+    fn skip_value(&mut self, event: JsonEvent) -> Result<(), DeserializeError> {
+        let mut depth = match event {
+            JsonEvent::StartObject | JsonEvent::StartArray => 1,
+            JsonEvent::PartialValueString => {
+                self.join_partial_string()?;
+                0
+            }
+            _ => 0,
+        };
+        while depth > 0 {
+            match self.next_event()? {
+                JsonEvent::StartObject | JsonEvent::StartArray => depth += 1,
+                JsonEvent::EndObject | JsonEvent::EndArray => depth -= 1,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Check if the given event does not contribute to the deserialized value
+/// and can be skipped
+fn is_skipped(event: JsonEvent) -> bool {
+    matches!(
+        event,
+        JsonEvent::Whitespace
+            | JsonEvent::Comment
+            | JsonEvent::StartDocument
+            | JsonEvent::EndDocument
+    )
+}
+
+/// Deserialize a value of type `D` from a byte slice
+///
+/// ```
+/// use std::collections::HashMap;
+/// use actson::serde::from_slice;
+///
+/// let json = r#"{"Elvis": [1935, 1977]}"#.as_bytes();
+/// let value: HashMap<String, Vec<u32>> = from_slice(json).unwrap();
+///
+/// assert_eq!(value["Elvis"], vec![1935, 1977]);
+/// ```
+pub fn from_slice<D>(v: &[u8]) -> Result<D, DeserializeError>
+where
+    D: DeserializeOwned,
+{
+    let mut deserializer = ActsonDeserializer::from_slice(v);
+    let r = D::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(r)
+}
+
+impl<'de, T, P> de::Deserializer<'de> for &mut ActsonDeserializer<T, P>
+where
+    T: JsonFeeder,
+    P: BorrowMut<JsonParser<T>>,
+{
+    type Error = DeserializeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.next_event()? {
+            JsonEvent::StartObject => visitor.visit_map(Entries { de: self }),
+            JsonEvent::StartArray => {
+                let mut elements = Elements {
+                    de: self,
+                    len: 0,
+                    done: false,
+                };
+                let r = visitor.visit_seq(&mut elements)?;
+                elements.end()?;
+                Ok(r)
+            }
+            JsonEvent::FieldName | JsonEvent::ValueString => {
+                visitor.visit_str(self.parser.borrow_mut().current_str()?)
+            }
+            JsonEvent::PartialValueString => visitor.visit_string(self.join_partial_string()?),
+            JsonEvent::ValueInt => {
+                if let Ok(i) = self.parser.borrow_mut().current_int::<i64>() {
+                    visitor.visit_i64(i)
+                } else if let Ok(u) = self.parser.borrow_mut().current_int::<u64>() {
+                    visitor.visit_u64(u)
+                } else {
+                    visitor.visit_f64(self.parser.borrow_mut().current_float()?)
+                }
+            }
+            JsonEvent::ValueFloat => visitor.visit_f64(self.parser.borrow_mut().current_float()?),
+            JsonEvent::ValueTrue => visitor.visit_bool(true),
+            JsonEvent::ValueFalse => visitor.visit_bool(false),
+            JsonEvent::ValueNull => visitor.visit_unit(),
+            JsonEvent::NeedMoreInput
+            | JsonEvent::Whitespace
+            | JsonEvent::Comment
+            | JsonEvent::StartDocument
+            | JsonEvent::EndDocument
+            | JsonEvent::Entry
+            | JsonEvent::EndObject
+            | JsonEvent::EndArray => Err(self.parser.borrow_mut().syntax_error(None).into()),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.peek_event()? == JsonEvent::ValueNull {
+            self.next_event()?;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.next_event()? {
+            JsonEvent::ValueString => {
+                visitor.visit_enum(self.parser.borrow_mut().current_str()?.into_deserializer())
+            }
+            JsonEvent::PartialValueString => {
+                visitor.visit_enum(self.join_partial_string()?.into_deserializer())
+            }
+            JsonEvent::StartObject => {
+                let r = visitor.visit_enum(Variant { de: self })?;
+                match self.next_event()? {
+                    JsonEvent::EndObject => Ok(r),
+                    _ => Err(self.parser.borrow_mut().syntax_error(None).into()),
+                }
+            }
+            _ => Err(de::Error::custom("expected an enum")),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let event = self.next_event()?;
+        self.skip_value(event)?;
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier
+    }
+}
+
+/// Provides access to the entries of a JSON object
+struct Entries<'a, T, P> {
+    de: &'a mut ActsonDeserializer<T, P>,
+}
+
+impl<'de, T, P> MapAccess<'de> for Entries<'_, T, P>
+where
+    T: JsonFeeder,
+    P: BorrowMut<JsonParser<T>>,
+{
+    type Error = DeserializeError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.de.next_event()? {
+            JsonEvent::EndObject => Ok(None),
+            JsonEvent::FieldName => seed
+                .deserialize(KeyDeserializer {
+                    key: self.de.parser.borrow_mut().current_str()?,
+                })
+                .map(Some),
+            _ => Err(self.de.parser.borrow_mut().syntax_error(None).into()),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+/// Provides access to the elements of a JSON array
+struct Elements<'a, T, P> {
+    de: &'a mut ActsonDeserializer<T, P>,
+
+    /// The number of elements that have been deserialized so far
+    len: usize,
+
+    /// `true` if the end of the array has been reached
+    done: bool,
+}
+
+impl<T, P> Elements<'_, T, P>
+where
+    T: JsonFeeder,
+    P: BorrowMut<JsonParser<T>>,
+{
+    /// Consume the end of the array after the visitor has returned. Fixed
+    /// length sequences (such as tuples) do not ask for more elements than
+    /// they expect, so the end might not have been reached yet. Fail if the
+    /// array has more elements.
+    fn end(&mut self) -> Result<(), DeserializeError> {
+        if self.done {
+            return Ok(());
+        }
+        let mut remaining = 0;
+        loop {
+            match self.de.next_event()? {
+                JsonEvent::EndArray => break,
+                e => {
+                    self.de.skip_value(e)?;
+                    remaining += 1;
+                }
+            }
+        }
+        self.done = true;
+        if remaining > 0 {
+            return Err(de::Error::invalid_length(
+                self.len + remaining,
+                &"fewer elements in array",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<'de, T, P> SeqAccess<'de> for Elements<'_, T, P>
+where
+    T: JsonFeeder,
+    P: BorrowMut<JsonParser<T>>,
+{
+    type Error = DeserializeError;
+
+    fn next_element_seed<E>(&mut self, seed: E) -> Result<Option<E::Value>, Self::Error>
+    where
+        E: DeserializeSeed<'de>,
+    {
+        if self.de.peek_event()? == JsonEvent::EndArray {
+            self.de.next_event()?;
+            self.done = true;
+            return Ok(None);
+        }
+        self.len += 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+/// Provides access to an enum variant that is represented as a JSON object
+/// with a single entry
+struct Variant<'a, T, P> {
+    de: &'a mut ActsonDeserializer<T, P>,
+}
+
+impl<'de, T, P> EnumAccess<'de> for Variant<'_, T, P>
+where
+    T: JsonFeeder,
+    P: BorrowMut<JsonParser<T>>,
+{
+    type Error = DeserializeError;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.de.next_event()? {
+            JsonEvent::FieldName => {
+                let v = seed.deserialize(KeyDeserializer {
+                    key: self.de.parser.borrow_mut().current_str()?,
+                })?;
+                Ok((v, self))
+            }
+            _ => Err(self.de.parser.borrow_mut().syntax_error(None).into()),
+        }
+    }
+}
+
+impl<'de, T, P> VariantAccess<'de> for Variant<'_, T, P>
+where
+    T: JsonFeeder,
+    P: BorrowMut<JsonParser<T>>,
+{
+    type Error = DeserializeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        de::Deserialize::deserialize(&mut *self.de)
+    }
+
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, Self::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(&mut *self.de, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(&mut *self.de, visitor)
+    }
+}
+
+/// Deserializes field names. Since JSON object keys are always strings,
+/// integer keys (e.g. in a `HashMap<u32, _>`) are parsed from the string.
+struct KeyDeserializer<'a> {
+    key: &'a str,
+}
+
+macro_rules! deserialize_int_key {
+    ($($method:ident => $visit:ident),*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                match self.key.parse() {
+                    Ok(i) => visitor.$visit(i),
+                    Err(_) => visitor.visit_str(self.key),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer<'_> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.key)
+    }
+
+    deserialize_int_key! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i128 u128 f32 f64 char str string bytes byte_buf option unit
+        unit_struct seq tuple tuple_struct map struct enum identifier
+        ignored_any
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, HashMap};
+
+    use serde::Deserialize;
+
+    use crate::feeder::SliceJsonFeeder;
+    use crate::options::JsonParserOptionsBuilder;
+    use crate::parser::ParserError;
+    use crate::serde::{from_slice, ActsonDeserializer, DeserializeError};
+    use crate::JsonParser;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Album {
+        title: String,
+        year: u16,
+        tracks: Option<Vec<String>>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Artist {
+        name: String,
+        born: i32,
+        height: f64,
+        alive: bool,
+        albums: Vec<Album>,
+        genre: Genre,
+        label: Option<String>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    enum Genre {
+        Rock,
+        Pop(u8),
+        Country { subgenre: String },
+    }
+
+    /// Test that a struct with nested values can be deserialized
+    #[test]
+    fn struct_with_nested_values() {
+        let json = r#"{
+            "name": "Elvis",
+            "born": 1935,
+            "height": 1.82,
+            "unknown": {"a": [1, 2, {"b": null}]},
+            "alive": false,
+            "albums": [
+                {"title": "Elvis Presley", "year": 1956, "tracks": ["Blue Suede Shoes"]},
+                {"title": "Elvis", "year": 1956, "tracks": null}
+            ],
+            "genre": "Rock",
+            "label": null
+        }"#;
+        let artist: Artist = from_slice(json.as_bytes()).unwrap();
+        assert_eq!(
+            artist,
+            Artist {
+                name: "Elvis".to_string(),
+                born: 1935,
+                height: 1.82,
+                alive: false,
+                albums: vec![
+                    Album {
+                        title: "Elvis Presley".to_string(),
+                        year: 1956,
+                        tracks: Some(vec!["Blue Suede Shoes".to_string()]),
+                    },
+                    Album {
+                        title: "Elvis".to_string(),
+                        year: 1956,
+                        tracks: None,
+                    },
+                ],
+                genre: Genre::Rock,
+                label: None,
+            }
+        );
+    }
+
+    /// Test that enum variants can be deserialized
+    #[test]
+    fn enums() {
+        let genres: Vec<Genre> = from_slice(
+            r#"["Rock", {"Pop": 5}, {"Country": {"subgenre": "Rockabilly"}}]"#.as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(
+            genres,
+            vec![
+                Genre::Rock,
+                Genre::Pop(5),
+                Genre::Country {
+                    subgenre: "Rockabilly".to_string()
+                }
+            ]
+        );
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Range(i32, i32);
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Shape {
+        p: (i32, i32),
+        q: i32,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    enum Direction {
+        D(u8, u8),
+    }
+
+    /// Test that fixed-length sequences can be deserialized
+    #[test]
+    fn fixed_length_sequences() {
+        assert_eq!(from_slice::<(i32, i32)>(b"[1, 2]").unwrap(), (1, 2));
+        assert_eq!(from_slice::<[u8; 2]>(b"[1, 2]").unwrap(), [1, 2]);
+        assert_eq!(from_slice::<Range>(b"[1, 2]").unwrap(), Range(1, 2));
+        assert_eq!(
+            from_slice::<Shape>(br#"{"p": [1, 2], "q": 3}"#).unwrap(),
+            Shape { p: (1, 2), q: 3 }
+        );
+        assert_eq!(
+            from_slice::<Direction>(br#"{"D": [1, 2]}"#).unwrap(),
+            Direction::D(1, 2)
+        );
+        assert_eq!(
+            from_slice::<Vec<(u8, [u8; 1])>>(b"[[1, [2]], [3, [4]]]").unwrap(),
+            vec![(1, [2]), (3, [4])]
+        );
+
+        assert!(matches!(
+            from_slice::<(i32, i32)>(b"[1, 2, [3, 4]]"),
+            Err(DeserializeError::Custom(_))
+        ));
+        assert!(matches!(
+            from_slice::<[u8; 2]>(b"[1]"),
+            Err(DeserializeError::Custom(_))
+        ));
+        assert!(matches!(
+            from_slice::<Direction>(br#"{"D": [1, 2, 3]}"#),
+            Err(DeserializeError::Custom(_))
+        ));
+    }
+
+    /// Test that parser options that produce additional events are supported
+    #[test]
+    fn parser_options() {
+        let json = br#"
+            // the artist
+            {"name": "Elvis Presley", "genre": "Rock", "albums": [
+                {"title": "Elvis", "year": 1956, "tracks": null}
+            ], "born": 1935, "height": 1.82, "alive": false, "label": null}
+        "#;
+        let options = JsonParserOptionsBuilder::default()
+            .with_max_string_chunk(2)
+            .with_emit_whitespace(true)
+            .with_allow_comments(true)
+            .with_emit_comments(true)
+            .with_document_events(true)
+            .build();
+        let parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+        let mut deserializer = ActsonDeserializer::new(parser);
+        let artist = Artist::deserialize(&mut deserializer).unwrap();
+        deserializer.end().unwrap();
+        assert_eq!(artist.name, "Elvis Presley");
+        assert_eq!(artist.genre, Genre::Rock);
+        assert_eq!(artist.albums[0].title, "Elvis");
+
+        let options = JsonParserOptionsBuilder::default()
+            .with_allow_comments(true)
+            .with_entry_events(true)
+            .build();
+        let parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+        assert!(matches!(
+            Artist::deserialize(&mut ActsonDeserializer::new(parser)),
+            Err(DeserializeError::EntryEvents)
+        ));
+    }
+
+    /// Test that maps with string and integer keys can be deserialized
+    #[test]
+    fn maps() {
+        let m: HashMap<String, u64> = from_slice(r#"{"a": 1, "b": 2}"#.as_bytes()).unwrap();
+        assert_eq!(m.len(), 2);
+        assert_eq!(m["a"], 1);
+        assert_eq!(m["b"], 2);
+
+        let m: BTreeMap<u32, String> = from_slice(r#"{"1": "a", "2": "b"}"#.as_bytes()).unwrap();
+        assert_eq!(
+            m.into_iter().collect::<Vec<_>>(),
+            vec![(1, "a".to_string()), (2, "b".to_string())]
+        );
+    }
+
+    /// Test that invalid input and type mismatches are reported
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            from_slice::<Vec<u8>>(r#"[1, 2"#.as_bytes()),
+            Err(DeserializeError::Parse(ParserError::UnexpectedEof {
+                open_containers: 1
+            }))
+        ));
+        assert!(matches!(
+            from_slice::<Vec<u8>>(r#"[1, 256]"#.as_bytes()),
+            Err(DeserializeError::Custom(_))
+        ));
+        assert!(matches!(
+            from_slice::<Album>(r#"{"title": "Elvis"}"#.as_bytes()),
+            Err(DeserializeError::Custom(_))
+        ));
+        assert!(matches!(
+            from_slice::<u8>(r#"1 2"#.as_bytes()),
+            Err(DeserializeError::Parse(ParserError::TrailingData(_)))
+        ));
+    }
+}
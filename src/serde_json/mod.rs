@@ -1,10 +1,17 @@
+use std::io::{BufReader, Read};
+use std::iter::FusedIterator;
+
 use serde_json::{Map, Number, Value};
 use thiserror::Error;
 
-use crate::feeder::{JsonFeeder, SliceJsonFeeder};
+use crate::dom::{build_value, ValueAssembler, ValueBuilder};
+use crate::feeder::{BufReaderJsonFeeder, FillError, SliceJsonFeeder};
+use crate::options::JsonParserOptionsBuilder;
 use crate::parser::{
     InvalidFloatValueError, InvalidIntValueError, InvalidStringValueError, ParserError,
 };
+#[cfg(feature = "tokio")]
+use crate::tokio::AsyncBufReaderJsonFeeder;
 use crate::{JsonEvent, JsonParser};
 
 /// An error that can happen when parsing JSON to a Serde [`Value`]
@@ -24,25 +31,137 @@ pub enum IntoSerdeValueError {
 
     #[error("not a JSON number: {0}")]
     IllegalJsonNumber(f64),
+
+    #[error("{0}")]
+    Fill(#[from] FillError),
 }
 
-fn to_value<T>(event: &JsonEvent, parser: &JsonParser<T>) -> Result<Value, IntoSerdeValueError>
-where
-    T: JsonFeeder,
-{
-    Ok(match event {
-        JsonEvent::ValueString => Value::String(parser.current_str()?.to_string()),
-        JsonEvent::ValueInt => Value::Number(Number::from(parser.current_int::<i64>()?)),
-        JsonEvent::ValueFloat => {
-            let f = parser.current_float()?;
-            let n = Number::from_f64(f).ok_or(IntoSerdeValueError::IllegalJsonNumber(f))?;
-            Value::Number(n)
+/// Specifies how numbers are converted to Serde JSON [`Value`]s by
+/// [`parse_value_with()`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NumberPolicy {
+    /// Integers are converted to [`Number`]s if they fit into an `i64` or
+    /// a `u64`. All other numbers are converted to `f64`, which may lose
+    /// precision.
+    #[default]
+    F64,
+
+    /// All numbers are converted to [`Value::String`]s containing the
+    /// number exactly as it appears in the JSON text. No precision is lost.
+    Raw,
+
+    /// Integers are converted to [`Number`]s if they fit into an `i64` or a
+    /// `u64`. Larger integers are converted to [`Value::String`]s containing
+    /// all their digits, so they can be parsed to a big integer later.
+    /// Floating point numbers are converted to `f64`, unless they are out of
+    /// its range (e.g. `1e400`). Such numbers are converted to
+    /// [`Value::String`]s as well.
+    BigInt,
+}
+
+/// A [`ValueBuilder`] that creates Serde JSON [`Value`]s. Use it with a
+/// [`ValueAssembler`] or with
+/// [`ArrayElements::build_next()`](crate::extract::ArrayElements::build_next()).
+/// By default, integers must fit into an `i64` (just like in
+/// [`from_slice()`]).
+#[derive(Default)]
+pub struct SerdeValueBuilder {
+    /// How numbers should be converted or `None` if integers must fit into
+    /// an `i64`
+    number_policy: Option<NumberPolicy>,
+}
+
+impl SerdeValueBuilder {
+    /// Create a builder that converts numbers according to the given policy
+    pub fn with_number_policy(number_policy: NumberPolicy) -> Self {
+        SerdeValueBuilder {
+            number_policy: Some(number_policy),
         }
-        JsonEvent::ValueTrue => Value::Bool(true),
-        JsonEvent::ValueFalse => Value::Bool(false),
-        JsonEvent::ValueNull => Value::Null,
-        _ => unreachable!("this function will only be called for valid events"),
-    })
+    }
+
+    /// Convert an integer that fits into an `i64` or a `u64`
+    fn exact_int(text: &str) -> Option<Value> {
+        if let Ok(i) = text.parse::<i64>() {
+            Some(Value::Number(Number::from(i)))
+        } else {
+            text.parse::<u64>()
+                .ok()
+                .map(|u| Value::Number(Number::from(u)))
+        }
+    }
+}
+
+impl ValueBuilder for SerdeValueBuilder {
+    type Value = Value;
+    type Error = IntoSerdeValueError;
+
+    fn object(&mut self) -> Value {
+        Value::Object(Map::new())
+    }
+
+    fn array(&mut self) -> Value {
+        Value::Array(vec![])
+    }
+
+    fn insert(&mut self, obj: &mut Value, key: String, val: Value) {
+        if let Some(m) = obj.as_object_mut() {
+            m.insert(key, val);
+        }
+    }
+
+    fn push(&mut self, arr: &mut Value, val: Value) {
+        if let Some(a) = arr.as_array_mut() {
+            a.push(val);
+        }
+    }
+
+    fn string(&mut self, value: &str) -> Value {
+        Value::String(value.to_string())
+    }
+
+    fn int(&mut self, value: i64) -> Value {
+        Value::Number(Number::from(value))
+    }
+
+    fn float(&mut self, value: f64) -> Result<Value, IntoSerdeValueError> {
+        let n = Number::from_f64(value).ok_or(IntoSerdeValueError::IllegalJsonNumber(value))?;
+        Ok(Value::Number(n))
+    }
+
+    fn bool(&mut self, value: bool) -> Value {
+        Value::Bool(value)
+    }
+
+    fn null(&mut self) -> Value {
+        Value::Null
+    }
+
+    fn number(
+        &mut self,
+        text: &str,
+        event: JsonEvent,
+    ) -> Option<Result<Value, IntoSerdeValueError>> {
+        let policy = self.number_policy?;
+        if policy == NumberPolicy::Raw {
+            return Some(Ok(Value::String(text.to_string())));
+        }
+        if event == JsonEvent::ValueInt {
+            if let Some(v) = Self::exact_int(text) {
+                return Some(Ok(v));
+            }
+            if policy == NumberPolicy::BigInt {
+                return Some(Ok(Value::String(text.to_string())));
+            }
+        }
+        let f = match text.parse::<f64>() {
+            Ok(f) => f,
+            Err(e) => return Some(Err(InvalidFloatValueError::from(e).into())),
+        };
+        if policy == NumberPolicy::BigInt && !f.is_finite() {
+            return Some(Ok(Value::String(text.to_string())));
+        }
+        Some(self.float(f))
+    }
 }
 
 /// Parse a byte slice into a Serde JSON [Value]
@@ -62,73 +181,223 @@ pub fn from_slice(v: &[u8]) -> Result<Value, IntoSerdeValueError> {
     let feeder = SliceJsonFeeder::new(v);
     let mut parser = JsonParser::new(feeder);
 
-    let mut stack = vec![];
-    let mut result = None;
-    let mut current_key = None;
+    build_value(&mut parser, SerdeValueBuilder::default())
+}
 
-    while let Some(event) = parser.next_event()? {
-        match event {
-            JsonEvent::NeedMoreInput => {}
-
-            JsonEvent::StartObject | JsonEvent::StartArray => {
-                let v = if event == JsonEvent::StartObject {
-                    Value::Object(Map::new())
-                } else {
-                    Value::Array(vec![])
-                };
-                stack.push((current_key, v));
-                current_key = None;
-            }
+/// Parse a byte slice into a Serde JSON [Value] and convert numbers
+/// according to the given [`NumberPolicy`]. In contrast to [`from_slice()`],
+/// this function does not reject integers that do not fit into an `i64`.
+/// Numbers that are out of the range of an `f64` (e.g. `1e400`) are only
+/// accepted with [`NumberPolicy::Raw`] and [`NumberPolicy::BigInt`].
+///
+/// ```
+/// use serde_json::json;
+/// use actson::serde_json::{parse_value_with, NumberPolicy};
+///
+/// let json = br#"{"id": 123456789012345678901234567890, "price": 0.10}"#;
+///
+/// let value = parse_value_with(json, NumberPolicy::Raw).unwrap();
+/// assert_eq!(value, json!({"id": "123456789012345678901234567890", "price": "0.10"}));
+///
+/// let value = parse_value_with(json, NumberPolicy::BigInt).unwrap();
+/// assert_eq!(value, json!({"id": "123456789012345678901234567890", "price": 0.1}));
+/// ```
+pub fn parse_value_with(
+    v: &[u8],
+    number_policy: NumberPolicy,
+) -> Result<Value, IntoSerdeValueError> {
+    let feeder = SliceJsonFeeder::new(v);
+    let mut parser = JsonParser::new(feeder);
 
-            JsonEvent::EndObject | JsonEvent::EndArray => {
-                let v = stack.pop().unwrap();
-                if let Some((_, top)) = stack.last_mut() {
-                    if let Some(m) = top.as_object_mut() {
-                        m.insert(v.0.unwrap(), v.1);
-                    } else if let Some(a) = top.as_array_mut() {
-                        a.push(v.1);
-                    }
-                } else {
-                    result = Some(v.1);
-                }
+    build_value(
+        &mut parser,
+        SerdeValueBuilder::with_number_policy(number_policy),
+    )
+}
+
+/// Parse the first top-level JSON value from a byte slice into a Serde JSON
+/// [Value] and return it together with the number of bytes consumed. Any
+/// data after the value is ignored, so you can continue reading from
+/// `&v[consumed..]`. The consumed bytes include leading whitespace but not
+/// whitespace after the value.
+///
+/// ```
+/// use serde_json::json;
+/// use actson::serde_json::from_slice_partial;
+///
+/// let json = br#"{"name": "Elvis"} [1, 2]"#;
+/// let (value, consumed) = from_slice_partial(json).unwrap();
+/// assert_eq!(value, json!({"name": "Elvis"}));
+/// assert_eq!(&json[consumed..], b" [1, 2]");
+/// ```
+pub fn from_slice_partial(v: &[u8]) -> Result<(Value, usize), IntoSerdeValueError> {
+    let feeder = SliceJsonFeeder::new(v);
+    let mut parser = JsonParser::new_with_options(
+        feeder,
+        JsonParserOptionsBuilder::default()
+            .with_stop_after_first_value(true)
+            .build(),
+    );
+
+    let value = build_value(&mut parser, SerdeValueBuilder::default())?;
+    Ok((value, parser.parsed_bytes()))
+}
+
+/// Parse a byte slice containing multiple top-level JSON values (e.g.
+/// newline-delimited JSON) and return an iterator over Serde JSON [Value]s.
+/// The parser will be run in streaming mode (see
+/// [`JsonParserOptionsBuilder::with_streaming()`](crate::options::JsonParserOptionsBuilder::with_streaming())).
+/// The iterator ends after the first error.
+///
+/// ```
+/// use serde_json::json;
+/// use actson::serde_json::iter_from_slice;
+///
+/// let json = r#"{"name": "Elvis"}
+/// {"name": "Max"}
+/// "#.as_bytes();
+///
+/// let values = iter_from_slice(json).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(values, vec![
+///     json!({"name": "Elvis"}),
+///     json!({"name": "Max"}),
+/// ]);
+/// ```
+pub fn iter_from_slice(v: &[u8]) -> Values<'_> {
+    let feeder = SliceJsonFeeder::new(v);
+    let parser = JsonParser::new_with_options(
+        feeder,
+        JsonParserOptionsBuilder::default()
+            .with_streaming(true)
+            .build(),
+    );
+    Values {
+        parser,
+        finished: false,
+    }
+}
+
+/// An iterator over multiple top-level JSON values. Created by
+/// [`iter_from_slice()`].
+pub struct Values<'a> {
+    parser: JsonParser<SliceJsonFeeder<'a>>,
+    finished: bool,
+}
+
+impl Values<'_> {
+    /// Parse the next top-level value
+    fn next_value(&mut self) -> Result<Option<Value>, IntoSerdeValueError> {
+        let mut assembler = ValueAssembler::new(SerdeValueBuilder::default());
+        while let Some(event) = self.parser.next_event()? {
+            assembler.on_event(event, &self.parser)?;
+            if let Some(v) = assembler.take_complete() {
+                return Ok(Some(v));
             }
+        }
+        Ok(None)
+    }
+}
+
+impl Iterator for Values<'_> {
+    type Item = Result<Value, IntoSerdeValueError>;
 
-            JsonEvent::FieldName => current_key = Some(parser.current_str()?.to_string()),
-
-            JsonEvent::ValueString
-            | JsonEvent::ValueInt
-            | JsonEvent::ValueFloat
-            | JsonEvent::ValueTrue
-            | JsonEvent::ValueFalse
-            | JsonEvent::ValueNull => {
-                if let Some((_, top)) = stack.last_mut() {
-                    let v = to_value(&event, &parser)?;
-                    if let Some(m) = top.as_object_mut() {
-                        m.insert(current_key.unwrap(), v);
-                        current_key = None
-                    } else if let Some(a) = top.as_array_mut() {
-                        a.push(v);
-                    }
-                } else if result.is_none() {
-                    let v = to_value(&event, &parser)?;
-                    result = Some(v);
-                } else {
-                    return Err(IntoSerdeValueError::Parse(ParserError::SyntaxError));
-                }
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.next_value() {
+            Ok(Some(v)) => Some(Ok(v)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
             }
         }
     }
+}
 
-    result.ok_or(IntoSerdeValueError::Parse(ParserError::NoMoreInput))
+impl FusedIterator for Values<'_> {}
+
+/// Parse JSON from a reader into a Serde JSON [Value]. The reader will be
+/// wrapped into a [`BufReader`], so you don't have to do this yourself.
+///
+/// ```
+/// use serde_json::json;
+/// use actson::serde_json::from_reader;
+///
+/// let json = r#"{"name": "Elvis"}"#.as_bytes();
+/// let expected = json!({
+///     "name": "Elvis"
+/// });
+/// let actual = from_reader(json).unwrap();
+/// assert_eq!(expected, actual);
+/// ```
+pub fn from_reader<R>(reader: R) -> Result<Value, IntoSerdeValueError>
+where
+    R: Read,
+{
+    let feeder = BufReaderJsonFeeder::new(BufReader::new(reader));
+    let mut parser = JsonParser::new(feeder);
+
+    build_value(&mut parser, SerdeValueBuilder::default())
+}
+
+/// Asynchronously parse JSON from a reader into a Serde JSON [Value]. The
+/// reader will be wrapped into a Tokio
+/// [`BufReader`](tokio::io::BufReader), so you don't have to do this
+/// yourself.
+///
+/// *Heads up:* The `tokio` feature has to be enabled for this.
+///
+/// ```
+/// use serde_json::json;
+/// use actson::serde_json::from_async_reader;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let json = r#"{"name": "Elvis"}"#.as_bytes();
+///     let expected = json!({
+///         "name": "Elvis"
+///     });
+///     let actual = from_async_reader(json).await.unwrap();
+///     assert_eq!(expected, actual);
+/// }
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn from_async_reader<R>(reader: R) -> Result<Value, IntoSerdeValueError>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    let feeder = AsyncBufReaderJsonFeeder::new(tokio::io::BufReader::new(reader));
+    let mut parser = JsonParser::new(feeder);
+
+    let mut assembler = ValueAssembler::new(SerdeValueBuilder::default());
+    while let Some(event) = parser.next_event()? {
+        if event == JsonEvent::NeedMoreInput {
+            parser.feeder.fill_buf().await?;
+        } else {
+            assembler.on_event(event, &parser)?;
+        }
+    }
+
+    assembler.finish()
 }
 
 #[cfg(test)]
 mod test {
+    use crate::feeder::BufReaderJsonFeeder;
+    use crate::JsonParser;
     use crate::{
         parser::ParserError,
-        serde_json::{from_slice, IntoSerdeValueError},
+        serde_json::{
+            from_reader, from_slice, from_slice_partial, iter_from_slice, parse_value_with,
+            IntoSerdeValueError, NumberPolicy, SerdeValueBuilder,
+        },
     };
-    use serde_json::{from_slice as serde_from_slice, Value};
+    use serde_json::{from_slice as serde_from_slice, json, Value};
 
     /// Test that a top-level string value can be parsed
     #[test]
@@ -241,7 +510,9 @@ mod test {
         let json = r#"{"name":"#.as_bytes();
         assert!(matches!(
             from_slice(json),
-            Err(IntoSerdeValueError::Parse(ParserError::NoMoreInput))
+            Err(IntoSerdeValueError::Parse(ParserError::UnexpectedEof {
+                open_containers: 1
+            }))
         ));
     }
 
@@ -251,7 +522,191 @@ mod test {
         let json = r#"{"name"}"#.as_bytes();
         assert!(matches!(
             from_slice(json),
-            Err(IntoSerdeValueError::Parse(ParserError::SyntaxError))
+            Err(IntoSerdeValueError::Parse(ParserError::SyntaxError(_)))
+        ));
+    }
+
+    /// Test that JSON can be parsed from a reader
+    #[test]
+    fn reader() {
+        let json = r#"{
+            "name": "Elvis",
+            "address": {"street": "Graceland", "city": "Memphis"},
+            "albums": ["Elvis Presley", "Elvis", {"title": "His Hand in Mine"}]
+        }"#
+        .as_bytes();
+        assert_eq!(
+            serde_from_slice::<Value>(json).unwrap(),
+            from_reader(json).unwrap()
+        );
+
+        let json = r#"{"name":"#.as_bytes();
+        assert!(matches!(
+            from_reader(json),
+            Err(IntoSerdeValueError::Parse(ParserError::UnexpectedEof {
+                open_containers: 1
+            }))
+        ));
+    }
+
+    /// Test that JSON can be parsed from an asynchronous reader
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn async_reader() {
+        use crate::serde_json::from_async_reader;
+
+        let json = r#"{
+            "name": "Elvis",
+            "address": {"street": "Graceland", "city": "Memphis"},
+            "albums": ["Elvis Presley", "Elvis", {"title": "His Hand in Mine"}]
+        }"#
+        .as_bytes();
+        assert_eq!(
+            serde_from_slice::<Value>(json).unwrap(),
+            from_async_reader(json).await.unwrap()
+        );
+
+        let json = r#"{"name"}"#.as_bytes();
+        assert!(matches!(
+            from_async_reader(json).await,
+            Err(IntoSerdeValueError::Parse(ParserError::SyntaxError(_)))
         ));
     }
+
+    /// Test that multiple top-level values can be parsed
+    #[test]
+    fn multiple_values() {
+        let json = r#"{"name": "Elvis", "albums": [{"title": "Elvis"}]}
+            {"name": "Max"}
+            [1, 2]"a"5 true
+            null"#
+            .as_bytes();
+        let values = iter_from_slice(json)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            values,
+            vec![
+                json!({"name": "Elvis", "albums": [{"title": "Elvis"}]}),
+                json!({"name": "Max"}),
+                json!([1, 2]),
+                json!("a"),
+                json!(5),
+                json!(true),
+                json!(null),
+            ]
+        );
+
+        assert_eq!(iter_from_slice(b"").count(), 0);
+        assert_eq!(iter_from_slice(b" \n ").count(), 0);
+    }
+
+    /// Test that the iterator ends after an error
+    #[test]
+    fn multiple_values_error() {
+        let json = r#"{"name": "Elvis"}
+            {"name"}
+            {"name": "Max"}"#
+            .as_bytes();
+        let mut values = iter_from_slice(json);
+        assert_eq!(values.next().unwrap().unwrap(), json!({"name": "Elvis"}));
+        assert!(matches!(
+            values.next(),
+            Some(Err(IntoSerdeValueError::Parse(ParserError::SyntaxError(_))))
+        ));
+        assert!(values.next().is_none());
+    }
+
+    /// Test that values can be read one after the other from a slice
+    #[test]
+    fn partial() {
+        let json = b" {\"a\": [1, 2]}\n12 \"b\"true";
+        let mut rest = &json[..];
+        let mut values = Vec::new();
+        while !rest.trim_ascii().is_empty() {
+            let (value, consumed) = from_slice_partial(rest).unwrap();
+            values.push(value);
+            rest = &rest[consumed..];
+        }
+        assert_eq!(
+            values,
+            vec![json!({"a": [1, 2]}), json!(12), json!("b"), json!(true)]
+        );
+
+        let (value, consumed) = from_slice_partial(b"1.5,").unwrap();
+        assert_eq!(value, json!(1.5));
+        assert_eq!(consumed, 3);
+
+        assert!(matches!(
+            from_slice_partial(b"  "),
+            Err(IntoSerdeValueError::Parse(ParserError::EmptyInput))
+        ));
+        assert!(matches!(
+            from_slice_partial(b"[1, 2"),
+            Err(IntoSerdeValueError::Parse(_))
+        ));
+    }
+
+    /// Test that numbers are converted according to the number policy
+    #[test]
+    fn number_policy() {
+        let json = br#"[1, -2, 18446744073709551615, 18446744073709551616, 0.5, 1e400]"#;
+        let finite = br#"[1, -2, 18446744073709551615, 18446744073709551616, 0.5]"#;
+        assert!(from_slice(finite).is_err());
+
+        assert!(matches!(
+            parse_value_with(json, NumberPolicy::F64),
+            Err(IntoSerdeValueError::IllegalJsonNumber(_))
+        ));
+        assert_eq!(
+            parse_value_with(finite, NumberPolicy::F64).unwrap(),
+            json!([1, -2, u64::MAX, 18446744073709551616.0, 0.5])
+        );
+
+        assert_eq!(
+            parse_value_with(json, NumberPolicy::Raw).unwrap(),
+            json!([
+                "1",
+                "-2",
+                "18446744073709551615",
+                "18446744073709551616",
+                "0.5",
+                "1e400"
+            ])
+        );
+
+        assert_eq!(
+            parse_value_with(finite, NumberPolicy::BigInt).unwrap(),
+            json!([1, -2, u64::MAX, "18446744073709551616", 0.5])
+        );
+        assert_eq!(
+            parse_value_with(json, NumberPolicy::BigInt).unwrap(),
+            json!([1, -2, u64::MAX, "18446744073709551616", 0.5, "1e400"])
+        );
+        assert_eq!(
+            parse_value_with(b"[-1e400, 1e-400]", NumberPolicy::BigInt).unwrap(),
+            json!(["-1e400", 0.0])
+        );
+        assert!(matches!(
+            parse_value_with(b"[1e400]", NumberPolicy::F64),
+            Err(IntoSerdeValueError::IllegalJsonNumber(_))
+        ));
+    }
+
+    /// Test that array elements can be built from a reader that is filled
+    /// in small chunks
+    #[test]
+    fn array_elements() {
+        let json = br#"{"a": 1, "features": [{"id": 1}, 2, [3, "x"]], "b": 2}"#;
+        let mut parser = JsonParser::new(BufReaderJsonFeeder::with_capacity(3, &json[..]));
+        let mut elements = parser
+            .array_elements_at_pointer("/features")
+            .unwrap()
+            .unwrap();
+        let mut values = Vec::new();
+        while let Some(v) = elements.build_next(SerdeValueBuilder::default()).unwrap() {
+            values.push(v);
+        }
+        assert_eq!(values, vec![json!({"id": 1}), json!(2), json!([3, "x"])]);
+    }
 }
@@ -0,0 +1,180 @@
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::feeder::SliceJsonFeeder;
+use crate::options::JsonParserOptionsBuilder;
+use crate::parser::ParserError;
+use crate::{JsonEvent, JsonParser};
+
+/// Splits a byte slice containing a stream of multiple top-level JSON values
+/// (see [`JsonParserOptionsBuilder::with_streaming()`]) into separate
+/// documents. The splitter is an iterator over the byte ranges of the
+/// individual values. Leading and trailing white space is not included in
+/// the ranges.
+///
+/// ```
+/// use actson::DocumentSplitter;
+///
+/// let json = br#"{"name": "Elvis"} [1, 2]
+/// "a" 42"#;
+///
+/// let documents = DocumentSplitter::new(json)
+///     .map(|r| r.map(|range| &json[range]))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(documents, vec![
+///     br#"{"name": "Elvis"}"#.as_slice(),
+///     b"[1, 2]",
+///     br#""a""#,
+///     b"42",
+/// ]);
+/// ```
+pub struct DocumentSplitter<'a> {
+    json: &'a [u8],
+    parser: JsonParser<SliceJsonFeeder<'a>>,
+
+    /// The end of the last document
+    pos: usize,
+
+    /// The current nesting depth
+    depth: usize,
+
+    /// `true` if the end of the stream or an error has been reached
+    finished: bool,
+}
+
+impl<'a> DocumentSplitter<'a> {
+    /// Create a new splitter for the given stream of JSON values
+    pub fn new(json: &'a [u8]) -> Self {
+        let parser = JsonParser::new_with_options(
+            SliceJsonFeeder::new(json),
+            JsonParserOptionsBuilder::default()
+                .with_streaming(true)
+                .build(),
+        );
+        DocumentSplitter {
+            json,
+            parser,
+            pos: 0,
+            depth: 0,
+            finished: false,
+        }
+    }
+
+    /// Get the byte range of the document that ends at the parser's current
+    /// position
+    fn current_range(&self) -> Range<usize> {
+        let is_whitespace = |b: &&u8| matches!(**b, b' ' | b'\t' | b'\n' | b'\r');
+        let mut end = self.parser.parsed_bytes();
+        end -= self.json[self.pos..end]
+            .iter()
+            .rev()
+            .take_while(is_whitespace)
+            .count();
+        let start = self.pos
+            + self.json[self.pos..end]
+                .iter()
+                .take_while(is_whitespace)
+                .count();
+        start..end
+    }
+}
+
+impl Iterator for DocumentSplitter<'_> {
+    type Item = Result<Range<usize>, ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        loop {
+            let event = match self.parser.next_event() {
+                Ok(Some(e)) => e,
+                Ok(None) => {
+                    self.finished = true;
+                    return None;
+                }
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+            };
+
+            match event {
+                JsonEvent::StartObject | JsonEvent::StartArray => {
+                    self.depth += 1;
+                    continue;
+                }
+                JsonEvent::EndObject | JsonEvent::EndArray => self.depth -= 1,
+                _ => {}
+            }
+
+            if self.depth == 0 {
+                let range = self.current_range();
+                self.pos = range.end;
+                return Some(Ok(range));
+            }
+        }
+    }
+}
+
+impl FusedIterator for DocumentSplitter<'_> {}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::parser::ParserError;
+    use crate::DocumentSplitter;
+
+    /// Split the given JSON text and return the documents
+    fn split(json: &[u8]) -> Result<Vec<&[u8]>, ParserError> {
+        DocumentSplitter::new(json)
+            .map(|r| r.map(|range| &json[range]))
+            .collect()
+    }
+
+    /// Test that all kinds of values are split correctly
+    #[test]
+    fn values() {
+        let json = b" 1 2\"\"{\"key\":\"value\"}\n[\"a\",\"b\"]4true -1.5e3\tnull[] ";
+        assert_eq!(
+            split(json).unwrap(),
+            vec![
+                b"1".as_slice(),
+                b"2",
+                b"\"\"",
+                b"{\"key\":\"value\"}",
+                b"[\"a\",\"b\"]",
+                b"4",
+                b"true",
+                b"-1.5e3",
+                b"null",
+                b"[]",
+            ]
+        );
+    }
+
+    /// Test that empty input results in no documents
+    #[test]
+    fn empty() {
+        assert!(split(b"").unwrap().is_empty());
+        assert!(split(b" \n ").unwrap().is_empty());
+    }
+
+    /// Test that an error is returned for invalid input and that the
+    /// iterator ends afterwards
+    #[test]
+    fn invalid() {
+        let mut splitter = DocumentSplitter::new(b"[1] {\"a\" 1}");
+        assert_eq!(splitter.next().unwrap().unwrap(), 0..3);
+        assert!(matches!(
+            splitter.next(),
+            Some(Err(ParserError::SyntaxError(_)))
+        ));
+        assert!(splitter.next().is_none());
+    }
+}
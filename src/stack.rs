@@ -0,0 +1,114 @@
+//! Storage for the parser's mode stack. By default, the
+//! [`JsonParser`](crate::JsonParser) keeps its modes in a [`HeapStack`].
+//! For documents with a known maximum nesting depth, an [`InlineStack`]
+//! can be used instead to avoid allocating memory on the heap (see
+//! [`JsonParser::new_with_inline_stack()`](crate::JsonParser::new_with_inline_stack())).
+
+use alloc::vec::Vec;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A stack of parser modes. This trait is sealed and cannot be implemented
+/// outside of this crate.
+pub trait ModeStack: private::Sealed {
+    /// Get the number of modes on the stack
+    fn len(&self) -> usize;
+
+    /// Check if the stack is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the maximum number of modes the stack can hold or `None` if it
+    /// can grow indefinitely
+    fn capacity(&self) -> Option<usize>;
+
+    /// Push a mode to the stack. The caller has to make sure the stack is
+    /// not full.
+    fn push(&mut self, mode: i8);
+
+    /// Remove the topmost mode from the stack and return it
+    fn pop(&mut self) -> Option<i8>;
+
+    /// Get the topmost mode
+    fn last(&self) -> Option<i8>;
+}
+
+/// A [`ModeStack`] that stores its modes on the heap and grows as needed
+#[derive(Default, Debug, Clone)]
+pub struct HeapStack {
+    modes: Vec<i8>,
+}
+
+impl private::Sealed for HeapStack {}
+
+impl ModeStack for HeapStack {
+    fn len(&self) -> usize {
+        self.modes.len()
+    }
+
+    fn capacity(&self) -> Option<usize> {
+        None
+    }
+
+    fn push(&mut self, mode: i8) {
+        self.modes.push(mode);
+    }
+
+    fn pop(&mut self) -> Option<i8> {
+        self.modes.pop()
+    }
+
+    fn last(&self) -> Option<i8> {
+        self.modes.last().copied()
+    }
+}
+
+/// A [`ModeStack`] that stores up to `N` modes inline without allocating
+/// memory on the heap
+#[derive(Debug, Clone)]
+pub struct InlineStack<const N: usize> {
+    modes: [i8; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for InlineStack<N> {
+    fn default() -> Self {
+        const { assert!(N > 0, "an inline stack must hold at least one mode") };
+        InlineStack {
+            modes: [0; N],
+            len: 0,
+        }
+    }
+}
+
+impl<const N: usize> private::Sealed for InlineStack<N> {}
+
+impl<const N: usize> ModeStack for InlineStack<N> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn capacity(&self) -> Option<usize> {
+        Some(N)
+    }
+
+    fn push(&mut self, mode: i8) {
+        self.modes[self.len] = mode;
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<i8> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(self.modes[self.len])
+    }
+
+    fn last(&self) -> Option<i8> {
+        self.len.checked_sub(1).map(|i| self.modes[i])
+    }
+}
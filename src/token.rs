@@ -0,0 +1,241 @@
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use thiserror::Error;
+
+use crate::feeder::{JsonFeeder, PushJsonFeeder};
+use crate::parser::{
+    InvalidFloatValueError, InvalidIntValueError, InvalidStringValueError, ParserError,
+};
+use crate::stack::ModeStack;
+use crate::{JsonEvent, JsonParser};
+
+/// A JSON event bundled with its value. Returned by
+/// [`JsonParser::next_token()`](crate::JsonParser::next_token()).
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum JsonToken {
+    /// The JSON parser needs more input before the next token can be
+    /// returned. Invoke the parser's feeder to give it more input.
+    NeedMoreInput,
+
+    /// The start of a JSON object.
+    StartObject,
+
+    /// The end of a JSON object.
+    EndObject,
+
+    /// The start of a JSON array.
+    StartArray,
+
+    /// The end of a JSON array.
+    EndArray,
+
+    /// A field name.
+    FieldName(String),
+
+    /// A string value.
+    ValueString(String),
+
+    /// A chunk of a string value (see [`JsonEvent::PartialValueString`]).
+    PartialValueString(String),
+
+    /// An integer value.
+    ValueInt(i64),
+
+    /// A floating point value.
+    ValueFloat(f64),
+
+    /// A boolean value.
+    ValueBool(bool),
+
+    /// A `null` value.
+    ValueNull,
+
+    /// A run of white space (see [`JsonEvent::Whitespace`]).
+    Whitespace(String),
+
+    /// The text of a comment (see [`JsonEvent::Comment`]).
+    Comment(String),
+
+    /// A field name together with the token of its scalar value (see
+    /// [`JsonEvent::Entry`]).
+    Entry(String, Box<JsonToken>),
+
+    /// The start of a top-level value (see [`JsonEvent::StartDocument`]).
+    StartDocument,
+
+    /// The end of a top-level value (see [`JsonEvent::EndDocument`]).
+    EndDocument,
+}
+
+impl JsonToken {
+    /// Get the [`JsonEvent`] this token has been created from
+    pub fn event(&self) -> JsonEvent {
+        match self {
+            JsonToken::NeedMoreInput => JsonEvent::NeedMoreInput,
+            JsonToken::StartObject => JsonEvent::StartObject,
+            JsonToken::EndObject => JsonEvent::EndObject,
+            JsonToken::StartArray => JsonEvent::StartArray,
+            JsonToken::EndArray => JsonEvent::EndArray,
+            JsonToken::FieldName(_) => JsonEvent::FieldName,
+            JsonToken::ValueString(_) => JsonEvent::ValueString,
+            JsonToken::PartialValueString(_) => JsonEvent::PartialValueString,
+            JsonToken::ValueInt(_) => JsonEvent::ValueInt,
+            JsonToken::ValueFloat(_) => JsonEvent::ValueFloat,
+            JsonToken::ValueBool(true) => JsonEvent::ValueTrue,
+            JsonToken::ValueBool(false) => JsonEvent::ValueFalse,
+            JsonToken::ValueNull => JsonEvent::ValueNull,
+            JsonToken::Whitespace(_) => JsonEvent::Whitespace,
+            JsonToken::Comment(_) => JsonEvent::Comment,
+            JsonToken::Entry(..) => JsonEvent::Entry,
+            JsonToken::StartDocument => JsonEvent::StartDocument,
+            JsonToken::EndDocument => JsonEvent::EndDocument,
+        }
+    }
+}
+
+/// An error that can happen when reading the next [`JsonToken`]
+#[derive(Error, Debug)]
+pub enum TokenError {
+    #[error("{0}")]
+    Parse(#[from] ParserError),
+
+    #[error("{0}")]
+    InvalidStringValue(#[from] InvalidStringValueError),
+
+    #[error("{0}")]
+    InvalidIntValue(#[from] InvalidIntValueError),
+
+    #[error("{0}")]
+    InvalidFloatValue(#[from] InvalidFloatValueError),
+}
+
+impl<T, S> JsonParser<T, S>
+where
+    T: JsonFeeder,
+    S: ModeStack,
+{
+    /// Call this method to proceed parsing the JSON text and to get the next
+    /// token. In contrast to [`Self::next_event()`], the token already
+    /// contains the value of the event. This is less efficient, as every
+    /// value has to be converted and copied, but more convenient.
+    ///
+    /// ```
+    /// use actson::{JsonParser, JsonToken};
+    /// use actson::feeder::SliceJsonFeeder;
+    ///
+    /// let json = r#"{"name": "Elvis", "age": 42}"#.as_bytes();
+    ///
+    /// let feeder = SliceJsonFeeder::new(json);
+    /// let mut parser = JsonParser::new(feeder);
+    /// let mut tokens = Vec::new();
+    /// while let Some(token) = parser.next_token().unwrap() {
+    ///     tokens.push(token);
+    /// }
+    ///
+    /// assert_eq!(tokens, vec![
+    ///     JsonToken::StartObject,
+    ///     JsonToken::FieldName("name".to_string()),
+    ///     JsonToken::ValueString("Elvis".to_string()),
+    ///     JsonToken::FieldName("age".to_string()),
+    ///     JsonToken::ValueInt(42),
+    ///     JsonToken::EndObject,
+    /// ]);
+    /// ```
+    pub fn next_token(&mut self) -> Result<Option<JsonToken>, TokenError> {
+        let Some(event) = self.next_event()? else {
+            return Ok(None);
+        };
+
+        Ok(Some(match event {
+            JsonEvent::NeedMoreInput => JsonToken::NeedMoreInput,
+            JsonEvent::StartObject => JsonToken::StartObject,
+            JsonEvent::EndObject => JsonToken::EndObject,
+            JsonEvent::StartArray => JsonToken::StartArray,
+            JsonEvent::EndArray => JsonToken::EndArray,
+            JsonEvent::FieldName => JsonToken::FieldName(self.current_str()?.to_string()),
+            JsonEvent::ValueString => JsonToken::ValueString(self.current_str()?.to_string()),
+            JsonEvent::PartialValueString => {
+                JsonToken::PartialValueString(self.current_str()?.to_string())
+            }
+            JsonEvent::ValueInt => JsonToken::ValueInt(self.current_int()?),
+            JsonEvent::ValueFloat => JsonToken::ValueFloat(self.current_float()?),
+            JsonEvent::ValueTrue => JsonToken::ValueBool(true),
+            JsonEvent::ValueFalse => JsonToken::ValueBool(false),
+            JsonEvent::ValueNull => JsonToken::ValueNull,
+            JsonEvent::Whitespace => JsonToken::Whitespace(self.current_str()?.to_string()),
+            JsonEvent::Comment => JsonToken::Comment(self.current_str()?.to_string()),
+            JsonEvent::Entry => {
+                let key = self.current_str()?.to_string();
+                let value = match self.entry_value() {
+                    Some(v) => match v.event() {
+                        JsonEvent::ValueString => JsonToken::ValueString(v.as_str()?.to_string()),
+                        JsonEvent::ValueInt => JsonToken::ValueInt(v.as_int()?),
+                        JsonEvent::ValueFloat => JsonToken::ValueFloat(v.as_float()?),
+                        JsonEvent::ValueTrue => JsonToken::ValueBool(true),
+                        JsonEvent::ValueFalse => JsonToken::ValueBool(false),
+                        _ => JsonToken::ValueNull,
+                    },
+                    None => JsonToken::ValueNull,
+                };
+                JsonToken::Entry(key, Box::new(value))
+            }
+            JsonEvent::StartDocument => JsonToken::StartDocument,
+            JsonEvent::EndDocument => JsonToken::EndDocument,
+        }))
+    }
+}
+
+impl<S> JsonParser<PushJsonFeeder, S>
+where
+    S: ModeStack,
+{
+    /// Push as many bytes from the given buffer to the feeder as it accepts,
+    /// then parse all tokens that are now available and append them to the
+    /// given vector. Returns the number of bytes consumed from the buffer.
+    /// Call this method repeatedly with the remaining bytes until all of
+    /// them have been consumed. At the end of the input, call
+    /// [`PushJsonFeeder::done()`] and then this method with an empty buffer
+    /// to collect the remaining tokens.
+    ///
+    /// ```
+    /// use actson::feeder::PushJsonFeeder;
+    /// use actson::{JsonParser, JsonToken};
+    ///
+    /// let json = r#"{"name": "Elvis", "age": 42}"#.as_bytes();
+    ///
+    /// let mut parser = JsonParser::new(PushJsonFeeder::with_capacity(8));
+    /// let mut tokens = Vec::new();
+    /// let mut pos = 0;
+    /// while pos < json.len() {
+    ///     pos += parser.feed_and_collect(&json[pos..], &mut tokens).unwrap();
+    /// }
+    /// parser.feeder.done();
+    /// parser.feed_and_collect(&[], &mut tokens).unwrap();
+    ///
+    /// assert_eq!(tokens, vec![
+    ///     JsonToken::StartObject,
+    ///     JsonToken::FieldName("name".to_string()),
+    ///     JsonToken::ValueString("Elvis".to_string()),
+    ///     JsonToken::FieldName("age".to_string()),
+    ///     JsonToken::ValueInt(42),
+    ///     JsonToken::EndObject,
+    /// ]);
+    /// ```
+    pub fn feed_and_collect(
+        &mut self,
+        buf: &[u8],
+        tokens: &mut Vec<JsonToken>,
+    ) -> Result<usize, TokenError> {
+        let n = self.feeder.push_bytes(buf);
+        while let Some(token) = self.next_token()? {
+            if token == JsonToken::NeedMoreInput {
+                break;
+            }
+            tokens.push(token);
+        }
+        Ok(n)
+    }
+}
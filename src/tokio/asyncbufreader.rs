@@ -1,5 +1,8 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
 use crate::feeder::{FillError, JsonFeeder};
-use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, BufReader};
 
 /// A [`JsonFeeder`] that reads from an asynchronous [`BufReader`].
 pub struct AsyncBufReaderJsonFeeder<T> {
@@ -21,6 +24,18 @@ where
         }
     }
 
+    /// Create a new feeder that reads from the given reader through an
+    /// asynchronous [`BufReader`] with the given capacity. Larger buffers
+    /// can increase the throughput when parsing large files.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn with_capacity(capacity: usize, reader: T) -> Self {
+        assert!(capacity > 0, "capacity must not be 0");
+        Self::new(BufReader::with_capacity(capacity, reader))
+    }
+
     /// Fill the feeder's internal buffer
     pub async fn fill_buf(&mut self) -> Result<(), FillError> {
         self.reader.consume(self.pos);
@@ -29,6 +44,32 @@ where
         self.pos = 0;
         Ok(())
     }
+
+    /// Attempt to fill the feeder's internal buffer. This is the poll-based
+    /// variant of [`fill_buf()`](Self::fill_buf()), which is useful for
+    /// implementing futures or streams by hand.
+    pub fn poll_fill_buf(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), FillError>> {
+        if self.pos > 0 {
+            // the buffer must not look empty while we're waiting for more data
+            self.reader.consume(self.pos);
+            self.filled = false;
+            self.pos = 0;
+        }
+        match Pin::new(&mut self.reader).poll_fill_buf(cx) {
+            Poll::Ready(Ok(_)) => {
+                self.filled = true;
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e.into())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+    /// Get the number of bytes in the feeder's internal buffer that have not
+    /// been consumed by the parser yet. Use this to decide if it is
+    /// necessary to fill the buffer.
+    pub fn buffered_len(&self) -> usize {
+        self.reader.buffer().len() - self.pos
+    }
 }
 
 impl<T> JsonFeeder for AsyncBufReaderJsonFeeder<T>
@@ -1,3 +1,5 @@
 mod asyncbufreader;
+mod stream;
 
 pub use asyncbufreader::AsyncBufReaderJsonFeeder;
+pub use stream::{JsonEventStream, JsonStreamError};
@@ -0,0 +1,130 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::{FusedStream, Stream};
+use thiserror::Error;
+use tokio::io::AsyncRead;
+
+use super::AsyncBufReaderJsonFeeder;
+use crate::feeder::FillError;
+use crate::parser::ParserError;
+use crate::{JsonEvent, JsonParser};
+
+/// An error that can happen while reading events from a [`JsonEventStream`]
+#[derive(Error, Debug)]
+pub enum JsonStreamError {
+    #[error("{0}")]
+    Parse(#[from] ParserError),
+
+    #[error("{0}")]
+    Fill(#[from] FillError),
+}
+
+/// A [`Stream`] of JSON events. It asynchronously fills the feeder's buffer
+/// whenever the parser needs more input, so it never yields
+/// [`JsonEvent::NeedMoreInput`]. Created by [`JsonParser::into_stream()`].
+///
+/// Use [`parser()`](Self::parser()) between two items to access the values
+/// of the current event.
+///
+/// ```
+/// use std::future::poll_fn;
+/// use std::pin::Pin;
+///
+/// use futures_core::Stream;
+/// use tokio::io::BufReader;
+///
+/// use actson::{JsonParser, JsonEvent};
+/// use actson::tokio::AsyncBufReaderJsonFeeder;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let json = r#"{"name": "Elvis"}"#.as_bytes();
+///
+///     let feeder = AsyncBufReaderJsonFeeder::new(BufReader::new(json));
+///     let mut stream = JsonParser::new(feeder).into_stream();
+///     while let Some(event) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+///         match event.unwrap() {
+///             JsonEvent::FieldName => assert!(matches!(stream.parser().current_str(), Ok("name"))),
+///             JsonEvent::ValueString => assert!(matches!(stream.parser().current_str(), Ok("Elvis"))),
+///             _ => {}
+///         }
+///     }
+/// }
+/// ```
+pub struct JsonEventStream<T> {
+    parser: JsonParser<AsyncBufReaderJsonFeeder<T>>,
+    finished: bool,
+}
+
+impl<T> JsonEventStream<T> {
+    /// Get a reference to the underlying parser (e.g. to access the values
+    /// of the current event)
+    pub fn parser(&self) -> &JsonParser<AsyncBufReaderJsonFeeder<T>> {
+        &self.parser
+    }
+
+    /// Consume the stream and return the underlying parser
+    pub fn into_inner(self) -> JsonParser<AsyncBufReaderJsonFeeder<T>> {
+        self.parser
+    }
+}
+
+impl<T> JsonParser<AsyncBufReaderJsonFeeder<T>>
+where
+    T: AsyncRead + Unpin,
+{
+    /// Convert this parser into a [`Stream`] of JSON events (see
+    /// [`JsonEventStream`])
+    pub fn into_stream(self) -> JsonEventStream<T> {
+        JsonEventStream {
+            parser: self,
+            finished: false,
+        }
+    }
+}
+
+impl<T> Stream for JsonEventStream<T>
+where
+    T: AsyncRead + Unpin,
+{
+    type Item = Result<JsonEvent, JsonStreamError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.finished {
+            return Poll::Ready(None);
+        }
+
+        loop {
+            match this.parser.next_event() {
+                Ok(Some(JsonEvent::NeedMoreInput)) => match this.parser.feeder.poll_fill_buf(cx) {
+                    Poll::Ready(Ok(())) => {}
+                    Poll::Ready(Err(e)) => {
+                        this.finished = true;
+                        return Poll::Ready(Some(Err(e.into())));
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+                Ok(Some(e)) => return Poll::Ready(Some(Ok(e))),
+                Ok(None) => {
+                    this.finished = true;
+                    return Poll::Ready(None);
+                }
+                Err(e) => {
+                    this.finished = true;
+                    return Poll::Ready(Some(Err(e.into())));
+                }
+            }
+        }
+    }
+}
+
+impl<T> FusedStream for JsonEventStream<T>
+where
+    T: AsyncRead + Unpin,
+{
+    fn is_terminated(&self) -> bool {
+        self.finished
+    }
+}
@@ -0,0 +1,427 @@
+use thiserror::Error;
+
+use crate::feeder::{FillError, FillJsonFeeder, SliceJsonFeeder};
+use crate::parser::{
+    EntryValue, InvalidFloatValueError, InvalidIntValueError, InvalidStringValueError, ParserError,
+};
+use crate::stack::ModeStack;
+use crate::{JsonEvent, JsonParser};
+
+/// A handler that receives the values of a JSON text from
+/// [`JsonParser::drive()`]. All methods have empty default implementations,
+/// so you only need to implement the ones you are interested in.
+#[allow(unused_variables)]
+pub trait Visitor {
+    /// Called at the start of a JSON object
+    fn start_object(&mut self) {}
+
+    /// Called at the end of a JSON object
+    fn end_object(&mut self) {}
+
+    /// Called at the start of a JSON array
+    fn start_array(&mut self) {}
+
+    /// Called at the end of a JSON array
+    fn end_array(&mut self) {}
+
+    /// Called for each field name
+    fn field_name(&mut self, name: &str) {}
+
+    /// Called for each string value
+    fn value_string(&mut self, value: &str) {}
+
+    /// Called for each chunk of a string value if the parser has been
+    /// configured to return strings in chunks (see
+    /// [`JsonParserOptionsBuilder::with_max_string_chunk()`](crate::options::JsonParserOptionsBuilder::with_max_string_chunk())).
+    /// The last chunk is passed to [`value_string()`](Self::value_string()).
+    fn partial_value_string(&mut self, value: &str) {}
+
+    /// Called for each integer value
+    fn value_int(&mut self, value: i64) {}
+
+    /// Called for each floating point value
+    fn value_float(&mut self, value: f64) {}
+
+    /// Called for each boolean value
+    fn value_bool(&mut self, value: bool) {}
+
+    /// Called for each `null` value
+    fn value_null(&mut self) {}
+
+    /// Called for each run of white space between tokens if the parser has
+    /// been configured to return it (see
+    /// [`JsonParserOptionsBuilder::with_emit_whitespace()`](crate::options::JsonParserOptionsBuilder::with_emit_whitespace()))
+    fn whitespace(&mut self, value: &str) {}
+
+    /// Called for each comment if the parser has been configured to return
+    /// comments (see
+    /// [`JsonParserOptionsBuilder::with_emit_comments()`](crate::options::JsonParserOptionsBuilder::with_emit_comments()))
+    fn comment(&mut self, text: &str) {}
+
+    /// Called at the start of each top-level value if the parser has been
+    /// configured to return document events (see
+    /// [`JsonParserOptionsBuilder::with_document_events()`](crate::options::JsonParserOptionsBuilder::with_document_events()))
+    fn start_document(&mut self) {}
+
+    /// Called at the end of each top-level value if the parser has been
+    /// configured to return document events (see
+    /// [`JsonParserOptionsBuilder::with_document_events()`](crate::options::JsonParserOptionsBuilder::with_document_events()))
+    fn end_document(&mut self) {}
+}
+
+/// An error that can happen in [`JsonParser::drive()`]
+#[derive(Error, Debug)]
+pub enum DriveError {
+    #[error("{0}")]
+    Parse(#[from] ParserError),
+
+    #[error("{0}")]
+    Fill(#[from] FillError),
+
+    #[error("{0}")]
+    InvalidStringValue(#[from] InvalidStringValueError),
+
+    #[error("{0}")]
+    InvalidIntValue(#[from] InvalidIntValueError),
+
+    #[error("{0}")]
+    InvalidFloatValue(#[from] InvalidFloatValueError),
+}
+
+/// Pass the value of a [`JsonEvent::Entry`] to the given [`Visitor`]
+fn visit_entry_value<V>(visitor: &mut V, value: EntryValue<'_>) -> Result<(), DriveError>
+where
+    V: Visitor,
+{
+    match value.event() {
+        JsonEvent::ValueString => visitor.value_string(value.as_str()?),
+        JsonEvent::ValueInt => visitor.value_int(value.as_int()?),
+        JsonEvent::ValueFloat => visitor.value_float(value.as_float()?),
+        JsonEvent::ValueTrue => visitor.value_bool(true),
+        JsonEvent::ValueFalse => visitor.value_bool(false),
+        JsonEvent::ValueNull => visitor.value_null(),
+        _ => unreachable!("entries only contain scalar values"),
+    }
+    Ok(())
+}
+
+impl<T, S> JsonParser<T, S>
+where
+    T: FillJsonFeeder,
+    S: ModeStack,
+{
+    /// Parse the whole JSON text and pass all values to the given
+    /// [`Visitor`]. Whenever the parser needs more input, the feeder is
+    /// filled synchronously.
+    ///
+    /// ```
+    /// use actson::JsonParser;
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::visitor::Visitor;
+    ///
+    /// #[derive(Default)]
+    /// struct Sum {
+    ///     sum: i64,
+    /// }
+    ///
+    /// impl Visitor for Sum {
+    ///     fn value_int(&mut self, value: i64) {
+    ///         self.sum += value;
+    ///     }
+    /// }
+    ///
+    /// let json = r#"{"a": 1, "b": [2, 3, "4"]}"#.as_bytes();
+    ///
+    /// let feeder = SliceJsonFeeder::new(json);
+    /// let mut parser = JsonParser::new(feeder);
+    /// let mut sum = Sum::default();
+    /// parser.drive(&mut sum).unwrap();
+    ///
+    /// assert_eq!(sum.sum, 6);
+    /// ```
+    pub fn drive<V>(&mut self, visitor: &mut V) -> Result<(), DriveError>
+    where
+        V: Visitor,
+    {
+        while let Some(event) = self.next_event()? {
+            match event {
+                JsonEvent::NeedMoreInput => self.feeder.fill()?,
+                JsonEvent::StartObject => visitor.start_object(),
+                JsonEvent::EndObject => visitor.end_object(),
+                JsonEvent::StartArray => visitor.start_array(),
+                JsonEvent::EndArray => visitor.end_array(),
+                JsonEvent::FieldName => visitor.field_name(self.current_str()?),
+                JsonEvent::ValueString => visitor.value_string(self.current_str()?),
+                JsonEvent::PartialValueString => visitor.partial_value_string(self.current_str()?),
+                JsonEvent::ValueInt => visitor.value_int(self.current_int()?),
+                JsonEvent::ValueFloat => visitor.value_float(self.current_float()?),
+                JsonEvent::ValueTrue => visitor.value_bool(true),
+                JsonEvent::ValueFalse => visitor.value_bool(false),
+                JsonEvent::ValueNull => visitor.value_null(),
+                JsonEvent::Whitespace => visitor.whitespace(self.current_str()?),
+                JsonEvent::Comment => visitor.comment(self.current_str()?),
+                JsonEvent::StartDocument => visitor.start_document(),
+                JsonEvent::EndDocument => visitor.end_document(),
+                JsonEvent::Entry => {
+                    visitor.field_name(self.current_str()?);
+                    if let Some(value) = self.entry_value() {
+                        visit_entry_value(visitor, value)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse the whole JSON text and call the given function each time a
+    /// top-level value has been completed. This is mostly useful if the
+    /// parser has been created in streaming mode (see
+    /// [`JsonParserOptionsBuilder::with_streaming()`](crate::options::JsonParserOptionsBuilder::with_streaming())),
+    /// where the JSON text can contain multiple top-level values. The
+    /// function receives the parser after the last event of the value (e.g.
+    /// [`JsonEvent::EndObject`]). Whenever the parser needs more input, the
+    /// feeder is filled synchronously.
+    ///
+    /// ```
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::options::JsonParserOptionsBuilder;
+    /// use actson::JsonParser;
+    ///
+    /// let json = b"1 [2, 3] {\"a\": 4}\n5";
+    ///
+    /// let options = JsonParserOptionsBuilder::default()
+    ///     .with_streaming(true)
+    ///     .build();
+    /// let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+    ///
+    /// let mut count = 0;
+    /// parser.for_each_value(|_| count += 1).unwrap();
+    /// assert_eq!(count, 4);
+    /// ```
+    pub fn for_each_value<F>(&mut self, mut f: F) -> Result<(), DriveError>
+    where
+        F: FnMut(&Self),
+    {
+        while let Some(event) = self.next_event()? {
+            match event {
+                JsonEvent::NeedMoreInput => self.feeder.fill()?,
+                JsonEvent::Whitespace
+                | JsonEvent::Comment
+                | JsonEvent::PartialValueString
+                | JsonEvent::StartDocument
+                | JsonEvent::EndDocument => {}
+                _ => {
+                    if self.container_stack().next().is_none() {
+                        f(self);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse the given byte slice and call the given function for each event.
+/// Since the whole input is available up front, the function is never
+/// called with [`JsonEvent::NeedMoreInput`]. It receives the parser, so it
+/// can get the values of the events (e.g. with
+/// [`JsonParser::current_str()`]). Parsing stops at the first error returned
+/// by the parser or the function.
+///
+/// ```
+/// use actson::parser::ParserError;
+/// use actson::{drive_slice, JsonEvent};
+///
+/// let json = br#"{"name": "Elvis", "albums": [1, 2]}"#;
+///
+/// let mut names = Vec::new();
+/// drive_slice(json, |event, parser| {
+///     if event == JsonEvent::FieldName {
+///         names.push(parser.current_str().unwrap().to_string());
+///     }
+///     Ok::<_, ParserError>(())
+/// })
+/// .unwrap();
+/// assert_eq!(names, vec!["name", "albums"]);
+/// ```
+pub fn drive_slice<F, E>(json: &[u8], mut f: F) -> Result<(), E>
+where
+    F: FnMut(JsonEvent, &JsonParser<SliceJsonFeeder<'_>>) -> Result<(), E>,
+    E: From<ParserError>,
+{
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    while let Some(event) = parser.next_event()? {
+        if event != JsonEvent::NeedMoreInput {
+            f(event, &parser)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+    use std::io::BufReader;
+
+    use crate::feeder::BufReaderJsonFeeder;
+    use crate::options::JsonParserOptionsBuilder;
+    use crate::parser::ParserError;
+    use crate::visitor::{DriveError, Visitor};
+    use crate::{JsonEvent, JsonParser};
+
+    /// A visitor that records all calls
+    #[derive(Default)]
+    struct Recorder {
+        calls: Vec<String>,
+    }
+
+    impl Visitor for Recorder {
+        fn start_object(&mut self) {
+            self.calls.push("{".to_string());
+        }
+
+        fn end_object(&mut self) {
+            self.calls.push("}".to_string());
+        }
+
+        fn start_array(&mut self) {
+            self.calls.push("[".to_string());
+        }
+
+        fn end_array(&mut self) {
+            self.calls.push("]".to_string());
+        }
+
+        fn field_name(&mut self, name: &str) {
+            self.calls.push(format!("field {name}"));
+        }
+
+        fn value_string(&mut self, value: &str) {
+            self.calls.push(format!("string {value}"));
+        }
+
+        fn value_int(&mut self, value: i64) {
+            self.calls.push(format!("int {value}"));
+        }
+
+        fn value_float(&mut self, value: f64) {
+            self.calls.push(format!("float {value}"));
+        }
+
+        fn value_bool(&mut self, value: bool) {
+            self.calls.push(format!("bool {value}"));
+        }
+
+        fn value_null(&mut self) {
+            self.calls.push("null".to_string());
+        }
+    }
+
+    /// Test that all values are passed to the visitor and that the feeder
+    /// is filled when necessary
+    #[test]
+    fn drive() {
+        let json = r#"{"a": [1, 2.5, "b"], "c": {"d": true, "e": false}, "f": null}"#;
+        let reader = BufReader::with_capacity(3, json.as_bytes());
+        let mut parser = JsonParser::new(BufReaderJsonFeeder::new(reader));
+        let mut recorder = Recorder::default();
+        parser.drive(&mut recorder).unwrap();
+
+        assert_eq!(
+            recorder.calls,
+            [
+                "{",
+                "field a",
+                "[",
+                "int 1",
+                "float 2.5",
+                "string b",
+                "]",
+                "field c",
+                "{",
+                "field d",
+                "bool true",
+                "field e",
+                "bool false",
+                "}",
+                "field f",
+                "null",
+                "}",
+            ]
+        );
+    }
+
+    /// Test that errors are returned
+    #[test]
+    fn error() {
+        let reader = BufReader::new(r#"{"a": 1"#.as_bytes());
+        let mut parser = JsonParser::new(BufReaderJsonFeeder::new(reader));
+        assert!(matches!(
+            parser.drive(&mut Recorder::default()),
+            Err(DriveError::Parse(ParserError::UnexpectedEof {
+                open_containers: 1
+            }))
+        ));
+    }
+
+    /// Test that the function is called once after each top-level value
+    #[test]
+    fn for_each_value() {
+        let json = "\"a\" [1, [2]] {\"b\": {}}\n\"cde\" 3";
+        let reader = BufReader::with_capacity(2, json.as_bytes());
+        let options = JsonParserOptionsBuilder::default()
+            .with_streaming(true)
+            .with_max_string_chunk(1)
+            .build();
+        let mut parser = JsonParser::new_with_options(BufReaderJsonFeeder::new(reader), options);
+        let mut offsets = Vec::new();
+        parser
+            .for_each_value(|p| offsets.push(p.parsed_bytes()))
+            .unwrap();
+        assert_eq!(offsets, [3, 12, 22, 28, 30]);
+    }
+
+    /// Test that the function is called for each event of a slice and that
+    /// errors of the parser and the function are returned
+    #[test]
+    fn drive_slice() {
+        let mut events = Vec::new();
+        super::drive_slice(br#"{"a": [1, true]}"#, |e, _| {
+            events.push(e);
+            Ok::<_, ParserError>(())
+        })
+        .unwrap();
+        assert_eq!(
+            events,
+            [
+                JsonEvent::StartObject,
+                JsonEvent::FieldName,
+                JsonEvent::StartArray,
+                JsonEvent::ValueInt,
+                JsonEvent::ValueTrue,
+                JsonEvent::EndArray,
+                JsonEvent::EndObject
+            ]
+        );
+
+        assert!(matches!(
+            super::drive_slice(b"[1", |_, _| Ok::<_, ParserError>(())),
+            Err(ParserError::UnexpectedEof { .. })
+        ));
+
+        let mut n = 0;
+        let r = super::drive_slice(b"[1, 2, 3]", |e, p| {
+            n += 1;
+            if e == JsonEvent::ValueInt && p.current_int::<i64>()? == 2 {
+                return Err(DriveError::Parse(ParserError::NoMoreInput));
+            }
+            Ok(())
+        });
+        assert!(matches!(
+            r,
+            Err(DriveError::Parse(ParserError::NoMoreInput))
+        ));
+        assert_eq!(n, 3);
+    }
+}
@@ -0,0 +1,810 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use num_traits::PrimInt;
+
+use thiserror::Error;
+
+use crate::feeder::{JsonFeeder, SliceJsonFeeder};
+use crate::parser::{InvalidStringValueError, ParserError};
+use crate::stack::ModeStack;
+use crate::{JsonEvent, JsonParser};
+
+/// An error that can happen when writing JSON
+#[derive(Error, Debug)]
+pub enum WriteError {
+    #[error("{0}")]
+    Io(#[from] io::Error),
+
+    #[error("{0}")]
+    InvalidStringValue(#[from] InvalidStringValueError),
+
+    #[error("not a JSON number: {0}")]
+    IllegalJsonNumber(f64),
+
+    #[error("hexadecimal number too large: {0}")]
+    HexNumberTooLarge(String),
+}
+
+/// The kinds of containers the writer can be in
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Container {
+    Object,
+    Array,
+}
+
+/// Writes JSON text to an underlying [`Write`]r. Values can either be
+/// written one by one (e.g. with [`begin_object()`](Self::begin_object()),
+/// [`field_name()`](Self::field_name()), [`value_str()`](Self::value_str()),
+/// or [`end_object()`](Self::end_object())) or directly from the events of a
+/// [`JsonParser`] with [`write_event()`](Self::write_event()). The writer
+/// takes care of separating values by commas and of escaping strings.
+///
+/// The writer either produces compact output (see [`new()`](Self::new())),
+/// or indented output (see [`new_pretty()`](Self::new_pretty())). The
+/// indentation can be configured with [`indent()`](Self::indent()) or
+/// [`indent_with_tabs()`](Self::indent_with_tabs()).
+///
+/// ```
+/// use actson::writer::JsonWriter;
+///
+/// let mut writer = JsonWriter::new(Vec::new());
+/// writer.begin_object().unwrap();
+/// writer.field_name("name").unwrap();
+/// writer.value_str("Elvis").unwrap();
+/// writer.field_name("albums").unwrap();
+/// writer.begin_array().unwrap();
+/// writer.value_str("Elvis Presley").unwrap();
+/// writer.value_str("Elvis").unwrap();
+/// writer.end_array().unwrap();
+/// writer.end_object().unwrap();
+///
+/// assert_eq!(
+///     String::from_utf8(writer.into_inner()).unwrap(),
+///     r#"{"name":"Elvis","albums":["Elvis Presley","Elvis"]}"#
+/// );
+/// ```
+pub struct JsonWriter<W> {
+    writer: W,
+
+    /// `true` if the output should be indented
+    pretty: bool,
+
+    /// The string used to indent each level if the output is indented
+    indent: Vec<u8>,
+
+    /// The containers the writer is currently in and the number of values
+    /// written to each of them
+    stack: Vec<(Container, usize)>,
+
+    /// The number of top-level values written so far
+    top_level_count: usize,
+
+    /// `true` if a field name has just been written and the writer now
+    /// expects the field's value
+    after_field_name: bool,
+
+    /// `true` if a partial string value has been written and the string
+    /// has not been closed yet
+    in_partial_string: bool,
+
+    /// `true` if all non-ASCII characters should be escaped
+    escape_non_ascii: bool,
+}
+
+impl<W> JsonWriter<W>
+where
+    W: Write,
+{
+    /// Create a new writer that produces compact output
+    pub fn new(writer: W) -> Self {
+        JsonWriter {
+            writer,
+            pretty: false,
+            indent: b"  ".to_vec(),
+            stack: vec![],
+            top_level_count: 0,
+            after_field_name: false,
+            in_partial_string: false,
+            escape_non_ascii: false,
+        }
+    }
+
+    /// Create a new writer that produces indented output
+    ///
+    /// ```
+    /// use actson::writer::JsonWriter;
+    ///
+    /// let mut writer = JsonWriter::new_pretty(Vec::new());
+    /// writer.begin_object().unwrap();
+    /// writer.field_name("name").unwrap();
+    /// writer.value_str("Elvis").unwrap();
+    /// writer.field_name("albums").unwrap();
+    /// writer.begin_array().unwrap();
+    /// writer.value_str("Elvis Presley").unwrap();
+    /// writer.end_array().unwrap();
+    /// writer.end_object().unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), r#"{
+    ///   "name": "Elvis",
+    ///   "albums": [
+    ///     "Elvis Presley"
+    ///   ]
+    /// }"#);
+    /// ```
+    pub fn new_pretty(writer: W) -> Self {
+        JsonWriter {
+            pretty: true,
+            ..Self::new(writer)
+        }
+    }
+
+    /// Escape all non-ASCII characters in strings and field names as
+    /// `\uXXXX` (or as a UTF-16 surrogate pair if they are outside the Basic
+    /// Multilingual Plane). This makes sure the output is pure ASCII.
+    ///
+    /// ```
+    /// use actson::writer::JsonWriter;
+    ///
+    /// let mut writer = JsonWriter::new(Vec::new()).escape_non_ascii(true);
+    /// writer.value_str("Grüße 🎸").unwrap();
+    ///
+    /// assert_eq!(
+    ///     String::from_utf8(writer.into_inner()).unwrap(),
+    ///     r#""Gr\u00fc\u00dfe \ud83c\udfb8""#
+    /// );
+    /// ```
+    pub fn escape_non_ascii(mut self, escape_non_ascii: bool) -> Self {
+        self.escape_non_ascii = escape_non_ascii;
+        self
+    }
+
+    /// Produce indented output with the given number of spaces per level
+    ///
+    /// ```
+    /// use actson::writer::JsonWriter;
+    ///
+    /// let mut writer = JsonWriter::new(Vec::new()).indent(4);
+    /// writer.begin_object().unwrap();
+    /// writer.field_name("name").unwrap();
+    /// writer.value_str("Elvis").unwrap();
+    /// writer.end_object().unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), r#"{
+    ///     "name": "Elvis"
+    /// }"#);
+    /// ```
+    pub fn indent(mut self, spaces: usize) -> Self {
+        self.pretty = true;
+        self.indent = vec![b' '; spaces];
+        self
+    }
+
+    /// Produce indented output with one tab per level
+    pub fn indent_with_tabs(mut self) -> Self {
+        self.pretty = true;
+        self.indent = vec![b'\t'];
+        self
+    }
+
+    /// Produce compact output without any insignificant white space. This
+    /// is the default for writers created with [`new()`](Self::new()).
+    pub fn compact(mut self) -> Self {
+        self.pretty = false;
+        self
+    }
+
+    /// Get a reference to the underlying writer
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Get a mutable reference to the underlying writer
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consume this writer and return the underlying one
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Write a line break and indent the next line according to the
+    /// current depth
+    fn newline(&mut self) -> io::Result<()> {
+        self.writer.write_all(b"\n")?;
+        for _ in 0..self.stack.len() {
+            self.writer.write_all(&self.indent)?;
+        }
+        Ok(())
+    }
+
+    /// Write everything that needs to come before a value (e.g. a comma)
+    fn begin_value(&mut self) -> io::Result<()> {
+        if self.after_field_name {
+            self.after_field_name = false;
+            return Ok(());
+        }
+
+        match self.stack.last_mut() {
+            Some((Container::Array, count)) => {
+                let first = *count == 0;
+                *count += 1;
+                if !first {
+                    self.writer.write_all(b",")?;
+                }
+                if self.pretty {
+                    self.newline()?;
+                }
+            }
+
+            Some((Container::Object, _)) => {}
+
+            None => {
+                // separate multiple top-level values
+                if self.top_level_count > 0 {
+                    self.writer.write_all(b"\n")?;
+                }
+                self.top_level_count += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the end of a container
+    fn end_container(&mut self, end: &[u8]) -> io::Result<()> {
+        if let Some((_, count)) = self.stack.pop() {
+            if self.pretty && count > 0 {
+                self.newline()?;
+            }
+        }
+        self.writer.write_all(end)
+    }
+
+    /// Write the given string with escaped special characters but without
+    /// quotes. Escapes quotation marks, reverse solidi, and control
+    /// characters as required by RFC 8259, as well as all non-ASCII
+    /// characters if [`escape_non_ascii`](Self::escape_non_ascii) is enabled.
+    fn write_escaped(&mut self, s: &str) -> io::Result<()> {
+        let bytes = s.as_bytes();
+        let mut start = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let b = bytes[i];
+            let escaped: &[u8] = match b {
+                b'"' => b"\\\"",
+                b'\\' => b"\\\\",
+                b'\n' => b"\\n",
+                b'\r' => b"\\r",
+                b'\t' => b"\\t",
+                0x08 => b"\\b",
+                0x0c => b"\\f",
+                0x00..=0x1f => {
+                    self.writer.write_all(&bytes[start..i])?;
+                    write!(self.writer, "\\u{:04x}", b)?;
+                    i += 1;
+                    start = i;
+                    continue;
+                }
+                0x80.. if self.escape_non_ascii => {
+                    self.writer.write_all(&bytes[start..i])?;
+                    // `i` always points to the start of a character here
+                    let c = s[i..].chars().next().unwrap_or_default();
+                    for u in c.encode_utf16(&mut [0; 2]) {
+                        write!(self.writer, "\\u{:04x}", u)?;
+                    }
+                    i += c.len_utf8();
+                    start = i;
+                    continue;
+                }
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            };
+            self.writer.write_all(&bytes[start..i])?;
+            self.writer.write_all(escaped)?;
+            i += 1;
+            start = i;
+        }
+        self.writer.write_all(&bytes[start..])
+    }
+
+    /// Write the start of a JSON object
+    pub fn begin_object(&mut self) -> Result<(), WriteError> {
+        self.begin_value()?;
+        self.writer.write_all(b"{")?;
+        self.stack.push((Container::Object, 0));
+        Ok(())
+    }
+
+    /// Write the end of a JSON object
+    pub fn end_object(&mut self) -> Result<(), WriteError> {
+        Ok(self.end_container(b"}")?)
+    }
+
+    /// Write the start of a JSON array
+    pub fn begin_array(&mut self) -> Result<(), WriteError> {
+        self.begin_value()?;
+        self.writer.write_all(b"[")?;
+        self.stack.push((Container::Array, 0));
+        Ok(())
+    }
+
+    /// Write the end of a JSON array
+    pub fn end_array(&mut self) -> Result<(), WriteError> {
+        Ok(self.end_container(b"]")?)
+    }
+
+    /// Write a field name. The next value will be the field's value.
+    pub fn field_name(&mut self, name: &str) -> Result<(), WriteError> {
+        if let Some((Container::Object, count)) = self.stack.last_mut() {
+            let first = *count == 0;
+            *count += 1;
+            if !first {
+                self.writer.write_all(b",")?;
+            }
+            if self.pretty {
+                self.newline()?;
+            }
+        }
+        self.writer.write_all(b"\"")?;
+        self.write_escaped(name)?;
+        self.writer
+            .write_all(if self.pretty { b"\": " } else { b"\":" })?;
+        self.after_field_name = true;
+        Ok(())
+    }
+
+    /// Write a string value
+    pub fn value_str(&mut self, value: &str) -> Result<(), WriteError> {
+        self.begin_value()?;
+        self.writer.write_all(b"\"")?;
+        self.write_escaped(value)?;
+        self.writer.write_all(b"\"")?;
+        Ok(())
+    }
+
+    /// Write an integer value
+    pub fn value_int<I>(&mut self, value: I) -> Result<(), WriteError>
+    where
+        I: PrimInt + Display,
+    {
+        self.begin_value()?;
+        write!(self.writer, "{}", value)?;
+        Ok(())
+    }
+
+    /// Write a floating point value. Returns an error if the value is not
+    /// finite, since JSON does not support NaN or infinity.
+    pub fn value_float(&mut self, value: f64) -> Result<(), WriteError> {
+        if !value.is_finite() {
+            return Err(WriteError::IllegalJsonNumber(value));
+        }
+        self.begin_value()?;
+        write!(self.writer, "{:?}", value)?;
+        Ok(())
+    }
+
+    /// Write a number as it appeared in a parsed JSON text. Forms that the
+    /// parser only accepts with certain options and that are not valid JSON
+    /// (a leading plus sign, leading zeros, or hexadecimal digits) are
+    /// converted to valid JSON.
+    fn value_number_text(&mut self, text: &[u8]) -> Result<(), WriteError> {
+        self.begin_value()?;
+        let digits = match text.split_first() {
+            Some((b'-', rest)) => {
+                self.writer.write_all(b"-")?;
+                rest
+            }
+            Some((b'+', rest)) => rest,
+            _ => text,
+        };
+
+        if let Some(hex) = digits
+            .strip_prefix(b"0x")
+            .or_else(|| digits.strip_prefix(b"0X"))
+        {
+            let n = core::str::from_utf8(hex)
+                .ok()
+                .and_then(|hex| u128::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| {
+                    WriteError::HexNumberTooLarge(String::from_utf8_lossy(text).into_owned())
+                })?;
+            write!(self.writer, "{}", n)?;
+            return Ok(());
+        }
+
+        // remove leading zeros but keep the last digit of the integer part
+        let int_len = digits
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .unwrap_or(digits.len());
+        let zeros = digits[..int_len.saturating_sub(1)]
+            .iter()
+            .take_while(|&&b| b == b'0')
+            .count();
+        self.writer.write_all(&digits[zeros..])?;
+        Ok(())
+    }
+
+    /// Write a boolean value
+    pub fn value_bool(&mut self, value: bool) -> Result<(), WriteError> {
+        self.begin_value()?;
+        self.writer
+            .write_all(if value { b"true" } else { b"false" })?;
+        Ok(())
+    }
+
+    /// Write a `null` value
+    pub fn value_null(&mut self) -> Result<(), WriteError> {
+        self.begin_value()?;
+        self.writer.write_all(b"null")?;
+        Ok(())
+    }
+
+    /// Write the given event together with its value from the given parser.
+    /// Numbers are written exactly as they appeared in the parsed JSON text,
+    /// unless the parser has been configured to accept numbers that are not
+    /// valid JSON (e.g. with
+    /// [`with_allow_hex_numbers()`](crate::options::JsonParserOptionsBuilder::with_allow_hex_numbers())).
+    /// Such numbers are converted to valid JSON (e.g. `0x1F` to `31`, `+1`
+    /// to `1`, and `007` to `7`).
+    ///
+    /// ```
+    /// use actson::{JsonParser, JsonEvent};
+    /// use actson::feeder::SliceJsonFeeder;
+    /// use actson::writer::JsonWriter;
+    ///
+    /// let json = r#"{ "name": "Elvis", "albums": [ 1, 2.0e3 ] }"#.as_bytes();
+    ///
+    /// let feeder = SliceJsonFeeder::new(json);
+    /// let mut parser = JsonParser::new(feeder);
+    /// let mut writer = JsonWriter::new(Vec::new());
+    /// while let Some(event) = parser.next_event().unwrap() {
+    ///     writer.write_event(event, &parser).unwrap();
+    /// }
+    ///
+    /// assert_eq!(
+    ///     String::from_utf8(writer.into_inner()).unwrap(),
+    ///     r#"{"name":"Elvis","albums":[1,2.0e3]}"#
+    /// );
+    /// ```
+    pub fn write_event<T, S>(
+        &mut self,
+        event: JsonEvent,
+        parser: &JsonParser<T, S>,
+    ) -> Result<(), WriteError>
+    where
+        T: JsonFeeder,
+        S: ModeStack,
+    {
+        match event {
+            JsonEvent::NeedMoreInput
+            | JsonEvent::Whitespace
+            | JsonEvent::Comment
+            | JsonEvent::StartDocument
+            | JsonEvent::EndDocument => {}
+            JsonEvent::StartObject => self.begin_object()?,
+            JsonEvent::EndObject => self.end_object()?,
+            JsonEvent::StartArray => self.begin_array()?,
+            JsonEvent::EndArray => self.end_array()?,
+            JsonEvent::FieldName => self.field_name(parser.current_str()?)?,
+            JsonEvent::PartialValueString => {
+                if !self.in_partial_string {
+                    self.begin_value()?;
+                    self.writer.write_all(b"\"")?;
+                    self.in_partial_string = true;
+                }
+                self.write_escaped(parser.current_str()?)?;
+            }
+            JsonEvent::ValueString => {
+                if self.in_partial_string {
+                    self.write_escaped(parser.current_str()?)?;
+                    self.writer.write_all(b"\"")?;
+                    self.in_partial_string = false;
+                } else {
+                    self.value_str(parser.current_str()?)?;
+                }
+            }
+            JsonEvent::ValueInt | JsonEvent::ValueFloat => {
+                self.value_number_text(parser.current_bytes())?;
+            }
+            JsonEvent::ValueTrue => self.value_bool(true)?,
+            JsonEvent::ValueFalse => self.value_bool(false)?,
+            JsonEvent::ValueNull => self.value_null()?,
+            JsonEvent::Entry => {
+                self.field_name(parser.current_str()?)?;
+                if let Some(value) = parser.entry_value() {
+                    match value.event() {
+                        JsonEvent::ValueString => self.value_str(value.as_str()?)?,
+                        JsonEvent::ValueInt | JsonEvent::ValueFloat => {
+                            self.value_number_text(value.as_bytes())?;
+                        }
+                        JsonEvent::ValueTrue => self.value_bool(true)?,
+                        JsonEvent::ValueFalse => self.value_bool(false)?,
+                        _ => self.value_null()?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error that can happen when minifying JSON with [`minify()`]
+#[derive(Error, Debug)]
+pub enum MinifyError {
+    #[error("{0}")]
+    Parse(#[from] ParserError),
+
+    #[error("{0}")]
+    Write(#[from] WriteError),
+}
+
+/// Parse the given JSON text and write it to `out` without any insignificant
+/// white space. Numbers are written exactly as they appear in the input.
+///
+/// ```
+/// let json = r#"{
+///     "name": "Elvis",
+///     "price": 1.50
+/// }"#.as_bytes();
+///
+/// let mut out = Vec::new();
+/// actson::minify(json, &mut out).unwrap();
+///
+/// assert_eq!(out, br#"{"name":"Elvis","price":1.50}"#);
+/// ```
+pub fn minify<W>(input: &[u8], out: &mut W) -> Result<(), MinifyError>
+where
+    W: Write,
+{
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(input));
+    let mut writer = JsonWriter::new(out);
+    while let Some(event) = parser.next_event()? {
+        writer.write_event(event, &parser)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::feeder::SliceJsonFeeder;
+    use crate::options::JsonParserOptionsBuilder;
+    use crate::parser::ParserError;
+    use crate::writer::{minify, JsonWriter, MinifyError, WriteError};
+    use crate::{JsonEvent, JsonParser};
+
+    /// Parse the given JSON text and write all events to a new writer
+    fn rewrite(json: &str, pretty: bool, max_string_chunk: Option<usize>) -> String {
+        let mut builder = JsonParserOptionsBuilder::default();
+        if let Some(c) = max_string_chunk {
+            builder = builder.with_max_string_chunk(c);
+        }
+        let mut parser =
+            JsonParser::new_with_options(SliceJsonFeeder::new(json.as_bytes()), builder.build());
+        let mut writer = if pretty {
+            JsonWriter::new_pretty(Vec::new())
+        } else {
+            JsonWriter::new(Vec::new())
+        };
+        while let Some(event) = parser.next_event().unwrap() {
+            writer.write_event(event, &parser).unwrap();
+        }
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    /// Test that events are written in compact mode
+    #[test]
+    fn compact() {
+        assert_eq!(
+            rewrite(
+                r#" { "a" : [ 1 , -2.5e3 , true , false , null ] , "b" : { } , "c" : [ ] } "#,
+                false,
+                None
+            ),
+            r#"{"a":[1,-2.5e3,true,false,null],"b":{},"c":[]}"#
+        );
+    }
+
+    /// Test that the indentation can be configured
+    #[test]
+    fn indent() {
+        let write = |mut writer: JsonWriter<Vec<u8>>| {
+            let mut parser = JsonParser::new(SliceJsonFeeder::new(br#"{"a":[1,{}]}"#));
+            while let Some(event) = parser.next_event().unwrap() {
+                writer.write_event(event, &parser).unwrap();
+            }
+            String::from_utf8(writer.into_inner()).unwrap()
+        };
+
+        assert_eq!(
+            write(JsonWriter::new(Vec::new()).indent(3)),
+            "{\n   \"a\": [\n      1,\n      {}\n   ]\n}"
+        );
+        assert_eq!(
+            write(JsonWriter::new(Vec::new()).indent_with_tabs()),
+            "{\n\t\"a\": [\n\t\t1,\n\t\t{}\n\t]\n}"
+        );
+        assert_eq!(
+            write(JsonWriter::new(Vec::new()).indent(0)),
+            "{\n\"a\": [\n1,\n{}\n]\n}"
+        );
+        assert_eq!(
+            write(JsonWriter::new_pretty(Vec::new()).compact()),
+            r#"{"a":[1,{}]}"#
+        );
+    }
+
+    /// Test that events are written in indented mode
+    #[test]
+    fn pretty() {
+        assert_eq!(
+            rewrite(r#"{"a":[1,{"b":null}],"c":{},"d":[]}"#, true, None),
+            r#"{
+  "a": [
+    1,
+    {
+      "b": null
+    }
+  ],
+  "c": {},
+  "d": []
+}"#
+        );
+    }
+
+    /// Test that special characters are escaped
+    #[test]
+    fn escape() {
+        assert_eq!(
+            rewrite(r#"{"a\"b":"\\\/\b\f\n\r\t\u0001ä"}"#, false, None),
+            "{\"a\\\"b\":\"\\\\/\\b\\f\\n\\r\\t\\u0001\u{e4}\"}"
+        );
+    }
+
+    /// Test that strings with special characters are written in a way
+    /// that they can be parsed back to the original values
+    #[test]
+    fn round_trip() {
+        let mut values = (0..0x20u8)
+            .map(|b| char::from(b).to_string())
+            .collect::<Vec<_>>();
+        values.push("\"\\/\u{7f}".to_string());
+        values.push("Grüße, 世界! 🎸 \u{2028}\u{ffff}\u{10ffff}".to_string());
+        values.push(String::new());
+
+        for escape_non_ascii in [false, true] {
+            let mut writer = JsonWriter::new(Vec::new()).escape_non_ascii(escape_non_ascii);
+            writer.begin_object().unwrap();
+            for v in &values {
+                writer.field_name(v).unwrap();
+                writer.value_str(v).unwrap();
+            }
+            writer.end_object().unwrap();
+            let json = writer.into_inner();
+            if escape_non_ascii {
+                assert!(json.is_ascii());
+            }
+
+            let mut parser = JsonParser::new(SliceJsonFeeder::new(&json));
+            let mut parsed = Vec::new();
+            while let Some(event) = parser.next_event().unwrap() {
+                if event == JsonEvent::FieldName || event == JsonEvent::ValueString {
+                    parsed.push(parser.current_str().unwrap().to_string());
+                }
+            }
+            let expected = values
+                .iter()
+                .flat_map(|v| [v.clone(), v.clone()])
+                .collect::<Vec<_>>();
+            assert_eq!(parsed, expected);
+        }
+    }
+
+    /// Test that partial strings are joined
+    #[test]
+    fn partial_strings() {
+        assert_eq!(
+            rewrite(r#"["abc\"def", "g"]"#, false, Some(2)),
+            r#"["abc\"def","g"]"#
+        );
+    }
+
+    /// Test that top-level values are separated
+    #[test]
+    fn top_level_values() {
+        let mut writer = JsonWriter::new(Vec::new());
+        writer.value_int(1).unwrap();
+        writer.value_int(2u8).unwrap();
+        writer.value_str("a").unwrap();
+        writer.begin_array().unwrap();
+        writer.end_array().unwrap();
+        assert_eq!(writer.into_inner(), b"1\n2\n\"a\"\n[]");
+    }
+
+    /// Test that floats are written so that they are parsed as floats again
+    /// and that non-finite floats are rejected
+    #[test]
+    fn floats() {
+        let mut writer = JsonWriter::new(Vec::new());
+        writer.begin_array().unwrap();
+        writer.value_float(1.0).unwrap();
+        writer.value_float(-0.5).unwrap();
+        writer.value_float(1e300).unwrap();
+        assert!(matches!(
+            writer.value_float(f64::NAN),
+            Err(WriteError::IllegalJsonNumber(_))
+        ));
+        assert!(matches!(
+            writer.value_float(f64::INFINITY),
+            Err(WriteError::IllegalJsonNumber(_))
+        ));
+        writer.end_array().unwrap();
+        assert_eq!(writer.into_inner(), b"[1.0,-0.5,1e300]");
+    }
+
+    /// Test that numbers that are not valid JSON are converted
+    #[test]
+    fn non_standard_numbers() {
+        let json = b"[0x1F, -0XfF, +1, +0.5e3, 007, -00.25, 00, 0, -0, 10, 0e5]";
+        let expected = "[31,-255,1,0.5e3,7,-0.25,0,0,-0,10,0e5]";
+        for entry_events in [false, true] {
+            let options = JsonParserOptionsBuilder::default()
+                .with_allow_hex_numbers(true)
+                .with_allow_plus_sign(true)
+                .with_allow_leading_zeros(true)
+                .with_entry_events(entry_events)
+                .build();
+
+            let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+            let mut writer = JsonWriter::new(Vec::new());
+            while let Some(event) = parser.next_event().unwrap() {
+                writer.write_event(event, &parser).unwrap();
+            }
+            assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), expected);
+
+            // numbers in entries
+            let json = br#"{"a": 0x10, "b": +2, "c": 003}"#;
+            let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+            let mut writer = JsonWriter::new(Vec::new());
+            while let Some(event) = parser.next_event().unwrap() {
+                writer.write_event(event, &parser).unwrap();
+            }
+            assert_eq!(
+                String::from_utf8(writer.into_inner()).unwrap(),
+                r#"{"a":16,"b":2,"c":3}"#
+            );
+        }
+
+        let json = b"0x100000000000000000000000000000000";
+        let options = JsonParserOptionsBuilder::default()
+            .with_allow_hex_numbers(true)
+            .build();
+        let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+        let mut writer = JsonWriter::new(Vec::new());
+        let event = parser.next_event().unwrap().unwrap();
+        assert!(matches!(
+            writer.write_event(event, &parser),
+            Err(WriteError::HexNumberTooLarge(_))
+        ));
+    }
+
+    /// Test that whitespace is removed and that invalid JSON is rejected
+    #[test]
+    fn minify_text() {
+        let mut out = Vec::new();
+        minify(b" [ 1.50 , -0e+1 , { \"a\" : \"b c\" } ]\n", &mut out).unwrap();
+        assert_eq!(out, br#"[1.50,-0e+1,{"a":"b c"}]"#);
+
+        let mut out = Vec::new();
+        assert!(matches!(
+            minify(b"[1, 2", &mut out),
+            Err(MinifyError::Parse(ParserError::UnexpectedEof {
+                open_containers: 1
+            }))
+        ));
+    }
+}
@@ -4,6 +4,7 @@ use std::io::{BufReader, Read};
 
 use crate::prettyprinter::PrettyPrinter;
 use actson::feeder::{BufReaderJsonFeeder, JsonFeeder};
+use actson::options::JsonParserOptionsBuilder;
 use actson::{JsonEvent, JsonParser};
 
 /// Test if [`BufReaderJsonFeeder`] can fully consume a file
@@ -47,6 +48,32 @@ fn read_from_file() {
     assert!(feeder.is_done());
 }
 
+/// Test if [`BufReaderJsonFeeder::buffered_len()`] returns the number of
+/// bytes that have not been consumed yet
+#[test]
+fn buffered_len() {
+    let file = File::open("tests/fixtures/pass1.txt").unwrap();
+    let reader = BufReader::with_capacity(32, file);
+
+    let mut feeder = BufReaderJsonFeeder::new(reader);
+    assert_eq!(feeder.buffered_len(), 0);
+
+    assert!(feeder.fill_buf().is_ok());
+    assert_eq!(feeder.buffered_len(), 32);
+
+    feeder.consume_input(2);
+    assert_eq!(feeder.buffered_len(), 30);
+
+    while feeder.next_input().is_some() {}
+    assert_eq!(feeder.buffered_len(), 0);
+
+    while !feeder.is_done() {
+        assert!(feeder.fill_buf().is_ok());
+        while feeder.next_input().is_some() {}
+        assert_eq!(feeder.buffered_len(), 0);
+    }
+}
+
 /// Test if [`BufReaderJsonFeeder`] can be used to parse a JSON file
 #[test]
 fn parse_from_file() {
@@ -78,3 +105,67 @@ fn parse_from_file() {
     let am: Value = serde_json::from_str(actual).unwrap();
     assert_eq!(em, am);
 }
+
+/// Test if a [`BufReaderJsonFeeder`] created with
+/// [`BufReaderJsonFeeder::with_capacity()`] can be used to parse a JSON file
+#[test]
+fn parse_with_capacity() {
+    let mut expected = Vec::new();
+    {
+        let mut file = File::open("tests/fixtures/pass1.txt").unwrap();
+        file.read_to_end(&mut expected).unwrap();
+    }
+
+    for capacity in [1, 32, 64 * 1024] {
+        let file = File::open("tests/fixtures/pass1.txt").unwrap();
+        let mut feeder = BufReaderJsonFeeder::with_capacity(capacity, file);
+
+        assert!(feeder.fill_buf().is_ok());
+        assert_eq!(feeder.buffered_len(), capacity.min(expected.len()));
+
+        let mut parser = JsonParser::new(feeder);
+        let mut prettyprinter = PrettyPrinter::new();
+        while let Some(e) = parser.next_event().unwrap() {
+            if e == JsonEvent::NeedMoreInput {
+                parser.feeder.fill_buf().unwrap();
+            }
+            prettyprinter.on_event(e, &parser).unwrap();
+        }
+
+        let em: Value = serde_json::from_slice(&expected).unwrap();
+        let am: Value = serde_json::from_str(prettyprinter.get_result()).unwrap();
+        assert_eq!(em, am);
+    }
+}
+
+/// Test that a [`BufReaderJsonFeeder`] cannot be created without a buffer
+#[test]
+#[should_panic(expected = "capacity must not be 0")]
+fn zero_capacity() {
+    BufReaderJsonFeeder::with_capacity(0, &b"[]"[..]);
+}
+
+/// Test if the reader can be recovered from the parser and continues where
+/// the parser has stopped
+#[test]
+fn into_inner() {
+    let data = br#"[1, {"a": "b"}] rest of the data"#;
+    let options = JsonParserOptionsBuilder::default()
+        .with_stop_after_first_value(true)
+        .build();
+
+    for capacity in [1, 4, 64] {
+        let feeder = BufReaderJsonFeeder::with_capacity(capacity, &data[..]);
+        let mut parser = JsonParser::new_with_options(feeder, options);
+        while let Some(e) = parser.next_event().unwrap() {
+            if e == JsonEvent::NeedMoreInput {
+                parser.feeder.fill_buf().unwrap();
+            }
+        }
+
+        let mut rest = String::new();
+        let mut reader = parser.into_inner().into_inner();
+        reader.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, " rest of the data");
+    }
+}
@@ -31,6 +31,7 @@ pub struct PrettyPrinter {
     types: Vec<Type>,
     element_counts: Vec<i32>,
     level: i32,
+    partial_string: String,
 }
 
 impl PrettyPrinter {
@@ -40,6 +41,7 @@ impl PrettyPrinter {
             types: vec![],
             element_counts: vec![],
             level: 0,
+            partial_string: String::new(),
         }
     }
 
@@ -164,18 +166,40 @@ impl PrettyPrinter {
         T: JsonFeeder,
     {
         match event {
-            JsonEvent::NeedMoreInput => {}
+            JsonEvent::NeedMoreInput
+            | JsonEvent::Whitespace
+            | JsonEvent::Comment
+            | JsonEvent::StartDocument
+            | JsonEvent::EndDocument => {}
             JsonEvent::StartObject => self.on_start_object(),
             JsonEvent::EndObject => self.on_end_object(),
             JsonEvent::StartArray => self.on_start_array(),
             JsonEvent::EndArray => self.on_end_array(),
             JsonEvent::FieldName => self.on_field_name(parser.current_str()?),
-            JsonEvent::ValueString => self.on_value_string(parser.current_str()?),
+            JsonEvent::PartialValueString => self.partial_string.push_str(parser.current_str()?),
+            JsonEvent::ValueString => {
+                let mut value = std::mem::take(&mut self.partial_string);
+                value.push_str(parser.current_str()?);
+                self.on_value_string(&value);
+            }
             JsonEvent::ValueInt => self.on_value_int(parser.current_int::<i64>()?),
             JsonEvent::ValueFloat => self.on_value_float(parser.current_float()?),
             JsonEvent::ValueTrue => self.on_value_bool(true),
             JsonEvent::ValueFalse => self.on_value_bool(false),
             JsonEvent::ValueNull => self.on_value_null(),
+            JsonEvent::Entry => {
+                self.on_field_name(parser.current_str()?);
+                let value = parser.entry_value().unwrap();
+                match value.event() {
+                    JsonEvent::ValueString => self.on_value_string(value.as_str()?),
+                    JsonEvent::ValueInt => self.on_value_int(value.as_int::<i64>()?),
+                    JsonEvent::ValueFloat => self.on_value_float(value.as_float()?),
+                    JsonEvent::ValueTrue => self.on_value_bool(true),
+                    JsonEvent::ValueFalse => self.on_value_bool(false),
+                    _ => self.on_value_null(),
+                }
+            }
+            _ => {}
         }
         Ok(())
     }
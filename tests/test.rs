@@ -4,10 +4,14 @@ mod tokio;
 
 use std::fs;
 
-use actson::feeder::PushJsonFeeder;
-use actson::options::JsonParserOptionsBuilder;
-use actson::parser::ParserError;
-use actson::{JsonEvent, JsonParser};
+use actson::feeder::{JsonFeeder, PushJsonFeeder, SliceJsonFeeder};
+use actson::options::{JsonParserOptions, JsonParserOptionsBuilder};
+use actson::parser::{
+    ErrorCode, InvalidFloatValueError, InvalidIntValueError, InvalidStringValueError, NumberKind,
+    ParserError, Position, SyntaxErrorDetails,
+};
+use actson::token::TokenError;
+use actson::{JsonEvent, JsonParser, JsonToken};
 use prettyprinter::PrettyPrinter;
 use serde_json::Value;
 
@@ -196,8 +200,21 @@ fn illegal_character() {
     let json = "{\"key\":\x02}";
     assert!(matches!(
         parse_fail(json.as_bytes()),
-        ParserError::IllegalInput(0x02)
+        ParserError::IllegalInput(0x02, _)
     ));
+
+    // control characters in strings have to be escaped
+    for json in ["[\"a\nb\"]", "{\"a\tb\": 1}", "\"\x1f\""] {
+        let err = parse_fail(json.as_bytes());
+        assert!(
+            matches!(err, ParserError::UnescapedControlCharacter(_, _)),
+            "{json:?}: {err:?}"
+        );
+    }
+    assert_eq!(
+        parse_fail(b"\"\t\"").to_string(),
+        "unescaped control character 0x09 in string at line 1, column 2"
+    );
 }
 
 #[test]
@@ -224,15 +241,112 @@ fn all_escape_characters() {
     assert_eq!(json_parser.current_str().unwrap(), "\"\\/\u{8}\u{c}\n\r\t");
 }
 
+/// Test that the raw bytes of a string can be accessed
+#[test]
+fn current_bytes() {
+    let json = r#"{"n\u00e4me": "Bj\u0153rn\n"}"#;
+    let mut parser = JsonParser::new(PushJsonFeeder::new());
+    parser.feeder.push_bytes(json.as_bytes());
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+    assert_eq!(parser.current_bytes(), "n\u{e4}me".as_bytes());
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    assert_eq!(parser.current_bytes(), "Bj\u{0153}rn\n".as_bytes());
+}
+
+/// Test that the current string value can be copied into a buffer
+#[test]
+fn copy_current_into() {
+    let json = r#"["Elvis", "Bj\u0153rn"]"#;
+    let mut parser = JsonParser::new(PushJsonFeeder::new());
+    parser.feeder.push_bytes(json.as_bytes());
+
+    let mut s = String::new();
+    let mut b = Vec::new();
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    assert_eq!(parser.copy_current_into(&mut s).unwrap(), 5);
+    assert_eq!(parser.copy_current_bytes_into(&mut b), 5);
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    assert_eq!(parser.copy_current_into(&mut s).unwrap(), 6);
+    assert_eq!(parser.copy_current_bytes_into(&mut b), 6);
+
+    assert_eq!(s, "ElvisBj\u{0153}rn");
+    assert_eq!(b, "ElvisBj\u{0153}rn".as_bytes());
+}
+
 #[test]
 fn syntax_error() {
     let json = "{key}";
     assert!(matches!(
         parse_fail(json.as_bytes()),
-        ParserError::SyntaxError
+        ParserError::SyntaxError(_)
+    ));
+}
+
+/// Test that syntax errors contain the offending byte, the parser state,
+/// and the position
+#[test]
+fn syntax_error_details() {
+    let json = "{\n  \"a\": 1,\n  \"b\": }";
+    let err = parse_fail(json.as_bytes());
+    assert!(matches!(
+        &err,
+        ParserError::SyntaxError(SyntaxErrorDetails {
+            byte: Some(b'}'),
+            state: "VA",
+            position: Position {
+                offset: 19,
+                line: 3,
+                column: 8
+            }
+        })
+    ));
+    assert_eq!(
+        err.to_string(),
+        "syntax error at line 3, column 8: unexpected '}' (state VA)"
+    );
+
+    // a newline that causes an error belongs to the line it ends
+    let err = parse_fail("[\"a\n\"]".as_bytes());
+    assert_eq!(
+        err.to_string(),
+        "unescaped control character 0x0a in string at line 1, column 4"
+    );
+}
+
+/// Test that illegal input contains the position
+#[test]
+fn illegal_input_position() {
+    let json = "[1,\n\x02]";
+    assert!(matches!(
+        parse_fail(json.as_bytes()),
+        ParserError::IllegalInput(
+            0x02,
+            Position {
+                offset: 4,
+                line: 2,
+                column: 1
+            }
+        )
     ));
 }
 
+/// Test that errors have the right codes
+#[test]
+fn error_codes() {
+    assert_eq!(parse_fail(b"[1,\x02]").code(), ErrorCode::IllegalInput);
+    assert_eq!(parse_fail(b"[1}").code(), ErrorCode::Syntax);
+    assert_eq!(parse_fail(b"[1").code(), ErrorCode::UnexpectedEof);
+    assert_eq!(parse_fail(b" ").code(), ErrorCode::EmptyInput);
+    assert_eq!(
+        parse_fail(br#""\ud800""#).code(),
+        ErrorCode::InvalidSurrogate
+    );
+    assert_eq!(parse_fail(b"[1] 2").code(), ErrorCode::TrailingData);
+    assert_eq!(ErrorCode::TooManyEvents as u32, 13);
+}
+
 /// Make sure a number right before the end of the object can be parsed
 #[test]
 fn number_and_end_of_object() {
@@ -253,7 +367,7 @@ fn illegal_number() {
     let json = r#"{"n":-2.}"#;
     assert!(matches!(
         parse_fail(json.as_bytes()),
-        ParserError::SyntaxError
+        ParserError::SyntaxError(_)
     ));
 }
 
@@ -298,8 +412,40 @@ fn number_and_eof() {
     let json = r#"{"i":42"#;
     assert!(matches!(
         parse_fail(json.as_bytes()),
-        ParserError::NoMoreInput
+        ParserError::UnexpectedEof { open_containers: 1 }
+    ));
+}
+
+/// Test that the number of unclosed containers is reported at the end of
+/// the input
+#[test]
+fn unclosed_containers() {
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(br#"[{"a": [[1, 2], [3"#));
+    let mut open = Vec::new();
+    let err = loop {
+        match parser.next_event() {
+            Ok(_) => open.push(parser.unclosed_containers()),
+            Err(e) => break e,
+        }
+    };
+    assert_eq!(open, vec![1, 2, 2, 3, 4, 4, 3, 3, 4, 4]);
+    assert!(matches!(
+        err,
+        ParserError::UnexpectedEof { open_containers: 4 }
     ));
+    assert_eq!(parser.unclosed_containers(), 4);
+    assert_eq!(
+        err.to_string(),
+        "unexpected end of input: 4 object(s) or array(s) left unclosed"
+    );
+
+    // incomplete top-level values do not have unclosed containers
+    assert!(matches!(parse_fail(br#""abc"#), ParserError::NoMoreInput));
+
+    // the stack is empty after the end of the input has been reached
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(b"[]"));
+    while parser.next_event().unwrap().is_some() {}
+    assert_eq!(parser.unclosed_containers(), 0);
 }
 
 /// Test if a top-level zero can be parsed
@@ -377,6 +523,73 @@ fn test_suite_pass() {
     }
 }
 
+/// Test that lone and mismatched UTF-16 surrogates are rejected
+#[test]
+fn invalid_surrogate() {
+    let offset = |json: &str| match parse_fail(json.as_bytes()) {
+        ParserError::InvalidSurrogate(position) => position.offset,
+        e => panic!("unexpected error: {e}"),
+    };
+
+    // lone high surrogate
+    assert_eq!(offset(r#"["\uD800"]"#), 8);
+    assert_eq!(offset(r#"{"\uD801": 1}"#), 8);
+
+    // lone low surrogate
+    assert_eq!(offset(r#"["\uDC00"]"#), 7);
+
+    // the low surrogate in the second string must not be combined with
+    // the high surrogate from the first one
+    assert_eq!(offset(r#"["\uD800", "abcdef\uDC00"]"#), 8);
+
+    // high surrogate followed by something other than `\u`
+    assert_eq!(offset(r#"["\uD800x\uDC00"]"#), 8);
+    assert_eq!(offset(r#"["\uD800\n"]"#), 9);
+    assert_eq!(offset("[\"\\uD800\u{e4}\"]"), 8);
+
+    // high surrogate followed by another high or a regular character
+    assert_eq!(offset(r#"["\uD800\uD800"]"#), 13);
+    assert_eq!(offset(r#"["\uD800\u0041"]"#), 13);
+
+    let err = parse_fail(br#"["\uD800"]"#);
+    assert_eq!(
+        err.to_string(),
+        "invalid UTF-16 surrogate at line 1, column 9"
+    );
+}
+
+/// Test that invalid surrogates are replaced with U+FFFD if lenient Unicode
+/// handling is enabled
+#[test]
+fn lenient_unicode() {
+    let parse_lenient = |json: &str| {
+        let mut parser = JsonParser::new_with_options(
+            PushJsonFeeder::new(),
+            JsonParserOptionsBuilder::default()
+                .with_lenient_unicode(true)
+                .build(),
+        );
+        parse_with_parser(json, &mut parser)
+    };
+
+    assert_eq!(parse_lenient(r#""\uD800""#), "\"\u{fffd}\"");
+    assert_eq!(parse_lenient(r#""\uDC00x""#), "\"\u{fffd}x\"");
+    assert_eq!(parse_lenient(r#""\uD800x""#), "\"\u{fffd}x\"");
+    assert_eq!(parse_lenient(r#""\uD800\n""#), "\"\u{fffd}\\n\"");
+    assert_eq!(parse_lenient(r#""\uD800\u0041""#), "\"\u{fffd}A\"");
+    assert_eq!(
+        parse_lenient(r#""\uD800\uD83D\uDE00""#),
+        "\"\u{fffd}\u{1f600}\""
+    );
+    assert_eq!(
+        parse_lenient(r#"{"\uD800": "\uDC00"}"#),
+        "{\n  \"\u{fffd}\": \"\u{fffd}\"\n}"
+    );
+
+    // valid surrogate pairs are not affected
+    assert_eq!(parse_lenient(r#""\uD83D\uDE00""#), "\"\u{1f600}\"");
+}
+
 #[test]
 fn test_string_1_2_3_bytes() {
     let json = r#"["\u0060\u012a\u12AB"]"#;
@@ -466,6 +679,20 @@ fn streaming_objects() {
     );
 }
 
+/// Test that an empty stream can be parsed in streaming mode
+#[test]
+fn streaming_empty() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_streaming(true)
+        .build();
+
+    for json in ["", "  \n  "] {
+        let feeder = PushJsonFeeder::new();
+        let r = parse_with_parser(json, &mut JsonParser::new_with_options(feeder, options));
+        assert_eq!("", r);
+    }
+}
+
 /// Test if multiple top-level numbers can be parsed in streaming mode
 #[test]
 fn streaming_complex() {
@@ -491,3 +718,2387 @@ fn streaming_complex() {
         r
     );
 }
+
+/// Test if long string values are returned in chunks
+#[test]
+fn max_string_chunk() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_max_string_chunk(4)
+        .build();
+    let json = r#"{"a long key": ["abcdefghij", "Bj\u0153rn\uD801\uDC37!", "", "ab"]}"#;
+
+    let feeder = PushJsonFeeder::new();
+    let mut parser = JsonParser::new_with_options(feeder, options);
+    parser.feeder.push_bytes(json.as_bytes());
+    parser.feeder.done();
+
+    let mut events = Vec::new();
+    while let Some(e) = parser.next_event().unwrap() {
+        match e {
+            JsonEvent::FieldName | JsonEvent::PartialValueString | JsonEvent::ValueString => {
+                events.push((e, parser.current_str().unwrap().to_string()))
+            }
+            _ => {}
+        }
+    }
+
+    assert_eq!(
+        events,
+        vec![
+            (JsonEvent::FieldName, "a long key".to_string()),
+            (JsonEvent::PartialValueString, "abcd".to_string()),
+            (JsonEvent::PartialValueString, "efgh".to_string()),
+            (JsonEvent::ValueString, "ij".to_string()),
+            (JsonEvent::PartialValueString, "Bj\u{0153}".to_string()),
+            (JsonEvent::PartialValueString, "rn\u{10437}".to_string()),
+            (JsonEvent::ValueString, "!".to_string()),
+            (JsonEvent::ValueString, "".to_string()),
+            (JsonEvent::ValueString, "ab".to_string()),
+        ]
+    );
+
+    let feeder = PushJsonFeeder::new();
+    let r = parse_with_parser(json, &mut JsonParser::new_with_options(feeder, options));
+    assert_json_eq(json, &r);
+}
+
+/// Test that strings and field names longer than the maximum length are rejected
+#[test]
+fn max_string_length() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_max_string_length(6)
+        .build();
+
+    let json = r#"{"Elvis": ["Bj\u0153rn", "\u00e4\u00e4\u00e4"]}"#;
+    let feeder = PushJsonFeeder::new();
+    let r = parse_with_parser(json, &mut JsonParser::new_with_options(feeder, options));
+    assert_json_eq(json, &r);
+
+    let json = r#"{"Presley": 1}"#;
+    let feeder = PushJsonFeeder::new();
+    assert!(matches!(
+        parse_fail_with_parser(
+            json.as_bytes(),
+            &mut JsonParser::new_with_options(feeder, options)
+        ),
+        ParserError::StringTooLong(6)
+    ));
+
+    let json = r#"["Bj\u0153rn!"]"#;
+    let feeder = PushJsonFeeder::new();
+    assert!(matches!(
+        parse_fail_with_parser(
+            json.as_bytes(),
+            &mut JsonParser::new_with_options(feeder, options)
+        ),
+        ParserError::StringTooLong(6)
+    ));
+
+    let json = r#"["\u00e4\u00e4\u00e4"]"#;
+    let options = JsonParserOptionsBuilder::default()
+        .with_max_string_length(5)
+        .build();
+    let feeder = PushJsonFeeder::new();
+    assert!(matches!(
+        parse_fail_with_parser(
+            json.as_bytes(),
+            &mut JsonParser::new_with_options(feeder, options)
+        ),
+        ParserError::StringTooLong(5)
+    ));
+}
+
+/// Test that the next event can be peeked without consuming it
+#[test]
+fn peek_event() {
+    let json = r#"{"name": "Elvis", "age": 42}"#;
+    let mut parser = JsonParser::new(PushJsonFeeder::new());
+
+    assert_eq!(parser.peek_event().unwrap(), Some(JsonEvent::NeedMoreInput));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::NeedMoreInput));
+    parser.feeder.push_bytes(json.as_bytes());
+    parser.feeder.done();
+
+    assert_eq!(parser.peek_event().unwrap(), Some(JsonEvent::StartObject));
+    assert_eq!(parser.peek_event().unwrap(), Some(JsonEvent::StartObject));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+
+    assert_eq!(parser.peek_event().unwrap(), Some(JsonEvent::FieldName));
+    assert_eq!(parser.current_str().unwrap(), "name");
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+    assert_eq!(parser.current_str().unwrap(), "name");
+
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+
+    // the value and the end of the object are returned at the same time
+    assert_eq!(parser.peek_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.current_int::<i64>().unwrap(), 42);
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.peek_event().unwrap(), Some(JsonEvent::EndObject));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndObject));
+
+    assert_eq!(parser.peek_event().unwrap(), None);
+    assert_eq!(parser.next_event().unwrap(), None);
+}
+
+/// Test that the events of a JSON text can be iterated
+#[test]
+fn events_iterator() {
+    let json = r#"{"name": "Elvis", "age": 42}"#;
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(json.as_bytes()));
+
+    let mut events = parser.events();
+    let mut values = Vec::new();
+    while let Some(e) = events.next() {
+        match e.unwrap() {
+            JsonEvent::FieldName | JsonEvent::ValueString => {
+                values.push(events.parser().current_str().unwrap().to_string())
+            }
+            JsonEvent::ValueInt => {
+                values.push(events.parser().current_int::<i64>().unwrap().to_string())
+            }
+            _ => {}
+        }
+    }
+    assert!(events.next().is_none());
+    assert_eq!(values, vec!["name", "Elvis", "age", "42"]);
+
+    let json = r#"[1, 2}"#;
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(json.as_bytes()));
+    let events = parser.events().collect::<Vec<_>>();
+    assert_eq!(events.len(), 3);
+    assert!(matches!(events[2], Err(ParserError::SyntaxError(_))));
+}
+
+/// Test that tokens contain the values of the events
+#[test]
+fn next_token() {
+    let json = r#"[{"name": "Elvis"}, 42, 1.5, true, false, null]"#;
+    let mut parser = JsonParser::new(PushJsonFeeder::new());
+
+    assert_eq!(parser.next_token().unwrap(), Some(JsonToken::NeedMoreInput));
+    parser.feeder.push_bytes(json.as_bytes());
+    parser.feeder.done();
+
+    let mut tokens = Vec::new();
+    while let Some(t) = parser.next_token().unwrap() {
+        tokens.push(t);
+    }
+
+    assert_eq!(
+        tokens,
+        vec![
+            JsonToken::StartArray,
+            JsonToken::StartObject,
+            JsonToken::FieldName("name".to_string()),
+            JsonToken::ValueString("Elvis".to_string()),
+            JsonToken::EndObject,
+            JsonToken::ValueInt(42),
+            JsonToken::ValueFloat(1.5),
+            JsonToken::ValueBool(true),
+            JsonToken::ValueBool(false),
+            JsonToken::ValueNull,
+            JsonToken::EndArray,
+        ]
+    );
+    assert_eq!(tokens[8].event(), JsonEvent::ValueFalse);
+
+    let json = r#"[99999999999999999999]"#;
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(json.as_bytes()));
+    assert_eq!(parser.next_token().unwrap(), Some(JsonToken::StartArray));
+    assert!(matches!(
+        parser.next_token(),
+        Err(TokenError::InvalidIntValue(_))
+    ));
+}
+
+/// Test that duplicate field names are rejected if the option is enabled
+#[test]
+fn reject_duplicate_keys() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_reject_duplicate_keys(true)
+        .build();
+
+    let json = r#"{"a": {"a": 1, "b": {}}, "b": [{"a": 2}, {"a": 3}], "c": {"b": 4}}"#;
+    let feeder = PushJsonFeeder::new();
+    let r = parse_with_parser(json, &mut JsonParser::new_with_options(feeder, options));
+    assert_json_eq(json, &r);
+
+    let json = r#"{"a": 1, "b": {"c": 2}, "a": 3}"#;
+    let feeder = PushJsonFeeder::new();
+    assert!(matches!(
+        parse_fail_with_parser(
+            json.as_bytes(),
+            &mut JsonParser::new_with_options(feeder, options)
+        ),
+        ParserError::DuplicateKey(k) if k == "a"
+    ));
+
+    let json = r#"{"a": 1, "\u0061": 2}"#;
+    let feeder = PushJsonFeeder::new();
+    assert!(matches!(
+        parse_fail_with_parser(
+            json.as_bytes(),
+            &mut JsonParser::new_with_options(feeder, options)
+        ),
+        ParserError::DuplicateKey(k) if k == "a"
+    ));
+
+    // duplicates are allowed by default
+    let json = r#"{"a": 1, "a": 2}"#;
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(json.as_bytes()));
+    while parser.next_event().unwrap().is_some() {}
+}
+
+/// Test that a parser with an inline stack produces the same events as one
+/// with a stack on the heap and that it respects its maximum depth
+#[test]
+fn inline_stack() {
+    let json = fs::read("tests/fixtures/pass1.txt").unwrap();
+
+    let mut heap_parser = JsonParser::new(SliceJsonFeeder::new(&json));
+    let heap_events = heap_parser.events().collect::<Result<Vec<_>, _>>().unwrap();
+
+    let mut inline_parser = JsonParser::new_with_inline_stack::<32>(SliceJsonFeeder::new(&json));
+    let inline_events = inline_parser
+        .events()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(heap_events, inline_events);
+
+    let json = br#"{"a": [{"b": 1}]}"#;
+    let mut parser = JsonParser::new_with_inline_stack::<3>(SliceJsonFeeder::new(json));
+    assert!(matches!(
+        parser.events().find_map(Result::err),
+        Some(ParserError::MaxDepthExceeded(3))
+    ));
+
+    let mut parser = JsonParser::new_with_options(
+        SliceJsonFeeder::new(json),
+        JsonParserOptionsBuilder::default()
+            .with_max_depth(3)
+            .build(),
+    );
+    assert!(matches!(
+        parser.events().find_map(Result::err),
+        Some(ParserError::MaxDepthExceeded(3))
+    ));
+}
+
+/// Test that strings can be enclosed in single quotes if the option is enabled
+#[test]
+fn single_quotes() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_allow_single_quotes(true)
+        .build();
+
+    let json = r#"{'name': 'Elvis "The King"', "it's": 'it\'s', 'a': ["\"'", 'ä\n']}"#;
+    let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(json.as_bytes()), options);
+    let mut tokens = Vec::new();
+    while let Some(t) = parser.next_token().unwrap() {
+        tokens.push(t);
+    }
+    assert_eq!(
+        tokens,
+        vec![
+            JsonToken::StartObject,
+            JsonToken::FieldName("name".to_string()),
+            JsonToken::ValueString(r#"Elvis "The King""#.to_string()),
+            JsonToken::FieldName("it's".to_string()),
+            JsonToken::ValueString("it's".to_string()),
+            JsonToken::FieldName("a".to_string()),
+            JsonToken::StartArray,
+            JsonToken::ValueString(r#""'"#.to_string()),
+            JsonToken::ValueString("\u{e4}\n".to_string()),
+            JsonToken::EndArray,
+            JsonToken::EndObject,
+        ]
+    );
+
+    // standard JSON is still parsed correctly
+    let json = fs::read_to_string("tests/fixtures/pass1.txt").unwrap();
+    let feeder = PushJsonFeeder::new();
+    let r = parse_with_parser(&json, &mut JsonParser::new_with_options(feeder, options));
+    assert_json_eq(&json, &r);
+
+    // single quotes are not allowed by default
+    let feeder = PushJsonFeeder::new();
+    assert!(matches!(
+        parse_fail_with_parser(b"['a']", &mut JsonParser::new(feeder)),
+        ParserError::SyntaxError(_)
+    ));
+    let feeder = PushJsonFeeder::new();
+    assert!(matches!(
+        parse_fail_with_parser(br#"["\'"]"#, &mut JsonParser::new(feeder)),
+        ParserError::SyntaxError(_)
+    ));
+}
+
+/// Test that unquoted field names are accepted if the option is enabled
+#[test]
+fn unquoted_keys() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_allow_unquoted_keys(true)
+        .build();
+
+    let json = r#"{name:"Elvis", _a1 : {$b: [1, 2]}, "c": null}"#;
+    let expected = r#"{"name":"Elvis", "_a1": {"$b": [1, 2]}, "c": null}"#;
+    let feeder = PushJsonFeeder::new();
+    let r = parse_with_parser(json, &mut JsonParser::new_with_options(feeder, options));
+    assert_json_eq(expected, &r);
+
+    for json in [
+        r#"{1a: 1}"#,
+        r#"{a-b: 1}"#,
+        r#"{a b: 1}"#,
+        r#"{"a": b}"#,
+        r#"[a]"#,
+    ] {
+        let feeder = PushJsonFeeder::new();
+        parse_fail_with_parser(
+            json.as_bytes(),
+            &mut JsonParser::new_with_options(feeder, options),
+        );
+    }
+
+    // unquoted field names are not allowed by default
+    let feeder = PushJsonFeeder::new();
+    assert!(matches!(
+        parse_fail_with_parser(b"{a: 1}", &mut JsonParser::new(feeder)),
+        ParserError::SyntaxError(_)
+    ));
+}
+
+/// Test that floats that are too large are detected
+#[test]
+fn current_float_checked() {
+    let json = b"[1.5, 1e400, -1e400, 1e-400, 1.7976931348623157e308]";
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    let mut values = Vec::new();
+    while let Some(e) = parser.next_event().unwrap() {
+        if e == JsonEvent::ValueFloat {
+            values.push((
+                parser.current_float().unwrap(),
+                parser.current_float_checked().ok(),
+            ));
+        }
+    }
+    assert_eq!(
+        values,
+        vec![
+            (1.5, Some(1.5)),
+            (f64::INFINITY, None),
+            (f64::NEG_INFINITY, None),
+            (0.0, Some(0.0)),
+            (f64::MAX, Some(f64::MAX)),
+        ]
+    );
+}
+
+/// Test that the kind of numbers is detected correctly, even if they are
+/// split across multiple chunks of input
+#[test]
+fn number_kind() {
+    let json = "[0, -0, 12, -12, 0.5, -10.25, 1e5, -0E+3, 1.5e-3] 7 8.5";
+    let mut parser = JsonParser::new_with_options(
+        PushJsonFeeder::new(),
+        JsonParserOptionsBuilder::default()
+            .with_streaming(true)
+            .build(),
+    );
+    let mut kinds = Vec::new();
+    let mut i = 0;
+    while let Some(e) = parser.next_event().unwrap() {
+        match e {
+            JsonEvent::NeedMoreInput => {
+                // feed one byte at a time
+                if i < json.len() {
+                    parser.feeder.push_bytes(&json.as_bytes()[i..=i]);
+                    i += 1;
+                } else {
+                    parser.feeder.done();
+                }
+            }
+            JsonEvent::ValueInt | JsonEvent::ValueFloat => {
+                assert_eq!(
+                    parser.number_is_integer(),
+                    parser.number_kind() == NumberKind::Integer
+                );
+                kinds.push(parser.number_kind());
+            }
+            _ => {}
+        }
+    }
+    assert_eq!(
+        kinds,
+        vec![
+            NumberKind::Integer,
+            NumberKind::Integer,
+            NumberKind::Integer,
+            NumberKind::Integer,
+            NumberKind::Fraction,
+            NumberKind::Fraction,
+            NumberKind::Exponent,
+            NumberKind::Exponent,
+            NumberKind::Exponent,
+            NumberKind::Integer,
+            NumberKind::Fraction,
+        ]
+    );
+}
+
+/// Collect all events and errors of the given stream with error recovery
+fn parse_with_recovery(json: &[u8]) -> Vec<Result<JsonEvent, ParserError>> {
+    let mut parser = JsonParser::new_with_options(
+        SliceJsonFeeder::new(json),
+        JsonParserOptionsBuilder::default()
+            .with_streaming(true)
+            .with_error_recovery(true)
+            .build(),
+    );
+    let mut events = Vec::new();
+    loop {
+        match parser.next_event() {
+            Ok(Some(e)) => events.push(Ok(e)),
+            Ok(None) => break,
+            Err(e) => events.push(Err(e)),
+        }
+    }
+    events
+}
+
+/// Test that the parser continues with the next value after an error if
+/// error recovery is enabled
+#[test]
+fn error_recovery() {
+    let events = parse_with_recovery(b"{\"a\": 1}\n{\"b\" 2}\n[true]\n[\x01]\n{}");
+    assert!(matches!(
+        events.as_slice(),
+        [
+            Ok(JsonEvent::StartObject),
+            Ok(JsonEvent::FieldName),
+            Ok(JsonEvent::ValueInt),
+            Ok(JsonEvent::EndObject),
+            Ok(JsonEvent::StartObject),
+            Ok(JsonEvent::FieldName),
+            Err(ParserError::SyntaxError(_)),
+            Ok(JsonEvent::StartArray),
+            Ok(JsonEvent::ValueTrue),
+            Ok(JsonEvent::EndArray),
+            Ok(JsonEvent::StartArray),
+            Err(ParserError::IllegalInput(0x01, _)),
+            Ok(JsonEvent::StartObject),
+            Ok(JsonEvent::EndObject),
+        ]
+    ));
+
+    // positions are still tracked correctly after recovery
+    let events = parse_with_recovery(b"[1 2]\n[3 4]");
+    assert_eq!(events.len(), 6);
+    assert!(matches!(
+        &events[5],
+        Err(ParserError::SyntaxError(SyntaxErrorDetails {
+            position: Position {
+                offset: 9,
+                line: 2,
+                column: 4
+            },
+            ..
+        }))
+    ));
+
+    // a stream that ends while recovering is complete
+    let events = parse_with_recovery(b"[1 2] 3");
+    assert_eq!(events.len(), 3);
+}
+
+/// Test that a truncated document at the end of a stream is only reported
+/// once if error recovery is enabled
+#[test]
+fn error_recovery_truncated() {
+    let events = parse_with_recovery(b"[\"a\"]\n[\"abc");
+    assert!(matches!(
+        events.as_slice(),
+        [
+            Ok(JsonEvent::StartArray),
+            Ok(JsonEvent::ValueString),
+            Ok(JsonEvent::EndArray),
+            Ok(JsonEvent::StartArray),
+            Err(ParserError::UnexpectedEof { open_containers: 1 }),
+        ]
+    ));
+
+    let events = parse_with_recovery(b"1\n\"abc");
+    assert!(matches!(
+        events.as_slice(),
+        [Ok(JsonEvent::ValueInt), Err(ParserError::NoMoreInput)]
+    ));
+
+    let events = parse_with_recovery(b"{\"a\": [1, {");
+    assert_eq!(events.len(), 6);
+    assert!(matches!(
+        events[5],
+        Err(ParserError::UnexpectedEof { open_containers: 3 })
+    ));
+}
+
+/// Test that error recovery has no effect without streaming mode
+#[test]
+fn error_recovery_without_streaming() {
+    let mut parser = JsonParser::new_with_options(
+        SliceJsonFeeder::new(b"{\"a\" 1} {}"),
+        JsonParserOptionsBuilder::default()
+            .with_error_recovery(true)
+            .build(),
+    );
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+    assert!(matches!(
+        parser.next_event(),
+        Err(ParserError::SyntaxError(_))
+    ));
+    assert!(parser.next_event().is_err());
+}
+
+/// Test that the parser can be used with a boxed feeder whose type has been
+/// erased
+#[test]
+fn boxed_feeder() {
+    let json = r#"{"name": "Elvis", "albums": [1, 2]}"#;
+    let feeders: Vec<Box<dyn JsonFeeder>> = vec![
+        Box::new(SliceJsonFeeder::new(json.as_bytes())),
+        Box::new({
+            let mut f = PushJsonFeeder::new();
+            f.push_bytes(json.as_bytes());
+            f.done();
+            f
+        }),
+    ];
+    for feeder in feeders {
+        let mut parser = JsonParser::new(feeder);
+        let mut events = Vec::new();
+        while let Some(e) = parser.next_event().unwrap() {
+            events.push(e);
+        }
+        assert_eq!(
+            events,
+            vec![
+                JsonEvent::StartObject,
+                JsonEvent::FieldName,
+                JsonEvent::ValueString,
+                JsonEvent::FieldName,
+                JsonEvent::StartArray,
+                JsonEvent::ValueInt,
+                JsonEvent::ValueInt,
+                JsonEvent::EndArray,
+                JsonEvent::EndObject,
+            ]
+        );
+    }
+}
+
+/// Test that long strings are parsed correctly from a contiguous slice (this
+/// exercises the fast path of the `simd` feature)
+#[test]
+fn long_strings_from_slice() {
+    let s = "Elvis \u{0153} 'Presley' ".repeat(20);
+    let json = format!(r#"["{s}", "a\"b\\cä{s}"]"#);
+
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(json.as_bytes()));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    assert_eq!(parser.current_str().unwrap(), s);
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    assert_eq!(parser.current_str().unwrap(), format!("a\"b\\c\u{e4}{s}"));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndArray));
+    assert_eq!(parser.next_event().unwrap(), None);
+
+    // chunks
+    let mut parser = JsonParser::new_with_options(
+        SliceJsonFeeder::new(json.as_bytes()),
+        JsonParserOptionsBuilder::default()
+            .with_max_string_chunk(7)
+            .build(),
+    );
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    while let Some(e) = parser.next_event().unwrap() {
+        match e {
+            JsonEvent::PartialValueString => {
+                assert!(parser.current_str().unwrap().len() >= 7);
+                current.push_str(parser.current_str().unwrap());
+            }
+            JsonEvent::ValueString => {
+                current.push_str(parser.current_str().unwrap());
+                chunks.push(std::mem::take(&mut current));
+            }
+            _ => {}
+        }
+    }
+    assert_eq!(chunks, vec![s.clone(), format!("a\"b\\c\u{e4}{s}")]);
+
+    // maximum length
+    let mut parser = JsonParser::new_with_options(
+        SliceJsonFeeder::new(json.as_bytes()),
+        JsonParserOptionsBuilder::default()
+            .with_max_string_length(10)
+            .build(),
+    );
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    assert!(matches!(
+        parser.next_event(),
+        Err(ParserError::StringTooLong(10))
+    ));
+
+    // control characters
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(b"\"abcdef\x01\""));
+    assert!(matches!(
+        parser.next_event(),
+        Err(ParserError::UnescapedControlCharacter(
+            0x01,
+            Position { offset: 7, .. }
+        ))
+    ));
+}
+
+/// Test that tokens can be collected while feeding the parser
+#[test]
+fn feed_and_collect() {
+    let json = r#"[{"name": "Elvis"}, 42, 1.5, "abcdefghij", null]"#.as_bytes();
+    let mut parser = JsonParser::new(PushJsonFeeder::with_capacity(4));
+    let mut tokens = Vec::new();
+
+    // the feeder only accepts a part of the input
+    assert_eq!(parser.feed_and_collect(json, &mut tokens).unwrap(), 4);
+    assert_eq!(tokens, vec![JsonToken::StartArray, JsonToken::StartObject]);
+
+    let mut pos = 4;
+    while pos < json.len() {
+        pos += parser.feed_and_collect(&json[pos..], &mut tokens).unwrap();
+    }
+    parser.feeder.done();
+    assert_eq!(parser.feed_and_collect(&[], &mut tokens).unwrap(), 0);
+
+    assert_eq!(
+        tokens,
+        vec![
+            JsonToken::StartArray,
+            JsonToken::StartObject,
+            JsonToken::FieldName("name".to_string()),
+            JsonToken::ValueString("Elvis".to_string()),
+            JsonToken::EndObject,
+            JsonToken::ValueInt(42),
+            JsonToken::ValueFloat(1.5),
+            JsonToken::ValueString("abcdefghij".to_string()),
+            JsonToken::ValueNull,
+            JsonToken::EndArray,
+        ]
+    );
+}
+
+/// Test that the index of the current element in its array is tracked
+#[test]
+fn array_index() {
+    let json = r#"[{"a": [true, "xyzxyzxyz"]}, null, "abcdefghi", [], 1.5]"#;
+    let mut parser = JsonParser::new_with_options(
+        SliceJsonFeeder::new(json.as_bytes()),
+        JsonParserOptionsBuilder::default()
+            .with_max_string_chunk(4)
+            .build(),
+    );
+    let mut indexes = Vec::new();
+    while let Some(e) = parser.next_event().unwrap() {
+        indexes.push((e, parser.array_index()));
+    }
+    assert_eq!(
+        indexes,
+        vec![
+            (JsonEvent::StartArray, None),
+            (JsonEvent::StartObject, Some(0)),
+            (JsonEvent::FieldName, None),
+            (JsonEvent::StartArray, None),
+            (JsonEvent::ValueTrue, Some(0)),
+            (JsonEvent::PartialValueString, Some(1)),
+            (JsonEvent::PartialValueString, Some(1)),
+            (JsonEvent::ValueString, Some(1)),
+            (JsonEvent::EndArray, None),
+            (JsonEvent::EndObject, Some(0)),
+            (JsonEvent::ValueNull, Some(1)),
+            (JsonEvent::PartialValueString, Some(2)),
+            (JsonEvent::PartialValueString, Some(2)),
+            (JsonEvent::ValueString, Some(2)),
+            (JsonEvent::StartArray, Some(3)),
+            (JsonEvent::EndArray, Some(3)),
+            (JsonEvent::ValueFloat, Some(4)),
+            (JsonEvent::EndArray, None),
+        ]
+    );
+
+    // peeking already updates the index
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(b"[1, 2]"));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.peek_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.array_index(), Some(1));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.array_index(), Some(1));
+}
+
+/// Test that array indexes are reset after error recovery
+#[test]
+fn array_index_after_recovery() {
+    let mut parser = JsonParser::new_with_options(
+        SliceJsonFeeder::new(b"[1, [2 3]]\n[4, 5]"),
+        JsonParserOptionsBuilder::default()
+            .with_streaming(true)
+            .with_error_recovery(true)
+            .build(),
+    );
+    let mut indexes = Vec::new();
+    loop {
+        match parser.next_event() {
+            Ok(Some(e)) => indexes.push((e, parser.array_index())),
+            Ok(None) => break,
+            Err(_) => {}
+        }
+    }
+    assert_eq!(
+        indexes,
+        vec![
+            (JsonEvent::StartArray, None),
+            (JsonEvent::ValueInt, Some(0)),
+            (JsonEvent::StartArray, Some(1)),
+            (JsonEvent::ValueInt, Some(0)),
+            (JsonEvent::StartArray, None),
+            (JsonEvent::ValueInt, Some(0)),
+            (JsonEvent::ValueInt, Some(1)),
+            (JsonEvent::EndArray, None),
+        ]
+    );
+}
+
+/// Test that the name of the field the current value belongs to is tracked
+#[test]
+fn parent_field_name() {
+    let json = r#"{"a": {"b": [1, {"c": null}], "d": true}, "e": []}"#;
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(json.as_bytes()));
+    let mut names = Vec::new();
+    while let Some(e) = parser.next_event().unwrap() {
+        names.push((e, parser.parent_field_name().map(String::from)));
+    }
+    let s = |n: &str| Some(n.to_string());
+    assert_eq!(
+        names,
+        vec![
+            (JsonEvent::StartObject, None),
+            (JsonEvent::FieldName, s("a")),
+            (JsonEvent::StartObject, s("a")),
+            (JsonEvent::FieldName, s("b")),
+            (JsonEvent::StartArray, s("b")),
+            (JsonEvent::ValueInt, s("b")),
+            (JsonEvent::StartObject, s("b")),
+            (JsonEvent::FieldName, s("c")),
+            (JsonEvent::ValueNull, s("c")),
+            (JsonEvent::EndObject, s("b")),
+            (JsonEvent::EndArray, s("b")),
+            (JsonEvent::FieldName, s("d")),
+            (JsonEvent::ValueTrue, s("d")),
+            (JsonEvent::EndObject, s("a")),
+            (JsonEvent::FieldName, s("e")),
+            (JsonEvent::StartArray, s("e")),
+            (JsonEvent::EndArray, s("e")),
+            (JsonEvent::EndObject, None),
+        ]
+    );
+}
+
+/// Test that integers that are too large can be returned as floats
+#[test]
+fn int_overflow_as_float() {
+    let json = "[9223372036854775807, 9223372036854775808, 18446744073709551615, \
+        18446744073709551616, -9223372036854775808, -9223372036854775809, 0, -12]";
+
+    let events = |int_overflow_as_float| {
+        let mut parser = JsonParser::new_with_options(
+            SliceJsonFeeder::new(json.as_bytes()),
+            JsonParserOptionsBuilder::default()
+                .with_int_overflow_as_float(int_overflow_as_float)
+                .build(),
+        );
+        let mut events = Vec::new();
+        while let Some(e) = parser.next_event().unwrap() {
+            if e == JsonEvent::ValueFloat {
+                assert!(parser.current_float().unwrap().abs() > 9e18);
+            }
+            events.push(e);
+        }
+        events
+    };
+
+    let i = JsonEvent::ValueInt;
+    let f = JsonEvent::ValueFloat;
+    assert_eq!(events(true)[1..9], [i, i, i, f, i, f, i, i]);
+
+    // by default, all integers are returned as such
+    assert_eq!(events(false)[1..9], [i, i, i, i, i, i, i, i]);
+}
+
+/// Test that a parser can be reused with a new feeder
+#[test]
+fn reset_with_feeder() {
+    let first = r#"{"name": "Elvis", "albums": ["#;
+    let second = r#"{"name": "Max", "albums": [1, 2]}"#;
+
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(first.as_bytes()));
+    while parser.next_event().is_ok() {}
+    assert!(parser.parsed_bytes() > 0);
+
+    parser.reset_with_feeder(SliceJsonFeeder::new(second.as_bytes()));
+    assert_eq!(parser.parsed_bytes(), 0);
+    assert_eq!(parser.unclosed_containers(), 0);
+    assert_eq!(parser.parent_field_name(), None);
+
+    let mut tokens = Vec::new();
+    while let Some(t) = parser.next_token().unwrap() {
+        tokens.push(t);
+    }
+    assert_eq!(
+        tokens,
+        vec![
+            JsonToken::StartObject,
+            JsonToken::FieldName("name".to_string()),
+            JsonToken::ValueString("Max".to_string()),
+            JsonToken::FieldName("albums".to_string()),
+            JsonToken::StartArray,
+            JsonToken::ValueInt(1),
+            JsonToken::ValueInt(2),
+            JsonToken::EndArray,
+            JsonToken::EndObject,
+        ]
+    );
+    assert_eq!(parser.parsed_bytes(), second.len());
+
+    // a peeked event is discarded
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(b"1"));
+    assert_eq!(parser.peek_event().unwrap(), Some(JsonEvent::ValueInt));
+    parser.reset_with_feeder(SliceJsonFeeder::new(b"null"));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueNull));
+}
+
+/// Test that the parser validates JSON texts without collecting values in
+/// validate-only mode
+#[test]
+fn validate_only() {
+    let validate = |json: &str| {
+        let mut parser = JsonParser::new_with_options(
+            SliceJsonFeeder::new(json.as_bytes()),
+            JsonParserOptionsBuilder::default()
+                .with_validate_only(true)
+                .with_reject_duplicate_keys(true)
+                .build(),
+        );
+        let mut events = Vec::new();
+        loop {
+            match parser.next_event() {
+                Ok(Some(e)) => {
+                    assert!(parser.current_bytes().is_empty());
+                    events.push(e);
+                }
+                Ok(None) => return Ok(events),
+                Err(e) => return Err(e),
+            }
+        }
+    };
+
+    let json = r#"{"name": "Elvis \"The King\" ä😀", "name": [-1.5e3, 42, true]}"#;
+    assert_eq!(
+        validate(json).unwrap(),
+        vec![
+            JsonEvent::StartObject,
+            JsonEvent::FieldName,
+            JsonEvent::ValueString,
+            JsonEvent::FieldName,
+            JsonEvent::StartArray,
+            JsonEvent::ValueFloat,
+            JsonEvent::ValueInt,
+            JsonEvent::ValueTrue,
+            JsonEvent::EndArray,
+            JsonEvent::EndObject,
+        ]
+    );
+
+    // escape sequences are validated but not collected
+    assert!(validate(r#"["\ud83d\ude00\n", "\u00e4", "\ud83d\ude00"]"#).is_ok());
+
+    // invalid escape sequences and surrogates are still detected
+    assert!(validate(r#""\x""#).is_err());
+    assert!(validate(r#""\u00g0""#).is_err());
+    assert!(validate(r#""\ud83d""#).is_err());
+    assert!(validate(r#""\ude00""#).is_err());
+    assert!(validate(r#"[1, 2"#).is_err());
+    assert!(validate("\"a\x01\"").is_err());
+}
+
+/// Test that the next event can be checked against an expected one
+#[test]
+fn expect() {
+    let mut parser = JsonParser::new(PushJsonFeeder::new());
+    assert!(matches!(
+        parser.expect(JsonEvent::StartArray),
+        Err(ParserError::UnexpectedEvent {
+            expected: JsonEvent::StartArray,
+            got: Some(JsonEvent::NeedMoreInput)
+        })
+    ));
+
+    parser.feeder.push_bytes(b"[1]");
+    parser.feeder.done();
+    parser.expect(JsonEvent::StartArray).unwrap();
+    parser.expect(JsonEvent::ValueInt).unwrap();
+    assert_eq!(parser.current_int::<i32>().unwrap(), 1);
+    parser.expect(JsonEvent::EndArray).unwrap();
+
+    let err = parser.expect(JsonEvent::EndArray).unwrap_err();
+    assert!(matches!(
+        err,
+        ParserError::UnexpectedEvent {
+            expected: JsonEvent::EndArray,
+            got: None
+        }
+    ));
+    assert_eq!(err.to_string(), "expected EndArray but got None");
+}
+
+/// Test that runs of white space are returned as events if enabled
+#[test]
+fn emit_whitespace() {
+    let tokens = |json: &str, streaming: bool| {
+        let mut parser = JsonParser::new_with_options(
+            PushJsonFeeder::new(),
+            JsonParserOptionsBuilder::default()
+                .with_emit_whitespace(true)
+                .with_allow_unquoted_keys(true)
+                .with_streaming(streaming)
+                .build(),
+        );
+        // feed one byte at a time so runs span multiple calls
+        let mut input = json.bytes();
+        let mut tokens = Vec::new();
+        while let Some(token) = parser.next_token().unwrap() {
+            if token == JsonToken::NeedMoreInput {
+                match input.next() {
+                    Some(b) => {
+                        parser.feeder.push_byte(b).unwrap();
+                    }
+                    None => parser.feeder.done(),
+                }
+            } else {
+                tokens.push(token);
+            }
+        }
+        tokens
+    };
+    let ws = |s: &str| JsonToken::Whitespace(s.to_string());
+
+    assert_eq!(
+        tokens("\n{ \"a\" :\t1 , b: [true ,\r\n 2.5]}  ", false),
+        vec![
+            ws("\n"),
+            JsonToken::StartObject,
+            ws(" "),
+            JsonToken::FieldName("a".to_string()),
+            ws(" "),
+            ws("\t"),
+            JsonToken::ValueInt(1),
+            ws(" "),
+            ws(" "),
+            JsonToken::FieldName("b".to_string()),
+            ws(" "),
+            JsonToken::StartArray,
+            JsonToken::ValueBool(true),
+            ws(" "),
+            ws("\r\n "),
+            JsonToken::ValueFloat(2.5),
+            JsonToken::EndArray,
+            JsonToken::EndObject,
+            ws("  "),
+        ]
+    );
+
+    assert_eq!(
+        tokens("1 2\n\"a\"", true),
+        vec![
+            JsonToken::ValueInt(1),
+            ws(" "),
+            JsonToken::ValueInt(2),
+            ws("\n"),
+            JsonToken::ValueString("a".to_string()),
+        ]
+    );
+
+    // white space inside strings is not affected
+    assert_eq!(
+        tokens(r#"[" a "]"#, false),
+        vec![
+            JsonToken::StartArray,
+            JsonToken::ValueString(" a ".to_string()),
+            JsonToken::EndArray,
+        ]
+    );
+}
+
+/// Test that comments are skipped or returned as events
+#[test]
+fn comments() {
+    let tokens = |json: &str, emit: bool| {
+        let mut parser = JsonParser::new_with_options(
+            PushJsonFeeder::new(),
+            JsonParserOptionsBuilder::default()
+                .with_allow_comments(true)
+                .with_emit_comments(emit)
+                .with_allow_unquoted_keys(true)
+                .build(),
+        );
+        let mut input = json.bytes();
+        let mut tokens = Vec::new();
+        loop {
+            match parser.next_token() {
+                Ok(Some(JsonToken::NeedMoreInput)) => match input.next() {
+                    Some(b) => {
+                        parser.feeder.push_byte(b).unwrap();
+                    }
+                    None => parser.feeder.done(),
+                },
+                Ok(Some(token)) => tokens.push(token),
+                Ok(None) => return Ok(tokens),
+                Err(TokenError::Parse(e)) => return Err(e),
+                Err(e) => panic!("{e}"),
+            }
+        }
+    };
+    let comment = |s: &str| JsonToken::Comment(s.to_string());
+
+    let json = "// head\n{a/* x **/: 1// one\n, \"b\": [2.5/**/]} // tail";
+    assert_eq!(
+        tokens(json, true).unwrap(),
+        vec![
+            comment(" head"),
+            JsonToken::StartObject,
+            JsonToken::FieldName("a".to_string()),
+            comment(" x *"),
+            JsonToken::ValueInt(1),
+            comment(" one"),
+            JsonToken::FieldName("b".to_string()),
+            JsonToken::StartArray,
+            JsonToken::ValueFloat(2.5),
+            comment(""),
+            JsonToken::EndArray,
+            JsonToken::EndObject,
+            comment(" tail"),
+        ]
+    );
+    assert_eq!(
+        tokens(json, false).unwrap(),
+        vec![
+            JsonToken::StartObject,
+            JsonToken::FieldName("a".to_string()),
+            JsonToken::ValueInt(1),
+            JsonToken::FieldName("b".to_string()),
+            JsonToken::StartArray,
+            JsonToken::ValueFloat(2.5),
+            JsonToken::EndArray,
+            JsonToken::EndObject,
+        ]
+    );
+
+    // comments are not allowed inside tokens and must be complete
+    assert!(matches!(
+        tokens("[1, /x]", false),
+        Err(ParserError::SyntaxError(_))
+    ));
+    assert!(matches!(
+        tokens("[tr/**/ue]", false),
+        Err(ParserError::SyntaxError(_))
+    ));
+    assert!(matches!(
+        tokens("[1] /* open", false),
+        Err(ParserError::SyntaxError(_))
+    ));
+    assert_eq!(
+        tokens(r#""// no comment""#, true).unwrap(),
+        vec![JsonToken::ValueString("// no comment".to_string())]
+    );
+
+    // comments are not allowed by default
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(b"[1 /* x */]"));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert!(matches!(
+        parser.next_event(),
+        Err(ParserError::SyntaxError(_))
+    ));
+}
+
+/// Test that the raw bytes of strings can be retrieved
+#[test]
+fn current_raw_str() {
+    let raw_strings = |json: &str, max_string_chunk: Option<usize>| {
+        let mut builder = JsonParserOptionsBuilder::default();
+        if let Some(max_string_chunk) = max_string_chunk {
+            builder = builder.with_max_string_chunk(max_string_chunk);
+        }
+        let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), builder.build());
+        let mut input = json.bytes();
+        let mut result = Vec::new();
+        while let Some(e) = parser.next_event().unwrap() {
+            match e {
+                JsonEvent::NeedMoreInput => match input.next() {
+                    Some(b) => {
+                        parser.feeder.push_byte(b).unwrap();
+                    }
+                    None => parser.feeder.done(),
+                },
+                JsonEvent::FieldName | JsonEvent::ValueString | JsonEvent::PartialValueString => {
+                    result.push(String::from_utf8(parser.current_raw_str().to_vec()).unwrap())
+                }
+                _ => {}
+            }
+        }
+        result
+    };
+
+    let json = r#"{"a\"b": ["plain", "Grüße 😀\/", "", "\\"]}"#;
+    assert_eq!(
+        raw_strings(json, None),
+        vec![r#"a\"b"#, "plain", r#"Grüße 😀\/"#, "", r#"\\"#]
+    );
+
+    // chunks of raw strings
+    assert_eq!(
+        raw_strings(r#""abc\n\u0041defgh""#, Some(4)),
+        vec!["abc\\n", "\\u0041def", "gh"]
+    );
+}
+
+/// Test that integers can be parsed as `i128` and `u128`
+#[test]
+fn current_int_128() {
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(
+        br#"[170141183460469231731687303715884105727, -170141183460469231731687303715884105728,
+            340282366920938463463374607431768211455, 170141183460469231731687303715884105728,
+            -170141183460469231731687303715884105729, 340282366920938463463374607431768211456,
+            -1]"#,
+    ));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.current_i128().unwrap(), i128::MAX);
+    assert_eq!(parser.current_u128().unwrap(), i128::MAX as u128);
+
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.current_i128().unwrap(), i128::MIN);
+    assert!(matches!(
+        parser.current_u128(),
+        Err(InvalidIntValueError::Overflow)
+    ));
+
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.current_u128().unwrap(), u128::MAX);
+    assert!(matches!(
+        parser.current_i128(),
+        Err(InvalidIntValueError::Overflow)
+    ));
+
+    // i128::MAX + 1
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert!(matches!(
+        parser.current_i128(),
+        Err(InvalidIntValueError::Overflow)
+    ));
+    assert_eq!(parser.current_u128().unwrap(), i128::MAX as u128 + 1);
+
+    // i128::MIN - 1
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert!(matches!(
+        parser.current_i128(),
+        Err(InvalidIntValueError::Overflow)
+    ));
+
+    // u128::MAX + 1
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert!(matches!(
+        parser.current_u128(),
+        Err(InvalidIntValueError::Overflow)
+    ));
+
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.current_i128().unwrap(), -1);
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndArray));
+}
+
+/// Test that `current_int()` distinguishes values that are out of range
+/// from values that are not integers
+#[test]
+fn current_int_overflow() {
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(
+        br#"[9223372036854775808, -1, -9223372036854775809, "12a"]"#,
+    ));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+
+    // i64::MAX + 1
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert!(matches!(
+        parser.current_int::<i64>(),
+        Err(InvalidIntValueError::Overflow)
+    ));
+    assert_eq!(parser.current_int::<u64>().unwrap(), 9223372036854775808);
+
+    // negative into unsigned
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert!(matches!(
+        parser.current_int::<u64>(),
+        Err(InvalidIntValueError::Overflow)
+    ));
+    assert_eq!(parser.current_int::<i8>().unwrap(), -1);
+
+    // i64::MIN - 1
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert!(matches!(
+        parser.current_int::<i64>(),
+        Err(InvalidIntValueError::Overflow)
+    ));
+
+    // not an integer at all
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    assert!(matches!(
+        parser.current_int::<i64>(),
+        Err(InvalidIntValueError::UnexpectedEvent(Some(
+            JsonEvent::ValueString
+        )))
+    ));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndArray));
+    assert!(matches!(
+        parser.current_int::<i64>(),
+        Err(InvalidIntValueError::UnexpectedEvent(Some(
+            JsonEvent::EndArray
+        )))
+    ));
+}
+
+/// Test that values can only be read after events that have them
+#[test]
+fn current_value_after_wrong_event() {
+    let json = br#"{"a": [1.5, "b", true]}"#;
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    assert_eq!(parser.current_event(), None);
+    assert!(matches!(
+        parser.current_str(),
+        Err(InvalidStringValueError::UnexpectedEvent(None))
+    ));
+
+    let mut strs = Vec::new();
+    while let Some(e) = parser.next_event().unwrap() {
+        assert_eq!(parser.current_event(), Some(e));
+        strs.push(parser.current_str().ok().map(String::from));
+        match e {
+            JsonEvent::ValueFloat => {
+                assert_eq!(parser.current_float().unwrap(), 1.5);
+                assert!(matches!(
+                    parser.current_int::<i64>(),
+                    Err(InvalidIntValueError::Invalid(_))
+                ));
+            }
+            _ => {
+                assert!(matches!(
+                    parser.current_float(),
+                    Err(InvalidFloatValueError::String(
+                        InvalidStringValueError::UnexpectedEvent(Some(ev))
+                    )) if ev == e
+                ));
+            }
+        }
+    }
+    assert_eq!(
+        strs,
+        [
+            None,
+            Some("a".to_string()),
+            None,
+            None,
+            Some("b".to_string()),
+            None,
+            None,
+            None,
+        ]
+    );
+}
+
+/// Test that a parser can take its input from different feeders
+#[test]
+fn next_event_with() {
+    let json = r#"{"name": "Elvis Presley", "albums": [1, 2]}"#.as_bytes();
+
+    let mut parser = JsonParser::stateless();
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::NeedMoreInput));
+
+    // split the input between multiple feeders
+    let mut events = Vec::new();
+    for chunk in json.chunks(7) {
+        let mut feeder = PushJsonFeeder::new();
+        feeder.push_bytes(chunk);
+        loop {
+            match parser.next_event_with(&mut feeder).unwrap() {
+                Some(JsonEvent::NeedMoreInput) => break,
+                Some(e) => events.push(e),
+                None => unreachable!(),
+            }
+        }
+    }
+    let mut rest = SliceJsonFeeder::new(b"");
+    while let Some(e) = parser.next_event_with(&mut rest).unwrap() {
+        events.push(e);
+    }
+
+    let mut expected = Vec::new();
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    while let Some(e) = parser.next_event().unwrap() {
+        expected.push(e);
+    }
+    assert_eq!(events, expected);
+}
+
+/// Test that the parser stops after the first top-level value
+#[test]
+fn stop_after_first_value() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_stop_after_first_value(true)
+        .build();
+
+    // the feeder does not have to be done
+    let mut feeder = PushJsonFeeder::new();
+    feeder.push_bytes(br#"{"a": [1, 2]}{"#);
+    let mut parser = JsonParser::new_with_options(feeder, options);
+    let mut events = Vec::new();
+    while let Some(e) = parser.next_event().unwrap() {
+        events.push(e);
+    }
+    assert_eq!(
+        events,
+        [
+            JsonEvent::StartObject,
+            JsonEvent::FieldName,
+            JsonEvent::StartArray,
+            JsonEvent::ValueInt,
+            JsonEvent::ValueInt,
+            JsonEvent::EndArray,
+            JsonEvent::EndObject,
+        ]
+    );
+    assert!(parser.feeder.has_input());
+    assert!(matches!(parser.next_event(), Err(ParserError::NoMoreInput)));
+
+    // the bytes after the value are not consumed
+    for (json, value, rest) in [
+        (
+            &b"\"a\"\x00\x01"[..],
+            JsonEvent::ValueString,
+            &b"\x00\x01"[..],
+        ),
+        (b"true false", JsonEvent::ValueTrue, b" false"),
+        (b"12 34", JsonEvent::ValueInt, b" 34"),
+        (b"1.5e3\x00", JsonEvent::ValueFloat, b"\x00"),
+        (b"-1[]", JsonEvent::ValueInt, b"[]"),
+    ] {
+        let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+        assert_eq!(parser.next_event().unwrap(), Some(value));
+        assert_eq!(parser.next_event().unwrap(), None);
+        assert_eq!(parser.remaining_input(), rest);
+    }
+
+    // a number at the end of the text is still complete
+    let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(b"42"), options);
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.current_int::<i64>().unwrap(), 42);
+    assert_eq!(parser.next_event().unwrap(), None);
+}
+
+/// Test that an input without any value leads to a dedicated error
+#[test]
+fn empty_input() {
+    assert!(matches!(parse_fail(b""), ParserError::EmptyInput));
+    assert!(matches!(parse_fail(b"   \n  "), ParserError::EmptyInput));
+
+    // a truncated value is not empty
+    assert!(matches!(parse_fail(b"  tr"), ParserError::NoMoreInput));
+
+    // in streaming mode, an empty stream is valid
+    let mut parser = JsonParser::new_with_options(
+        SliceJsonFeeder::new(b"   \n  "),
+        JsonParserOptionsBuilder::default()
+            .with_streaming(true)
+            .build(),
+    );
+    assert_eq!(parser.next_event().unwrap(), None);
+}
+
+/// Test that the parser can be rewound to a checkpoint, even after an error
+#[test]
+fn checkpoint_restore() {
+    let json = br#"{"a": [1, "b"], "c": x}"#;
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+
+    let checkpoint = parser.checkpoint();
+    let mut events = Vec::new();
+    let err = loop {
+        match parser.next_event() {
+            Ok(Some(e)) => events.push(e),
+            Ok(None) => unreachable!(),
+            Err(e) => break e,
+        }
+    };
+    assert!(matches!(err, ParserError::SyntaxError(_)));
+    assert_eq!(parser.current_str().unwrap(), "c");
+
+    parser.restore(checkpoint);
+    assert_eq!(parser.current_str().unwrap(), "a");
+    assert_eq!(parser.parsed_bytes(), 4);
+    assert_eq!(parser.array_index(), None);
+    for e in &events[..3] {
+        assert_eq!(parser.next_event().unwrap(), Some(*e));
+    }
+    assert_eq!(parser.array_index(), Some(1));
+    assert_eq!(parser.parent_field_name(), Some("a"));
+}
+
+/// Test that numbers with leading zeros are only accepted if enabled
+#[test]
+fn allow_leading_zeros() {
+    assert!(matches!(
+        parse_fail(b"[01]"),
+        ParserError::SyntaxError(SyntaxErrorDetails { state: "ZE", .. })
+    ));
+
+    let json = br#"[007, -0042, 00, 0, 000.5e1, 0010000000000000000000]"#;
+    let mut parser = JsonParser::new_with_options(
+        SliceJsonFeeder::new(json),
+        JsonParserOptionsBuilder::default()
+            .with_allow_leading_zeros(true)
+            .build(),
+    );
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    for expected in [7, -42, 0, 0] {
+        assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+        assert_eq!(parser.current_int::<i64>().unwrap(), expected);
+        assert_eq!(parser.number_kind(), NumberKind::Integer);
+    }
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueFloat));
+    assert_eq!(parser.current_float().unwrap(), 5.0);
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(
+        parser.current_int::<u64>().unwrap(),
+        10_000_000_000_000_000_000
+    );
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndArray));
+    assert_eq!(parser.next_event().unwrap(), None);
+}
+
+/// Test that hexadecimal integers are only accepted if enabled
+#[test]
+fn allow_hex_numbers() {
+    assert!(matches!(
+        parse_fail(b"[0x1]"),
+        ParserError::SyntaxError(SyntaxErrorDetails { state: "ZE", .. })
+    ));
+
+    let options = JsonParserOptionsBuilder::default()
+        .with_allow_hex_numbers(true)
+        .build();
+
+    let json =
+        br#"[0x0, 0XfF, -0x10 ,0x7fffffffffffffff, -0x8000000000000000, 0x00000000000000001]"#;
+    let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    for expected in [0, 255, -16, i64::MAX, i64::MIN, 1] {
+        assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+        assert_eq!(parser.current_int::<i64>().unwrap(), expected);
+    }
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndArray));
+    assert_eq!(parser.next_event().unwrap(), None);
+
+    // a hex number at the end of the text
+    let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(b"0xAb"), options);
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.current_bytes(), b"0xAb");
+    assert_eq!(parser.current_int::<u8>().unwrap(), 0xab);
+    assert_eq!(parser.next_event().unwrap(), None);
+
+    // values that do not fit into the requested type
+    let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(b"-0x1"), options);
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert!(matches!(
+        parser.current_int::<u64>(),
+        Err(InvalidIntValueError::Overflow)
+    ));
+
+    // malformed hex numbers
+    for json in [
+        &b"[0x]"[..],
+        b"[0xg]",
+        b"[0x1.5]",
+        b"[0x1g]",
+        b"[1x1]",
+        b"0x",
+    ] {
+        let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+        let err = loop {
+            match parser.next_event() {
+                Ok(Some(_)) => {}
+                Ok(None) => panic!("{json:?} should be rejected"),
+                Err(e) => break e,
+            }
+        };
+        assert!(
+            matches!(err, ParserError::SyntaxError(_) | ParserError::NoMoreInput),
+            "{json:?}: {err:?}"
+        );
+    }
+}
+
+/// Test that data after the top-level value is rejected
+#[test]
+fn trailing_data() {
+    for (json, offset) in [
+        (&br#"{"a":1} garbage"#[..], 8),
+        (b"{}{}", 2),
+        (b"[1]]", 3),
+        (b"1 2", 2),
+        (b"\"a\"\n\x00", 4),
+    ] {
+        match parse_fail(json) {
+            ParserError::TrailingData(position) => assert_eq!(position.offset, offset),
+            e => panic!("{json:?}: {e:?}"),
+        }
+    }
+
+    // trailing white space is fine
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(b"{} \n\t"));
+    while parser.next_event().unwrap().is_some() {}
+}
+
+/// Test that the number of events can be limited
+#[test]
+fn max_events() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_max_events(4)
+        .build();
+
+    // exactly the maximum number of events
+    let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(b"[[]]"), options);
+    let mut n = 0;
+    while parser.next_event().unwrap().is_some() {
+        n += 1;
+    }
+    assert_eq!(n, 4);
+
+    // the event at the end of the input counts as well
+    let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(b"[[[[1"), options);
+    assert!(matches!(
+        parse_fail_with_parser_slice(&mut parser),
+        ParserError::TooManyEvents(4)
+    ));
+
+    // a flat array
+    let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(b"[1,2,3,4,5]"), options);
+    assert!(matches!(
+        parse_fail_with_parser_slice(&mut parser),
+        ParserError::TooManyEvents(4)
+    ));
+
+    // the counter is reset with the feeder
+    parser.reset_with_feeder(SliceJsonFeeder::new(b"[1,2]"));
+    while parser.next_event().unwrap().is_some() {}
+}
+
+/// Call [`JsonParser::next_event()`] until it fails and return the error
+fn parse_fail_with_parser_slice(parser: &mut JsonParser<SliceJsonFeeder>) -> ParserError {
+    loop {
+        match parser.next_event() {
+            Ok(Some(_)) => {}
+            Ok(None) => panic!("End of file before error happened"),
+            Err(e) => return e,
+        }
+    }
+}
+
+/// Test that escaped NUL characters are decoded and do not truncate strings
+#[test]
+fn escaped_nul() {
+    let json = br#"{"k\u0000ey": "a\u0000b"}"#;
+    let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+    assert_eq!(parser.current_str().unwrap(), "k\0ey");
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    assert_eq!(parser.current_str().unwrap().len(), 3);
+    assert_eq!(parser.current_str().unwrap(), "a\0b");
+    assert_eq!(parser.current_bytes(), b"a\0b");
+    assert_eq!(parser.current_raw_str(), br"a\u0000b");
+
+    // the character is escaped again when the JSON text is written
+    let mut out = Vec::new();
+    actson::minify(json, &mut out).unwrap();
+    assert_eq!(out, br#"{"k\u0000ey":"a\u0000b"}"#);
+
+    // the same with a string that is returned in chunks
+    let mut parser = JsonParser::new_with_options(
+        SliceJsonFeeder::new(br#""\u0000\u0000\u0000""#),
+        JsonParserOptionsBuilder::default()
+            .with_max_string_chunk(2)
+            .build(),
+    );
+    let mut value = String::new();
+    while let Some(e) = parser.next_event().unwrap() {
+        if e == JsonEvent::PartialValueString || e == JsonEvent::ValueString {
+            value.push_str(parser.current_str().unwrap());
+        }
+    }
+    assert_eq!(value, "\0\0\0");
+
+    // a raw NUL byte is not allowed in a string
+    assert!(matches!(
+        parse_fail(b"\"a\0b\""),
+        ParserError::UnescapedControlCharacter(0, _)
+    ));
+}
+
+/// Test that unescaped control characters in strings are only accepted if
+/// enabled
+#[test]
+fn allow_control_chars_in_strings() {
+    let json = b"{\"a\tb\": [\"\x00\x1f\", \"line\r\nbreak\\n\"]}\n";
+    assert!(matches!(
+        parse_fail(json),
+        ParserError::UnescapedControlCharacter(b'\t', _)
+    ));
+
+    let mut parser = JsonParser::new_with_options(
+        SliceJsonFeeder::new(json),
+        JsonParserOptionsBuilder::default()
+            .with_allow_control_chars_in_strings(true)
+            .build(),
+    );
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartObject));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::FieldName));
+    assert_eq!(parser.current_str().unwrap(), "a\tb");
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    assert_eq!(parser.current_str().unwrap(), "\0\u{1f}");
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueString));
+    assert_eq!(parser.current_str().unwrap(), "line\r\nbreak\n");
+    assert_eq!(parser.current_raw_str(), b"line\r\nbreak\\n");
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndArray));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndObject));
+    assert_eq!(parser.next_event().unwrap(), None);
+
+    // control characters outside strings are still illegal
+    let mut parser = JsonParser::new_with_options(
+        SliceJsonFeeder::new(b"[\x01]"),
+        JsonParserOptionsBuilder::default()
+            .with_allow_control_chars_in_strings(true)
+            .build(),
+    );
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    assert!(matches!(
+        parser.next_event(),
+        Err(ParserError::IllegalInput(0x01, _))
+    ));
+}
+
+/// Test that field names and scalar values are combined into entries
+#[test]
+fn entry_events() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_entry_events(true)
+        .build();
+
+    for i in 1..=3 {
+        let json = fs::read_to_string(format!("tests/fixtures/pass{}.txt", i)).unwrap();
+        for capacity in [1, 7, 1024] {
+            let mut parser =
+                JsonParser::new_with_options(PushJsonFeeder::with_capacity(capacity), options);
+            let outjson = parse_with_parser(&json, &mut parser);
+            assert_json_eq(&json, &outjson);
+        }
+    }
+
+    let json = br#"{"a": "b", "c": {"d": 1.5}, "e": [true], "f": null}"#;
+    let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+    let mut tokens = Vec::new();
+    while let Some(token) = parser.next_token().unwrap() {
+        tokens.push(token);
+    }
+    assert_eq!(
+        tokens,
+        vec![
+            JsonToken::StartObject,
+            JsonToken::Entry(
+                "a".to_string(),
+                Box::new(JsonToken::ValueString("b".to_string()))
+            ),
+            JsonToken::FieldName("c".to_string()),
+            JsonToken::StartObject,
+            JsonToken::Entry("d".to_string(), Box::new(JsonToken::ValueFloat(1.5))),
+            JsonToken::EndObject,
+            JsonToken::FieldName("e".to_string()),
+            JsonToken::StartArray,
+            JsonToken::ValueBool(true),
+            JsonToken::EndArray,
+            JsonToken::Entry("f".to_string(), Box::new(JsonToken::ValueNull)),
+            JsonToken::EndObject,
+        ]
+    );
+
+    // the values of events that cannot be combined are kept
+    let options = JsonParserOptionsBuilder::default()
+        .with_entry_events(true)
+        .with_emit_whitespace(true)
+        .with_max_string_chunk(2)
+        .build();
+    let json = br#"{"a": "bcd","e":"fghi"}"#;
+    let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+    let mut events = Vec::new();
+    while let Some(e) = parser.next_event().unwrap() {
+        events.push((e, parser.current_str().unwrap_or_default().to_string()));
+        assert!(parser.entry_value().is_none());
+    }
+    assert_eq!(
+        events,
+        [
+            (JsonEvent::StartObject, "".to_string()),
+            (JsonEvent::FieldName, "a".to_string()),
+            (JsonEvent::Whitespace, " ".to_string()),
+            (JsonEvent::PartialValueString, "bc".to_string()),
+            (JsonEvent::ValueString, "d".to_string()),
+            (JsonEvent::FieldName, "e".to_string()),
+            (JsonEvent::PartialValueString, "fg".to_string()),
+            (JsonEvent::PartialValueString, "hi".to_string()),
+            (JsonEvent::ValueString, "".to_string()),
+            (JsonEvent::EndObject, "".to_string()),
+        ]
+    );
+}
+
+/// Test that a leading plus sign is only accepted if enabled
+#[test]
+fn allow_plus_sign() {
+    assert!(matches!(
+        parse_fail(b"[+1]"),
+        ParserError::SyntaxError(SyntaxErrorDetails { state: "AR", .. })
+    ));
+
+    let options = JsonParserOptionsBuilder::default()
+        .with_allow_plus_sign(true)
+        .build();
+    let json = br#"{"a": +1, "b": [+1.5, +1e3, +0, -2, 1e+2, +18446744073709551615]}"#;
+    let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+    let mut values = Vec::new();
+    while let Some(e) = parser.next_event().unwrap() {
+        match e {
+            JsonEvent::ValueInt => values.push(parser.current_i128().unwrap() as f64),
+            JsonEvent::ValueFloat => values.push(parser.current_float().unwrap()),
+            _ => {}
+        }
+    }
+    assert_eq!(
+        values,
+        [1.0, 1.5, 1000.0, 0.0, -2.0, 100.0, 18446744073709551615.0]
+    );
+
+    let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(b"+42"), options);
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.current_int::<i8>().unwrap(), 42);
+    assert!(matches!(parser.current_int::<i64>(), Ok(42)));
+
+    let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(b"+300"), options);
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert!(matches!(
+        parser.current_int::<u8>(),
+        Err(InvalidIntValueError::Overflow)
+    ));
+
+    // a bare plus sign behaves like a bare minus sign
+    assert!(matches!(parse_fail(b"-"), ParserError::NoMoreInput));
+    let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), options);
+    assert!(matches!(
+        parse_fail_with_parser(b"+", &mut parser),
+        ParserError::NoMoreInput
+    ));
+
+    for json in [
+        &b"[+]"[..],
+        b"++1",
+        b"+-1",
+        b"-+1",
+        b"[1, +]",
+        br#"{+"a": 1}"#,
+    ] {
+        let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), options);
+        assert!(matches!(
+            parse_fail_with_parser(json, &mut parser),
+            ParserError::SyntaxError(_)
+        ));
+    }
+}
+
+/// Test that the parser keeps the last event it has returned apart from
+/// [`JsonEvent::NeedMoreInput`]
+#[test]
+fn current_event() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_allow_comments(true)
+        .with_emit_comments(true)
+        .with_emit_whitespace(true)
+        .build();
+    let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), options);
+    parser.feeder.push_bytes(b"[1,");
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::NeedMoreInput));
+    assert_eq!(parser.current_event(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.last_event(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.current_int::<i64>().unwrap(), 1);
+
+    parser.feeder.push_bytes(b"true] // end");
+    parser.feeder.done();
+    assert_eq!(parser.peek_event().unwrap(), Some(JsonEvent::ValueTrue));
+    assert_eq!(parser.current_event(), Some(JsonEvent::ValueTrue));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueTrue));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndArray));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::Whitespace));
+    assert_eq!(parser.current_event(), Some(JsonEvent::Whitespace));
+
+    // events at the end of the text
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::Comment));
+    assert_eq!(parser.current_event(), Some(JsonEvent::Comment));
+    assert_eq!(parser.current_str().unwrap(), " end");
+    assert_eq!(parser.next_event().unwrap(), None);
+
+    parser.reset_with_feeder(PushJsonFeeder::new());
+    assert_eq!(parser.current_event(), None);
+    assert_eq!(parser.last_event(), None);
+}
+
+/// Test that strings are validated to be UTF-8 while they are parsed
+#[test]
+fn validate_utf8() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_validate_utf8(true)
+        .build();
+
+    let valid = "{\"Grüße\": [\"€\", \"𝄞\", \"a\\u00fcb\"]}";
+    let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), options);
+    assert_json_eq(valid, &parse_with_parser(valid, &mut parser));
+
+    let invalid: [(&[u8], usize); 8] = [
+        (b"[\"\x80\"]", 2),
+        (b"[\"a\xc3\"]", 4),
+        (b"[\"\xc0\x80\"]", 2),
+        (b"[\"\xed\xa0\x80\"]", 3),
+        (b"[\"\xf0\x9f\x98\\n\"]", 5),
+        (b"{\"\xf5\": 1}", 2),
+        // ASCII characters inside multi-byte sequences
+        (b"[\"\xc3A\xa9\"]", 3),
+        (b"[\"ab\xe2\x82cdefghijklmnopqrstuvwxyz\"]", 6),
+    ];
+    for (json, offset) in invalid {
+        let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), options);
+        match parse_fail_with_parser(json, &mut parser) {
+            ParserError::InvalidUtf8(position) => {
+                assert_eq!(position.offset, offset, "{}", json.escape_ascii())
+            }
+            e => panic!("unexpected error for {}: {e}", json.escape_ascii()),
+        }
+
+        // feed all bytes at once so runs of characters can be processed
+        // together
+        let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+        let err = loop {
+            match parser.next_event() {
+                Ok(Some(_)) => {}
+                Ok(None) => panic!("no error for {}", json.escape_ascii()),
+                Err(e) => break e,
+            }
+        };
+        assert!(
+            matches!(err, ParserError::InvalidUtf8(position) if position.offset == offset),
+            "{}: {err}",
+            json.escape_ascii()
+        );
+
+        // without validation, the parser accepts the input
+        let mut parser = JsonParser::new(SliceJsonFeeder::new(json));
+        while parser.next_event().unwrap().is_some() {}
+    }
+}
+
+/// Test that top-level values are enclosed in document events
+#[test]
+fn document_events() {
+    let json = "1 [2, {\"a\": 3}]\n\"x\" {} 4.5";
+    let options = JsonParserOptionsBuilder::default()
+        .with_streaming(true)
+        .with_document_events(true)
+        .build();
+
+    let expected = vec![
+        JsonEvent::StartDocument,
+        JsonEvent::ValueInt,
+        JsonEvent::EndDocument,
+        JsonEvent::StartDocument,
+        JsonEvent::StartArray,
+        JsonEvent::ValueInt,
+        JsonEvent::StartObject,
+        JsonEvent::FieldName,
+        JsonEvent::ValueInt,
+        JsonEvent::EndObject,
+        JsonEvent::EndArray,
+        JsonEvent::EndDocument,
+        JsonEvent::StartDocument,
+        JsonEvent::ValueString,
+        JsonEvent::EndDocument,
+        JsonEvent::StartDocument,
+        JsonEvent::StartObject,
+        JsonEvent::EndObject,
+        JsonEvent::EndDocument,
+        JsonEvent::StartDocument,
+        JsonEvent::ValueFloat,
+        JsonEvent::EndDocument,
+    ];
+
+    // feed the parser byte by byte
+    let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), options);
+    let mut events = Vec::new();
+    let mut bytes = json.bytes();
+    while let Some(e) = parser.next_event().unwrap() {
+        match e {
+            JsonEvent::NeedMoreInput => match bytes.next() {
+                Some(b) => {
+                    parser.feeder.push_bytes(&[b]);
+                }
+                None => parser.feeder.done(),
+            },
+            JsonEvent::StartDocument => {
+                assert_eq!(parser.current_event(), Some(JsonEvent::StartDocument));
+                events.push(e);
+            }
+            JsonEvent::ValueString => {
+                assert_eq!(parser.current_str().unwrap(), "x");
+                events.push(e);
+            }
+            _ => events.push(e),
+        }
+    }
+    assert_eq!(events, expected);
+
+    // peeking must not change the events
+    let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(json.as_bytes()), options);
+    let mut events = Vec::new();
+    while let Some(e) = parser.peek_event().unwrap() {
+        assert_eq!(parser.next_event().unwrap(), Some(e));
+        if e == JsonEvent::ValueFloat {
+            assert_eq!(parser.current_float().unwrap(), 4.5);
+        }
+        events.push(e);
+    }
+    assert_eq!(events, expected);
+}
+
+/// Test that a pending number at the end of the available input can be
+/// finalized without ending the JSON text
+#[test]
+fn flush() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_streaming(true)
+        .build();
+    let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), options);
+
+    // nothing is pending
+    assert_eq!(parser.flush().unwrap(), None);
+
+    parser.feeder.push_bytes(b"42");
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::NeedMoreInput));
+    assert_eq!(parser.flush().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.current_int::<i64>().unwrap(), 42);
+    assert_eq!(parser.flush().unwrap(), None);
+
+    // an incomplete number cannot be finalized
+    parser.feeder.push_bytes(b" 1.5e");
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::NeedMoreInput));
+    assert_eq!(parser.flush().unwrap(), None);
+    parser.feeder.push_bytes(b"2");
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::NeedMoreInput));
+    assert_eq!(parser.flush().unwrap(), Some(JsonEvent::ValueFloat));
+    assert_eq!(parser.current_float().unwrap(), 150.0);
+
+    // numbers inside containers are never pending
+    parser.feeder.push_bytes(b" [7");
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::StartArray));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::NeedMoreInput));
+    assert_eq!(parser.flush().unwrap(), None);
+    parser.feeder.push_bytes(b"]");
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndArray));
+
+    parser.feeder.done();
+    assert_eq!(parser.next_event().unwrap(), None);
+}
+
+/// Test that a flushed number is enclosed in document events
+#[test]
+fn flush_document_events() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_document_events(true)
+        .build();
+    let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), options);
+    parser.feeder.push_bytes(b"-0");
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::NeedMoreInput));
+    assert_eq!(parser.flush().unwrap(), Some(JsonEvent::StartDocument));
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::ValueInt));
+    assert_eq!(parser.current_int::<i64>().unwrap(), 0);
+    assert_eq!(parser.next_event().unwrap(), Some(JsonEvent::EndDocument));
+    parser.feeder.done();
+    assert_eq!(parser.next_event().unwrap(), None);
+}
+
+/// Collect the source text of all tokens in the given JSON text
+fn collect_spans(json: &str, options: JsonParserOptions, chunk_size: usize) -> Vec<String> {
+    let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), options);
+    let mut chunks = json.as_bytes().chunks(chunk_size);
+    let mut result = Vec::new();
+    while let Some(e) = parser.next_event().unwrap() {
+        match e {
+            JsonEvent::NeedMoreInput => match chunks.next() {
+                Some(c) => {
+                    parser.feeder.push_bytes(c);
+                }
+                None => parser.feeder.done(),
+            },
+            JsonEvent::FieldName
+            | JsonEvent::ValueString
+            | JsonEvent::ValueInt
+            | JsonEvent::ValueFloat
+            | JsonEvent::ValueTrue
+            | JsonEvent::ValueFalse
+            | JsonEvent::ValueNull
+            | JsonEvent::Entry => result.push(json[parser.current_span()].to_string()),
+            _ => {}
+        }
+    }
+    result
+}
+
+/// Test that the spans of all tokens are correct
+#[test]
+fn current_span() {
+    let json = "{\"a\\\"b\": [1, -2.5e+3,true ,false,null, \"Grüße\\u00e9\", 0], \"c\" : {}}";
+    let expected = vec![
+        "\"a\\\"b\"",
+        "1",
+        "-2.5e+3",
+        "true",
+        "false",
+        "null",
+        "\"Grüße\\u00e9\"",
+        "0",
+        "\"c\"",
+    ];
+    for chunk_size in [1, 3, json.len()] {
+        let spans = collect_spans(json, JsonParserOptions::default(), chunk_size);
+        assert_eq!(spans, expected);
+    }
+
+    // top-level values in streaming mode
+    let json = "12 \"x\"3.0\nnull -7";
+    let options = JsonParserOptionsBuilder::default()
+        .with_streaming(true)
+        .build();
+    for chunk_size in [1, json.len()] {
+        let spans = collect_spans(json, options, chunk_size);
+        assert_eq!(spans, vec!["12", "\"x\"", "3.0", "null", "-7"]);
+    }
+
+    // entries, unquoted keys, and hexadecimal numbers
+    let json = "{a: 0x1F, 'b': 'c', d: [2]}";
+    let options = JsonParserOptionsBuilder::relaxed()
+        .with_allow_hex_numbers(true)
+        .with_entry_events(true)
+        .build();
+    for chunk_size in [1, json.len()] {
+        let spans = collect_spans(json, options, chunk_size);
+        assert_eq!(spans, vec!["a: 0x1F", "'b': 'c'", "d", "2"]);
+    }
+}
+
+/// Test that top-level values must be separated by the record separator
+#[test]
+fn record_separator() {
+    fn parse(json: &[u8], separator: u8) -> Result<Vec<JsonEvent>, ParserError> {
+        let options = JsonParserOptionsBuilder::default()
+            .with_streaming(true)
+            .with_record_separator(Some(separator))
+            .build();
+        let mut parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+        let mut events = Vec::new();
+        while let Some(e) = parser.next_event()? {
+            events.push(e);
+        }
+        Ok(events)
+    }
+
+    let expected = vec![
+        JsonEvent::StartObject,
+        JsonEvent::EndObject,
+        JsonEvent::ValueInt,
+        JsonEvent::ValueString,
+        JsonEvent::ValueTrue,
+        JsonEvent::ValueFloat,
+    ];
+    assert_eq!(
+        parse(b"{}\n1\n \"a\" \n\ntrue\r\n1.5\n", b'\n').unwrap(),
+        expected
+    );
+    assert_eq!(
+        parse(b"\x1e{}\x1e1\x1e\"a\"\x1e true\x1e1.5", 0x1e).unwrap(),
+        expected
+    );
+
+    // values that are not separated
+    for json in [
+        &b"{} {}"[..],
+        b"{}{}",
+        b"1 2",
+        b"\"a\"\"b\"",
+        b"[1]\t[2]",
+        b"true null",
+    ] {
+        assert!(matches!(
+            parse(json, b'\n'),
+            Err(ParserError::SyntaxError(_))
+        ));
+    }
+    assert!(matches!(
+        parse(b"\x1e1 2", 0x1e),
+        Err(ParserError::SyntaxError(_))
+    ));
+
+    // the separator is only allowed between top-level values
+    assert!(matches!(
+        parse(b"[1\x1e]", 0x1e),
+        Err(ParserError::IllegalInput(0x1e, _))
+    ));
+    assert!(matches!(
+        parse(b"tr\x1eue", 0x1e),
+        Err(ParserError::SyntaxError(_))
+    ));
+}
+
+/// Test that arrays and objects may end with a comma
+#[test]
+fn trailing_commas() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_allow_trailing_commas(true)
+        .build();
+    for (json, expected) in [
+        ("[1,]", "[1]"),
+        ("[1 , ]", "[1]"),
+        ("{\"a\": 1,}", "{\"a\": 1}"),
+        (
+            "{\"a\": [true, {},], \"b\": {\"c\": null,},}",
+            "{\"a\": [true, {}], \"b\": {\"c\": null}}",
+        ),
+    ] {
+        let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), options);
+        assert_json_eq(expected, &parse_with_parser(json, &mut parser));
+    }
+
+    for json in [
+        &b"[,]"[..],
+        b"{,}",
+        b"[1,,]",
+        b"{\"a\": 1,,}",
+        b"[1,}",
+        b"{\"a\",}",
+    ] {
+        let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), options);
+        parse_fail_with_parser(json, &mut parser);
+    }
+
+    // trailing commas are rejected by default
+    let mut parser = JsonParser::new(PushJsonFeeder::new());
+    parse_fail_with_parser(b"[1,]", &mut parser);
+}
+
+/// Test that a byte order mark at the beginning of the input is skipped
+#[test]
+fn skip_bom() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_skip_bom(true)
+        .build();
+    let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), options);
+    assert_json_eq(
+        "{\"a\": 1}",
+        &parse_with_parser("\u{feff}{\"a\": 1}", &mut parser),
+    );
+    let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), options);
+    assert_json_eq("[1]", &parse_with_parser("[1]", &mut parser));
+
+    for json in [
+        &b"\xef\xbb\xbf\xef\xbb\xbf[1]"[..],
+        b"\xef\xbb[1]",
+        b" \xef\xbb\xbf[1]",
+        b"[\xef\xbb\xbf1]",
+    ] {
+        let mut parser = JsonParser::new_with_options(PushJsonFeeder::new(), options);
+        parse_fail_with_parser(json, &mut parser);
+    }
+
+    // a byte order mark is rejected by default
+    let mut parser = JsonParser::new(PushJsonFeeder::new());
+    parse_fail_with_parser(b"\xef\xbb\xbf[1]", &mut parser);
+}
+
+/// Test that the relaxed preset accepts typical hand-written JSON
+#[test]
+fn relaxed() {
+    let json = "\u{feff}{\n  // comment\n  name: 'Elvis',\n  /* albums */ 'albums': [1, 2,],\n}";
+    let mut parser = JsonParser::new_with_options(
+        PushJsonFeeder::new(),
+        JsonParserOptionsBuilder::relaxed().build(),
+    );
+    assert_json_eq(
+        "{\"name\": \"Elvis\", \"albums\": [1, 2]}",
+        &parse_with_parser(json, &mut parser),
+    );
+
+    let options = JsonParserOptionsBuilder::relaxed().build();
+    assert!(options.allow_comments());
+    assert!(options.allow_single_quotes());
+    assert!(options.allow_unquoted_keys());
+    assert!(options.allow_trailing_commas());
+    assert!(options.skip_bom());
+    assert!(!options.streaming());
+}
+
+/// Test that a builder can be created from existing options
+#[test]
+fn options_builder_from() {
+    let options = JsonParserOptionsBuilder::relaxed()
+        .with_max_events(10)
+        .with_record_separator(Some(b'\n'))
+        .build();
+    assert_eq!(JsonParserOptionsBuilder::from(options).build(), options);
+
+    let changed = JsonParserOptionsBuilder::from(options)
+        .with_allow_comments(false)
+        .build();
+    assert_ne!(changed, options);
+    assert!(!changed.allow_comments());
+    assert!(changed.allow_single_quotes());
+    assert_eq!(changed.max_events(), Some(10));
+    assert_eq!(changed.record_separator(), Some(b'\n'));
+}
+
+/// Test that options can be serialized and deserialized
+#[cfg(feature = "serde")]
+#[test]
+fn options_serde() {
+    let options = JsonParserOptionsBuilder::default()
+        .with_streaming(true)
+        .with_max_string_length(100)
+        .with_document_events(true)
+        .build();
+    let json = serde_json::to_string(&options).unwrap();
+    let deserialized: JsonParserOptions = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, options);
+
+    // missing fields get their default values
+    let deserialized: JsonParserOptions = serde_json::from_str("{}").unwrap();
+    assert_eq!(deserialized, JsonParserOptions::default());
+}
+
+/// Collect all events of the given parser together with the depth of the
+/// container stack and the array index at the time they are returned
+fn collect_container_positions(
+    mut parser: JsonParser<SliceJsonFeeder>,
+) -> Vec<(JsonEvent, usize, Option<usize>)> {
+    let mut result = Vec::new();
+    while let Some(e) = parser.next_event().unwrap() {
+        result.push((e, parser.container_stack().count(), parser.array_index()));
+    }
+    result
+}
+
+/// Test that containers are tracked correctly if field names and values are
+/// combined into entries
+#[test]
+fn entry_events_containers() {
+    let json = br#"{"a": {"b": 1}, "c": [2, {"e": 3}], "d": 4}"#;
+    let options = JsonParserOptionsBuilder::default()
+        .with_entry_events(true)
+        .build();
+    let parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+    assert_eq!(
+        collect_container_positions(parser),
+        vec![
+            (JsonEvent::StartObject, 1, None),
+            (JsonEvent::FieldName, 1, None),
+            (JsonEvent::StartObject, 2, None),
+            (JsonEvent::Entry, 2, None),
+            (JsonEvent::EndObject, 1, None),
+            (JsonEvent::FieldName, 1, None),
+            (JsonEvent::StartArray, 2, None),
+            (JsonEvent::ValueInt, 2, Some(0)),
+            (JsonEvent::StartObject, 3, Some(1)),
+            (JsonEvent::Entry, 3, None),
+            (JsonEvent::EndObject, 2, Some(1)),
+            (JsonEvent::EndArray, 1, None),
+            (JsonEvent::Entry, 1, None),
+            (JsonEvent::EndObject, 0, None),
+        ]
+    );
+}
+
+/// Test that containers are tracked correctly if top-level values are
+/// enclosed in document events
+#[test]
+fn document_events_containers() {
+    let json = b"1 [2, [3]] {}";
+    let options = JsonParserOptionsBuilder::default()
+        .with_streaming(true)
+        .with_document_events(true)
+        .build();
+    let parser = JsonParser::new_with_options(SliceJsonFeeder::new(json), options);
+    assert_eq!(
+        collect_container_positions(parser),
+        vec![
+            (JsonEvent::StartDocument, 0, None),
+            (JsonEvent::ValueInt, 0, None),
+            (JsonEvent::EndDocument, 0, None),
+            (JsonEvent::StartDocument, 0, None),
+            (JsonEvent::StartArray, 1, None),
+            (JsonEvent::ValueInt, 1, Some(0)),
+            (JsonEvent::StartArray, 2, Some(1)),
+            (JsonEvent::ValueInt, 2, Some(0)),
+            (JsonEvent::EndArray, 1, Some(1)),
+            (JsonEvent::EndArray, 0, None),
+            (JsonEvent::EndDocument, 0, None),
+            (JsonEvent::StartDocument, 0, None),
+            (JsonEvent::StartObject, 1, None),
+            (JsonEvent::EndObject, 0, None),
+            (JsonEvent::EndDocument, 0, None),
+        ]
+    );
+}
@@ -1 +1,2 @@
 mod asyncbufreader;
+mod stream;
@@ -0,0 +1,75 @@
+use std::future::poll_fn;
+use std::pin::Pin;
+
+use futures_core::{FusedStream, Stream};
+use serde_json::Value;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, BufReader};
+
+use crate::prettyprinter::PrettyPrinter;
+use actson::parser::ParserError;
+use actson::tokio::{AsyncBufReaderJsonFeeder, JsonEventStream, JsonStreamError};
+use actson::{JsonEvent, JsonParser};
+
+async fn next<T>(stream: &mut JsonEventStream<T>) -> Option<Result<JsonEvent, JsonStreamError>>
+where
+    T: tokio::io::AsyncRead + Unpin,
+{
+    poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+}
+
+/// Test if [`JsonEventStream`] can be used to parse a JSON file
+#[tokio::test]
+async fn parse_from_file() {
+    let expected;
+    {
+        let mut buf = Vec::new();
+        let mut file = File::open("tests/fixtures/pass1.txt").await.unwrap();
+        file.read_to_end(&mut buf).await.unwrap();
+        expected = String::from_utf8(buf).unwrap();
+    }
+
+    let file = File::open("tests/fixtures/pass1.txt").await.unwrap();
+    let reader = BufReader::with_capacity(32, file);
+
+    let feeder = AsyncBufReaderJsonFeeder::new(reader);
+    let mut stream = JsonParser::new(feeder).into_stream();
+    let mut prettyprinter = PrettyPrinter::new();
+
+    while let Some(e) = next(&mut stream).await {
+        let e = e.unwrap();
+        assert_ne!(e, JsonEvent::NeedMoreInput);
+        prettyprinter.on_event(e, stream.parser()).unwrap();
+    }
+    assert!(stream.is_terminated());
+    assert!(next(&mut stream).await.is_none());
+
+    let actual = prettyprinter.get_result();
+
+    let em: Value = serde_json::from_str(&expected).unwrap();
+    let am: Value = serde_json::from_str(actual).unwrap();
+    assert_eq!(em, am);
+}
+
+/// Test that the stream ends after a parser error
+#[tokio::test]
+async fn syntax_error() {
+    let json = r#"{"name"}"#.as_bytes();
+    let feeder = AsyncBufReaderJsonFeeder::new(BufReader::new(json));
+    let mut stream = JsonParser::new(feeder).into_stream();
+
+    assert_eq!(
+        next(&mut stream).await.unwrap().unwrap(),
+        JsonEvent::StartObject
+    );
+    assert_eq!(
+        next(&mut stream).await.unwrap().unwrap(),
+        JsonEvent::FieldName
+    );
+    assert!(matches!(
+        next(&mut stream).await,
+        Some(Err(JsonStreamError::Parse(ParserError::SyntaxError(_))))
+    ));
+    assert!(stream.is_terminated());
+    assert!(next(&mut stream).await.is_none());
+}